@@ -9,8 +9,13 @@ pub(crate) mod metadata;
 pub(crate) mod parse;
 pub(crate) mod request;
 pub(crate) mod response;
+mod schema;
 
-use self::{metadata::Metadata, request::Request, response::Response};
+use self::{
+    metadata::{MatrixVersionLiteral, Metadata},
+    request::Request,
+    response::Response,
+};
 use crate::util;
 
 /// The result of processing the `ruma_api` macro, ready for output back to source code.
@@ -37,6 +42,21 @@ pub fn expand_all(api: Api) -> syn::Result<TokenStream> {
     let method = &metadata.method;
     let name = &metadata.name;
     let path = &metadata.path;
+    let unstable_paths = &metadata.unstable_paths;
+    let stable_paths = metadata.stable_paths.iter().map(|stable_path| {
+        let added = &stable_path.added.variant;
+        let path = &stable_path.path;
+        quote! { (#ruma_api::MatrixVersion::#added, #path) }
+    });
+    let optional_version = |version: &Option<MatrixVersionLiteral>| match version {
+        Some(v) => {
+            let variant = &v.variant;
+            quote! { ::std::option::Option::Some(#ruma_api::MatrixVersion::#variant) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+    let deprecated = optional_version(&metadata.deprecated);
+    let removed = optional_version(&metadata.removed);
     let rate_limited: TokenStream = metadata
         .rate_limited
         .iter()
@@ -63,6 +83,8 @@ pub fn expand_all(api: Api) -> syn::Result<TokenStream> {
         })
         .collect();
 
+    let schema = schema::expand_schema(&api, &ruma_api);
+
     let error_ty =
         api.error_ty.map_or_else(|| quote! { #ruma_api::error::Void }, |err_ty| quote! { #err_ty });
 
@@ -78,10 +100,24 @@ pub fn expand_all(api: Api) -> syn::Result<TokenStream> {
             method: #http::Method::#method,
             name: #name,
             path: #path,
+            unstable_paths: &[ #( #unstable_paths ),* ],
+            stable_paths: &[ #( #stable_paths ),* ],
+            deprecated: #deprecated,
+            removed: #removed,
             #rate_limited
             #authentication
         };
 
+        #[cfg(feature = "schema")]
+        #ruma_api::exports::register_endpoint!(
+            self::METADATA,
+            ::std::option::Option::Some(self::SCHEMA)
+        );
+        #[cfg(not(feature = "schema"))]
+        #ruma_api::exports::register_endpoint!(self::METADATA, ::std::option::Option::None);
+
+        #schema
+
         #request
         #response
     })
@@ -1,18 +1,30 @@
 //! Details of the `#[ruma_api(...)]` attributes.
 
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
-    Ident, Token,
+    Ident, LitStr, Token,
 };
 
-/// Like syn::MetaNameValue, but expects an identifier as the value. Also, we don't care about the
-/// the span of the equals sign, so we don't have the `eq_token` field from syn::MetaNameValue.
+/// Like syn::MetaNameValue, but expects an identifier or a string literal as the value. Also, we
+/// don't care about the the span of the equals sign, so we don't have the `eq_token` field from
+/// syn::MetaNameValue.
 pub struct MetaNameValue {
     /// The part left of the equals sign
     pub name: Ident,
 
     /// The part right of the equals sign
-    pub value: Ident,
+    pub value: MetaValue,
+}
+
+/// The value of a name-value pair in a ruma_api attribute.
+pub enum MetaValue {
+    /// An identifier, like `CONTENT_TYPE` in `#[ruma_api(header = CONTENT_TYPE)]`
+    Ident(Ident),
+
+    /// A string literal, like `"x-custom"` in `#[ruma_api(header = "x-custom")]`
+    Str(LitStr),
 }
 
 /// Like syn::Meta, but only parses ruma_api attributes
@@ -41,9 +53,65 @@ impl Parse for Meta {
 
         if input.peek(Token![=]) {
             let _: Token![=] = input.parse()?;
-            Ok(Meta::NameValue(MetaNameValue { name: ident, value: input.parse()? }))
+            let value = if input.peek(LitStr) {
+                MetaValue::Str(input.parse()?)
+            } else {
+                MetaValue::Ident(input.parse()?)
+            };
+
+            Ok(Meta::NameValue(MetaNameValue { name: ident, value }))
         } else {
             Ok(Meta::Word(ident))
         }
     }
 }
+
+/// The name of a header field, from a `#[ruma_api(header = ...)]` attribute.
+pub enum HeaderName {
+    /// The name of a constant in `http::header`, like `CONTENT_TYPE`.
+    Const(Ident),
+
+    /// A lowercase header name, like `x-custom`, for headers without a constant in `http::header`.
+    Custom(LitStr),
+}
+
+impl HeaderName {
+    /// Creates a `HeaderName` from the value of a `header` attribute, checking that custom header
+    /// names are valid.
+    pub fn new(value: MetaValue) -> syn::Result<Self> {
+        match value {
+            MetaValue::Ident(ident) => Ok(Self::Const(ident)),
+            MetaValue::Str(lit) => {
+                let name = lit.value();
+                let is_valid_char = |c: char| {
+                    c.is_ascii_lowercase() || c.is_ascii_digit() || "!#$%&'*+-.^_`|~".contains(c)
+                };
+
+                if name.is_empty() || !name.chars().all(is_valid_char) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "custom header names must be non-empty and lowercase",
+                    ));
+                }
+
+                Ok(Self::Custom(lit))
+            }
+        }
+    }
+
+    /// The lowercase name of the header, as it appears in HTTP messages.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Const(ident) => ident.to_string().to_lowercase().replace('_', "-"),
+            Self::Custom(lit) => lit.value(),
+        }
+    }
+
+    /// Produces an expression of type `http::header::HeaderName` for the header.
+    pub fn to_tokens(&self, http: &TokenStream) -> TokenStream {
+        match self {
+            Self::Const(ident) => quote! { #http::header::#ident },
+            Self::Custom(lit) => quote! { #http::header::HeaderName::from_static(#lit) },
+        }
+    }
+}
@@ -1,10 +1,12 @@
 //! Details of the `metadata` section of the procedural macro.
 
+use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
-    braced,
+    braced, bracketed,
     parse::{Parse, ParseStream},
-    Attribute, Ident, LitBool, LitStr, Token,
+    punctuated::Punctuated,
+    Attribute, Ident, LitBool, LitFloat, LitStr, Token,
 };
 
 use crate::util;
@@ -15,6 +17,10 @@ mod kw {
     syn::custom_keyword!(method);
     syn::custom_keyword!(name);
     syn::custom_keyword!(path);
+    syn::custom_keyword!(unstable_paths);
+    syn::custom_keyword!(stable_paths);
+    syn::custom_keyword!(deprecated);
+    syn::custom_keyword!(removed);
     syn::custom_keyword!(rate_limited);
     syn::custom_keyword!(authentication);
 }
@@ -40,8 +46,23 @@ pub struct Metadata {
     pub name: LitStr,
 
     /// The path field.
+    ///
+    /// If it was omitted, this is the first of the `stable_paths`, or the first of the
+    /// `unstable_paths` if there are no stable paths.
     pub path: LitStr,
 
+    /// The unstable_paths field.
+    pub unstable_paths: Vec<LitStr>,
+
+    /// The stable_paths field.
+    pub stable_paths: Vec<StablePath>,
+
+    /// The deprecated field.
+    pub deprecated: Option<MatrixVersionLiteral>,
+
+    /// The removed field.
+    pub removed: Option<MatrixVersionLiteral>,
+
     /// The rate_limited field.
     pub rate_limited: Vec<MetadataField<LitBool>>,
 
@@ -49,6 +70,58 @@ pub struct Metadata {
     pub authentication: Vec<MetadataField<Ident>>,
 }
 
+/// A version of the Matrix specification, written as a float literal like `1.1`.
+pub struct MatrixVersionLiteral {
+    /// The literal as written in the macro input.
+    lit: LitFloat,
+
+    /// The name of the corresponding `MatrixVersion` variant.
+    pub variant: Ident,
+
+    /// The major and minor version numbers.
+    parts: (u8, u8),
+}
+
+impl Parse for MatrixVersionLiteral {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lit: LitFloat = input.parse()?;
+
+        let (variant, parts) = match lit.base10_digits() {
+            "1.0" => ("V1_0", (1, 0)),
+            "1.1" => ("V1_1", (1, 1)),
+            "1.2" => ("V1_2", (1, 2)),
+            _ => return Err(syn::Error::new_spanned(&lit, "unknown Matrix version")),
+        };
+
+        Ok(Self { variant: Ident::new(variant, lit.span()), parts, lit })
+    }
+}
+
+impl ToTokens for MatrixVersionLiteral {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.lit.to_tokens(tokens);
+    }
+}
+
+/// A stable path of an endpoint, written as `1.1 => "/path"`.
+pub struct StablePath {
+    /// The version of the specification that added the path.
+    pub added: MatrixVersionLiteral,
+
+    /// The path.
+    pub path: LitStr,
+}
+
+impl Parse for StablePath {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let added = input.parse()?;
+        let _: Token![=>] = input.parse()?;
+        let path = parse_path(input)?;
+
+        Ok(Self { added, path })
+    }
+}
+
 fn set_field<T: ToTokens>(field: &mut Option<T>, value: T) -> syn::Result<()> {
     match field {
         Some(existing_value) => {
@@ -78,6 +151,10 @@ impl Parse for Metadata {
         let mut method = None;
         let mut name = None;
         let mut path = None;
+        let mut unstable_paths = None;
+        let mut stable_paths: Option<Vec<StablePath>> = None;
+        let mut deprecated = None;
+        let mut removed = None;
         let mut rate_limited = vec![];
         let mut authentication = vec![];
 
@@ -87,6 +164,10 @@ impl Parse for Metadata {
                 FieldValue::Method(m) => set_field(&mut method, m)?,
                 FieldValue::Name(n) => set_field(&mut name, n)?,
                 FieldValue::Path(p) => set_field(&mut path, p)?,
+                FieldValue::UnstablePaths(kw, p) => set_list_field(&mut unstable_paths, kw, p)?,
+                FieldValue::StablePaths(kw, p) => set_list_field(&mut stable_paths, kw, p)?,
+                FieldValue::Deprecated(v) => set_field(&mut deprecated, v)?,
+                FieldValue::Removed(v) => set_field(&mut removed, v)?,
                 FieldValue::RateLimited(value, attrs) => {
                     rate_limited.push(MetadataField { attrs, value })
                 }
@@ -99,11 +180,42 @@ impl Parse for Metadata {
         let missing_field =
             |name| syn::Error::new_spanned(metadata_kw, format!("missing field `{}`", name));
 
+        let unstable_paths = unstable_paths.unwrap_or_default();
+        let stable_paths = stable_paths.unwrap_or_default();
+
+        for pair in stable_paths.windows(2) {
+            if pair[0].added.parts >= pair[1].added.parts {
+                return Err(syn::Error::new_spanned(
+                    &pair[1].added,
+                    "stable paths must be given in ascending order of versions",
+                ));
+            }
+        }
+
+        let path = path
+            .or_else(|| stable_paths.first().map(|p| p.path.clone()))
+            .or_else(|| unstable_paths.first().cloned())
+            .ok_or_else(|| missing_field("path"))?;
+
+        let params = path_params(&path);
+        for versioned_path in unstable_paths.iter().chain(stable_paths.iter().map(|p| &p.path)) {
+            if path_params(versioned_path) != params {
+                return Err(syn::Error::new_spanned(
+                    versioned_path,
+                    "all paths of an endpoint must have the same path parameters, in the same order",
+                ));
+            }
+        }
+
         Ok(Self {
             description: description.ok_or_else(|| missing_field("description"))?,
             method: method.ok_or_else(|| missing_field("method"))?,
             name: name.ok_or_else(|| missing_field("name"))?,
-            path: path.ok_or_else(|| missing_field("path"))?,
+            path,
+            unstable_paths,
+            stable_paths,
+            deprecated,
+            removed,
             rate_limited: if rate_limited.is_empty() {
                 return Err(missing_field("rate_limited"));
             } else {
@@ -118,11 +230,53 @@ impl Parse for Metadata {
     }
 }
 
+/// Returns the names of the path parameters of the given path, in order.
+fn path_params(path: &LitStr) -> Vec<String> {
+    path.value().split('/').filter(|s| s.starts_with(':')).map(|s| s[1..].to_owned()).collect()
+}
+
+fn set_list_field<T>(field: &mut Option<Vec<T>>, keyword: Ident, value: Vec<T>) -> syn::Result<()> {
+    match field {
+        Some(_) => Err(syn::Error::new_spanned(keyword, "duplicate field assignment")),
+        None => {
+            *field = Some(value);
+            Ok(())
+        }
+    }
+}
+
+fn parse_list<T>(
+    input: ParseStream<'_>,
+    parser: fn(ParseStream<'_>) -> syn::Result<T>,
+) -> syn::Result<Vec<T>> {
+    let list;
+    bracketed!(list in input);
+
+    Ok(Punctuated::<T, Token![,]>::parse_terminated_with(&list, parser)?.into_iter().collect())
+}
+
+fn parse_path(input: ParseStream<'_>) -> syn::Result<LitStr> {
+    let path: LitStr = input.parse()?;
+
+    if !util::is_valid_endpoint_path(&path.value()) {
+        return Err(syn::Error::new_spanned(
+            &path,
+            "path may only contain printable ASCII characters with no spaces",
+        ));
+    }
+
+    Ok(path)
+}
+
 enum Field {
     Description,
     Method,
     Name,
     Path,
+    UnstablePaths,
+    StablePaths,
+    Deprecated,
+    Removed,
     RateLimited,
     Authentication,
 }
@@ -143,6 +297,18 @@ impl Parse for Field {
         } else if lookahead.peek(kw::path) {
             let _: kw::path = input.parse()?;
             Ok(Self::Path)
+        } else if lookahead.peek(kw::unstable_paths) {
+            let _: kw::unstable_paths = input.parse()?;
+            Ok(Self::UnstablePaths)
+        } else if lookahead.peek(kw::stable_paths) {
+            let _: kw::stable_paths = input.parse()?;
+            Ok(Self::StablePaths)
+        } else if lookahead.peek(kw::deprecated) {
+            let _: kw::deprecated = input.parse()?;
+            Ok(Self::Deprecated)
+        } else if lookahead.peek(kw::removed) {
+            let _: kw::removed = input.parse()?;
+            Ok(Self::Removed)
         } else if lookahead.peek(kw::rate_limited) {
             let _: kw::rate_limited = input.parse()?;
             Ok(Self::RateLimited)
@@ -160,6 +326,10 @@ enum FieldValue {
     Method(Ident),
     Name(LitStr),
     Path(LitStr),
+    UnstablePaths(Ident, Vec<LitStr>),
+    StablePaths(Ident, Vec<StablePath>),
+    Deprecated(MatrixVersionLiteral),
+    Removed(MatrixVersionLiteral),
     RateLimited(LitBool, Vec<Attribute>),
     Authentication(Ident, Vec<Attribute>),
 }
@@ -175,6 +345,7 @@ impl Parse for FieldValue {
                 ));
             }
         }
+        let field_name: Ident = input.fork().call(syn::ext::IdentExt::parse_any)?;
         let field: Field = input.parse()?;
         let _: Token![:] = input.parse()?;
 
@@ -182,18 +353,13 @@ impl Parse for FieldValue {
             Field::Description => Self::Description(input.parse()?),
             Field::Method => Self::Method(input.parse()?),
             Field::Name => Self::Name(input.parse()?),
-            Field::Path => {
-                let path: LitStr = input.parse()?;
-
-                if !util::is_valid_endpoint_path(&path.value()) {
-                    return Err(syn::Error::new_spanned(
-                        &path,
-                        "path may only contain printable ASCII characters with no spaces",
-                    ));
-                }
-
-                Self::Path(path)
+            Field::Path => Self::Path(parse_path(input)?),
+            Field::UnstablePaths => Self::UnstablePaths(field_name, parse_list(input, parse_path)?),
+            Field::StablePaths => {
+                Self::StablePaths(field_name, parse_list(input, StablePath::parse)?)
             }
+            Field::Deprecated => Self::Deprecated(input.parse()?),
+            Field::Removed => Self::Removed(input.parse()?),
             Field::RateLimited => Self::RateLimited(input.parse()?, attrs),
             Field::Authentication => Self::Authentication(input.parse()?, attrs),
         })
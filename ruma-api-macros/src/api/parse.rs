@@ -9,7 +9,7 @@ use syn::{
 };
 
 use super::{
-    attribute::{Meta, MetaNameValue},
+    attribute::{HeaderName, Meta, MetaNameValue, MetaValue},
     request::{RequestField, RequestFieldKind, RequestLifetimes},
     response::{ResponseField, ResponseFieldKind},
     Api, Metadata, Request, Response,
@@ -211,7 +211,8 @@ fn parse_request(input: ParseStream<'_>, attributes: Vec<Attribute>) -> syn::Res
     Ok(Request { attributes, fields, lifetimes })
 }
 
-fn parse_response(input: ParseStream<'_>, attributes: Vec<Attribute>) -> syn::Result<Response> {
+fn parse_response(input: ParseStream<'_>, mut attributes: Vec<Attribute>) -> syn::Result<Response> {
+    let status = response_status(&mut attributes)?;
     let response_kw: kw::response = input.parse()?;
     let _: Token![:] = input.parse()?;
     let fields;
@@ -290,7 +291,7 @@ fn parse_response(input: ParseStream<'_>, attributes: Vec<Attribute>) -> syn::Re
         ));
     }
 
-    Ok(Response { attributes, fields })
+    Ok(Response { attributes, fields, status })
 }
 
 fn has_lifetime(ty: &Type) -> bool {
@@ -333,8 +334,8 @@ fn req_res_meta_word<T>(
 
 fn req_res_name_value<T>(
     name: Ident,
-    value: Ident,
-    header: &mut Option<Ident>,
+    value: MetaValue,
+    header: &mut Option<HeaderName>,
     field_kind: T,
 ) -> syn::Result<T> {
     if name != "header" {
@@ -344,6 +345,45 @@ fn req_res_name_value<T>(
         ));
     }
 
-    *header = Some(value);
+    *header = Some(HeaderName::new(value)?);
     Ok(field_kind)
 }
+
+/// Removes the `#[ruma_api(status = ...)]` attribute from the given attributes of the response
+/// section and returns the name of the status code, if any.
+fn response_status(attributes: &mut Vec<Attribute>) -> syn::Result<Option<Ident>> {
+    let mut status = None;
+
+    for attr in mem::take(attributes) {
+        let meta = match Meta::from_attribute(&attr)? {
+            Some(m) => m,
+            None => {
+                attributes.push(attr);
+                continue;
+            }
+        };
+
+        match meta {
+            Meta::NameValue(MetaNameValue { name, value: MetaValue::Ident(value) })
+                if name == "status" =>
+            {
+                if status.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "There can only be one status attribute",
+                    ));
+                }
+
+                status = Some(value);
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Invalid #[ruma_api] argument on the response, expected `status = NAME`",
+                ));
+            }
+        }
+    }
+
+    Ok(status)
+}
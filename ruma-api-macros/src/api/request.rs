@@ -8,7 +8,7 @@ use syn::{spanned::Spanned, Attribute, Field, Ident, Lifetime};
 
 use crate::util;
 
-use super::metadata::Metadata;
+use super::{attribute::HeaderName, metadata::Metadata};
 
 #[derive(Debug, Default)]
 pub(super) struct RequestLifetimes {
@@ -198,8 +198,10 @@ impl Request {
 
         let extract_request_path = if self.has_path_fields() {
             quote! {
-                let path_segments: ::std::vec::Vec<&::std::primitive::str> =
-                    request.uri().path()[1..].split('/').collect();
+                let path_segments = #ruma_api::try_deserialize!(
+                    request,
+                    #ruma_api::path::extract(&self::METADATA, request.uri().path()),
+                );
             }
         } else {
             TokenStream::new()
@@ -230,6 +232,7 @@ impl Request {
                 };
 
                 let field_name = &field.ident;
+                let header_name = header_name.to_tokens(&http);
 
                 match &field.ty {
                     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -238,16 +241,20 @@ impl Request {
                         quote! {
                             if let Some(header_val) = self.#field_name.as_ref() {
                                 req_headers.insert(
-                                    #http::header::#header_name,
-                                    #http::header::HeaderValue::from_str(header_val)?,
+                                    #header_name,
+                                    #http::header::HeaderValue::from_str(
+                                        &::std::string::ToString::to_string(header_val),
+                                    )?,
                                 );
                             }
                         }
                     }
                     _ => quote! {
                         req_headers.insert(
-                            #http::header::#header_name,
-                            #http::header::HeaderValue::from_str(self.#field_name.as_ref())?,
+                            #header_name,
+                            #http::header::HeaderValue::from_str(
+                                &::std::string::ToString::to_string(&self.#field_name),
+                            )?,
                         );
                     },
                 }
@@ -319,16 +326,29 @@ impl Request {
                 };
 
                 let field_name = &field.ident;
-                let header_name_string = header_name.to_string();
+                let header_name_string = header_name.name();
+                let header_name = header_name.to_tokens(&http);
+
+                let parse_value = quote! {
+                    #ruma_api::try_deserialize!(
+                        request,
+                        str_value.parse().map_err(|e| {
+                            #ruma_api::error::HeaderDeserializationError::InvalidHeader {
+                                header: #header_name_string.into(),
+                                message: ::std::string::ToString::to_string(&e),
+                            }
+                        })
+                    )
+                };
 
                 let (some_case, none_case) = match &field.ty {
                     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
                         if segments.last().unwrap().ident == "Option" =>
                     {
-                        (quote! { Some(str_value.to_owned()) }, quote! { None })
+                        (quote! { Some(#parse_value) }, quote! { None })
                     }
                     _ => (
-                        quote! { str_value.to_owned() },
+                        parse_value,
                         quote! {
                             // FIXME: Not a missing json field, a missing header!
                             return Err(#ruma_api::error::RequestDeserializationError::new(
@@ -343,7 +363,7 @@ impl Request {
                 };
 
                 quote! {
-                    #field_name: match headers.get(#http::header::#header_name) {
+                    #field_name: match headers.get(#header_name) {
                         Some(header_value) => {
                             let str_value =
                                 #ruma_api::try_deserialize!(request, header_value.to_str());
@@ -508,6 +528,74 @@ impl Request {
             })
             .collect();
 
+        let build_http_request = |body: TokenStream| {
+            quote! {
+                let metadata = self::METADATA;
+                let path = metadata.select_path(considering_versions)?;
+
+                let mut req_builder = #http::Request::builder()
+                    .method(#http::Method::#method)
+                    .uri(::std::format!(
+                        "{}{}{}",
+                        base_url.strip_suffix('/').unwrap_or(base_url),
+                        #request_path_string,
+                        #request_query_string,
+                    ))
+                    .header(
+                        #ruma_api::exports::http::header::CONTENT_TYPE,
+                        "application/json",
+                    );
+
+                let mut req_headers = req_builder
+                    .headers_mut()
+                    .expect("`http::RequestBuilder` is in unusable state");
+
+                #header_kvs
+
+                let http_request = req_builder.body(#body)?;
+
+                Ok(http_request)
+            }
+        };
+        let build_request = build_http_request(request_body);
+
+        let raw_body_impls = if let Some(field) = self.newtype_raw_body_field() {
+            let raw_body_field_name =
+                field.ident.as_ref().expect("expected field to have an identifier");
+            let build_raw_body_request = build_http_request(quote! { body });
+
+            quote! {
+                #[automatically_derived]
+                #[cfg(feature = "client")]
+                impl #request_lifetimes #ruma_api::OutgoingRawBodyRequest
+                    for Request #request_lifetimes
+                {
+                    fn try_into_http_request_with_body<B>(
+                        self,
+                        base_url: &::std::primitive::str,
+                        access_token: ::std::option::Option<&str>,
+                        considering_versions: &[#ruma_api::MatrixVersion],
+                        body: B,
+                    ) -> ::std::result::Result<
+                        #http::Request<B>,
+                        #ruma_api::error::IntoHttpError,
+                    > {
+                        if !self.#raw_body_field_name.is_empty() {
+                            return Err(#ruma_api::error::IntoHttpError::RawBodyNotEmpty);
+                        }
+
+                        #build_raw_body_request
+                    }
+                }
+
+                #[automatically_derived]
+                #[cfg(feature = "server")]
+                impl #ruma_api::IncomingRawBodyRequest for #incoming_request_type {}
+            }
+        } else {
+            TokenStream::new()
+        };
+
         quote! {
             #[doc = #docs]
             #[derive(Debug, Clone, #ruma_serde::Outgoing, #ruma_serde::_FakeDeriveSerde)]
@@ -517,6 +605,7 @@ impl Request {
             pub struct Request #request_generics #request_def
 
             #non_auth_endpoint_impls
+            #raw_body_impls
 
             #request_body_struct
             #request_query_struct
@@ -537,30 +626,19 @@ impl Request {
                     #http::Request<Vec<u8>>,
                     #ruma_api::error::IntoHttpError,
                 > {
-                    let metadata = self::METADATA;
-
-                    let mut req_builder = #http::Request::builder()
-                        .method(#http::Method::#method)
-                        .uri(::std::format!(
-                            "{}{}{}",
-                            base_url.strip_suffix('/').unwrap_or(base_url),
-                            #request_path_string,
-                            #request_query_string,
-                        ))
-                        .header(
-                            #ruma_api::exports::http::header::CONTENT_TYPE,
-                            "application/json",
-                        );
-
-                    let mut req_headers = req_builder
-                        .headers_mut()
-                        .expect("`http::RequestBuilder` is in unusable state");
-
-                    #header_kvs
-
-                    let http_request = req_builder.body(#request_body)?;
+                    self.try_into_http_request_with_versions(base_url, access_token, &[])
+                }
 
-                    Ok(http_request)
+                fn try_into_http_request_with_versions(
+                    self,
+                    base_url: &::std::primitive::str,
+                    access_token: ::std::option::Option<&str>,
+                    considering_versions: &[#ruma_api::MatrixVersion],
+                ) -> ::std::result::Result<
+                    #http::Request<Vec<u8>>,
+                    #ruma_api::error::IntoHttpError,
+                > {
+                    #build_request
                 }
             }
 
@@ -702,63 +780,40 @@ impl Request {
                 "number of declared path parameters needs to match amount of placeholders in path"
             );
 
-            let format_call = {
-                let mut format_string = path_string.clone();
-                let mut format_args = Vec::new();
-
-                while let Some(start_of_segment) = format_string.find(':') {
-                    // ':' should only ever appear at the start of a segment
-                    assert_eq!(&format_string[start_of_segment - 1..start_of_segment], "/");
+            let path_vars: Vec<_> = path_string[1..]
+                .split('/')
+                .filter(|s| s.starts_with(':'))
+                .map(|segment| Ident::new(&segment[1..], Span::call_site()))
+                .collect();
 
-                    let end_of_segment = match format_string[start_of_segment..].find('/') {
-                        Some(rel_pos) => start_of_segment + rel_pos,
-                        None => format_string.len(),
-                    };
-
-                    let path_var = Ident::new(
-                        &format_string[start_of_segment + 1..end_of_segment],
-                        Span::call_site(),
-                    );
-                    format_args.push(quote! {
+            let fill_call = {
+                let args = path_vars.iter().map(|path_var| {
+                    quote! {
                         #percent_encoding::utf8_percent_encode(
                             &self.#path_var.to_string(),
                             #percent_encoding::NON_ALPHANUMERIC,
                         )
-                    });
-                    format_string.replace_range(start_of_segment..end_of_segment, "{}");
-                }
+                        .to_string()
+                    }
+                });
 
                 quote! {
-                    format_args!(#format_string, #(#format_args),*)
+                    #ruma_api::path::fill(path, &[#(#args),*])
                 }
             };
 
-            let path_fields =
-                path_string[1..].split('/').enumerate().filter(|(_, s)| s.starts_with(':')).map(
-                    |(i, segment)| {
-                        let path_var = &segment[1..];
-                        let path_var_ident = Ident::new(path_var, Span::call_site());
-                        quote! {
-                            #path_var_ident: {
-                                let segment = path_segments[#i].as_bytes();
-                                let decoded = #ruma_api::try_deserialize!(
-                                    request,
-                                    #percent_encoding::percent_decode(segment)
-                                        .decode_utf8(),
-                                );
-
-                                #ruma_api::try_deserialize!(
-                                    request,
-                                    ::std::convert::TryFrom::try_from(&*decoded),
-                                )
-                            }
-                        }
-                    },
-                );
+            let path_fields = path_vars.iter().enumerate().map(|(i, path_var)| {
+                quote! {
+                    #path_var: #ruma_api::try_deserialize!(
+                        request,
+                        ::std::convert::TryFrom::try_from(&*path_segments[#i]),
+                    )
+                }
+            });
 
-            (format_call, quote! { #(#path_fields,)* })
+            (fill_call, quote! { #(#path_fields,)* })
         } else {
-            (quote! { metadata.path.to_owned() }, TokenStream::new())
+            (quote! { path }, TokenStream::new())
         }
     }
 }
@@ -769,7 +824,7 @@ pub(crate) enum RequestField {
     Body(Field),
 
     /// Data in an HTTP header.
-    Header(Field, Ident),
+    Header(Field, HeaderName),
 
     /// A specific data type in the body of the request.
     NewtypeBody(Field),
@@ -789,7 +844,7 @@ pub(crate) enum RequestField {
 
 impl RequestField {
     /// Creates a new `RequestField`.
-    pub fn new(kind: RequestFieldKind, field: Field, header: Option<Ident>) -> Self {
+    pub fn new(kind: RequestFieldKind, field: Field, header: Option<HeaderName>) -> Self {
         match kind {
             RequestFieldKind::Body => RequestField::Body(field),
             RequestFieldKind::Header => {
@@ -4,7 +4,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{spanned::Spanned, Attribute, Field, Ident};
 
-use super::metadata::Metadata;
+use super::{attribute::HeaderName, metadata::Metadata};
 
 /// The result of processing the `response` section of the macro.
 pub(crate) struct Response {
@@ -13,6 +13,10 @@ pub(crate) struct Response {
 
     /// The fields of the response.
     pub fields: Vec<ResponseField>,
+
+    /// The name of the `http::StatusCode` constant for successful responses, from the
+    /// `#[ruma_api(status = ...)]` attribute.
+    pub status: Option<Ident>,
 }
 
 impl Response {
@@ -47,30 +51,51 @@ impl Response {
                     }
                 }
                 ResponseField::Header(_, header_name) => {
-                    let optional_header = match &field.ty {
+                    let header_name_string = header_name.name();
+                    let header_name = header_name.to_tokens(&http);
+
+                    let parse_value = quote! {
+                        #ruma_api::try_deserialize!(
+                            response,
+                            str_value.parse().map_err(|e| {
+                                #ruma_api::error::HeaderDeserializationError::InvalidHeader {
+                                    header: #header_name_string.into(),
+                                    message: ::std::string::ToString::to_string(&e),
+                                }
+                            })
+                        )
+                    };
+
+                    let (some_case, none_case) = match &field.ty {
                         syn::Type::Path(syn::TypePath {
                             path: syn::Path { segments, .. }, ..
                         }) if segments.last().unwrap().ident == "Option" => {
+                            (quote! { Some(#parse_value) }, quote! { None })
+                        }
+                        _ => (
+                            parse_value,
                             quote! {
-                                #field_name: #ruma_api::try_deserialize!(
+                                return Err(#ruma_api::error::ResponseDeserializationError::new(
+                                    #ruma_api::error::HeaderDeserializationError::MissingHeader(
+                                        #header_name_string.into()
+                                    ),
                                     response,
-                                    headers.remove(#http::header::#header_name)
-                                        .map(|h| h.to_str().map(|s| s.to_owned()))
-                                        .transpose()
                                 )
+                                .into())
+                            },
+                        ),
+                    };
+
+                    quote_spanned! {span=>
+                        #field_name: match headers.remove(#header_name) {
+                            Some(header_value) => {
+                                let str_value =
+                                    #ruma_api::try_deserialize!(response, header_value.to_str());
+                                #some_case
                             }
+                            None => #none_case,
                         }
-                        _ => quote! {
-                            #field_name: #ruma_api::try_deserialize!(
-                                response,
-                                headers.remove(#http::header::#header_name)
-                                    .expect("response missing expected header")
-                                    .to_str()
-                            )
-                            .to_owned()
-                        },
-                    };
-                    quote_spanned! {span=> #optional_header }
+                    }
                 }
                 ResponseField::NewtypeBody(_) => {
                     quote_spanned! {span=>
@@ -105,6 +130,7 @@ impl Response {
                 let field_name =
                     field.ident.as_ref().expect("expected field to have an identifier");
                 let span = field.span();
+                let header_name = header_name.to_tokens(&http);
 
                 let optional_header = match &field.ty {
                     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -112,20 +138,18 @@ impl Response {
                     {
                         quote! {
                             if let Some(header) = response.#field_name {
-                                headers
-                                    .insert(
-                                        #http::header::#header_name,
-                                        header.parse()?,
-                                    );
+                                headers.insert(
+                                    #header_name,
+                                    ::std::string::ToString::to_string(&header).parse()?,
+                                );
                             }
                         }
                     }
                     _ => quote! {
-                        headers
-                            .insert(
-                                #http::header::#header_name,
-                                response.#field_name.parse()?,
-                            );
+                        headers.insert(
+                            #header_name,
+                            ::std::string::ToString::to_string(&response.#field_name).parse()?,
+                        );
                     },
                 };
 
@@ -153,7 +177,7 @@ impl Response {
         let body = if let Some(field) = self.newtype_body_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
             let span = field.span();
-            quote_spanned!(span=> response.#field_name)
+            quote_spanned!(span=> ResponseBody(response.#field_name))
         } else {
             let fields = self.fields.iter().filter_map(|response_field| {
                 if let ResponseField::Body(ref field) = *response_field {
@@ -238,6 +262,9 @@ impl Response {
                 TokenStream::new()
             };
 
+        let set_status =
+            self.status.as_ref().map(|status| quote! { .status(#http::StatusCode::#status) });
+
         let response_init_fields = self.init_fields(&ruma_api);
         let serialize_response_headers = self.apply_header_fields(&ruma_api);
 
@@ -270,6 +297,60 @@ impl Response {
             struct ResponseBody #def
         };
 
+        let build_http_response = |body: TokenStream| {
+            quote! {
+                let mut resp_builder = #http::Response::builder()
+                    #set_status
+                    .header(#http::header::CONTENT_TYPE, "application/json");
+
+                let mut headers = resp_builder
+                    .headers_mut()
+                    .expect("`http::ResponseBuilder` is in unusable state");
+                #serialize_response_headers
+
+                // This cannot fail because we parse each header value
+                // checking for errors as each value is inserted and
+                // we only allow keys from the `http::header` module or
+                // custom header names that are checked by the macro.
+                let response = resp_builder.body(#body).unwrap();
+                Ok(response)
+            }
+        };
+        let build_response = build_http_response(body);
+
+        let raw_body_impls = if let Some(field) = self.newtype_raw_body_field() {
+            let raw_body_field_name =
+                field.ident.as_ref().expect("expected field to have an identifier");
+            let build_raw_body_response = build_http_response(quote! { body });
+
+            quote! {
+                #[automatically_derived]
+                #[cfg(feature = "server")]
+                impl #ruma_api::OutgoingRawBodyResponse for Response {
+                    fn try_into_http_response_with_body<B>(
+                        self,
+                        body: B,
+                    ) -> ::std::result::Result<
+                        #http::Response<B>,
+                        #ruma_api::error::IntoHttpError,
+                    > {
+                        let response = self;
+                        if !response.#raw_body_field_name.is_empty() {
+                            return Err(#ruma_api::error::IntoHttpError::RawBodyNotEmpty);
+                        }
+
+                        #build_raw_body_response
+                    }
+                }
+
+                #[automatically_derived]
+                #[cfg(feature = "client")]
+                impl #ruma_api::IncomingRawBodyResponse<#error_ty> for Response {}
+            }
+        } else {
+            TokenStream::new()
+        };
+
         quote! {
             #[doc = #docs]
             #[derive(Debug, Clone, #ruma_serde::Outgoing, #ruma_serde::_FakeDeriveSerde)]
@@ -280,25 +361,15 @@ impl Response {
 
             #response_body_struct
 
+            #raw_body_impls
+
             #[automatically_derived]
             #[cfg(feature = "server")]
             impl ::std::convert::TryFrom<Response> for #http::Response<Vec<u8>> {
                 type Error = #ruma_api::error::IntoHttpError;
 
                 fn try_from(response: Response) -> ::std::result::Result<Self, Self::Error> {
-                    let mut resp_builder = #http::Response::builder()
-                        .header(#http::header::CONTENT_TYPE, "application/json");
-
-                    let mut headers = resp_builder
-                        .headers_mut()
-                        .expect("`http::ResponseBuilder` is in unusable state");
-                    #serialize_response_headers
-
-                    // This cannot fail because we parse each header value
-                    // checking for errors as each value is inserted and
-                    // we only allow keys from the `http::header` module.
-                    let response = resp_builder.body(#body).unwrap();
-                    Ok(response)
+                    #build_response
                 }
             }
 
@@ -338,7 +409,7 @@ pub(crate) enum ResponseField {
     Body(Field),
 
     /// Data in an HTTP header.
-    Header(Field, Ident),
+    Header(Field, HeaderName),
 
     /// A specific data type in the body of the response.
     NewtypeBody(Field),
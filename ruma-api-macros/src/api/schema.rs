@@ -0,0 +1,168 @@
+//! Details of the `SCHEMA` constant generated for the `schema` feature.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Field, Ident, Lit, Meta, NestedMeta, Type};
+
+use super::{attribute::HeaderName, request::RequestField, response::ResponseField, Api};
+use crate::util;
+
+/// Generates the `SCHEMA` constant describing the endpoint.
+pub(super) fn expand_schema(api: &Api, ruma_api: &TokenStream) -> TokenStream {
+    let request_fields: Vec<_> = api
+        .request
+        .iter()
+        .flat_map(|req| &req.fields)
+        .map(|field| match field {
+            RequestField::Body(f) => field_schema(f, "Body", None, ruma_api),
+            RequestField::Header(f, header) => field_schema(f, "Header", Some(header), ruma_api),
+            RequestField::NewtypeBody(f) => field_schema(f, "NewtypeBody", None, ruma_api),
+            RequestField::NewtypeRawBody(f) => field_schema(f, "RawBody", None, ruma_api),
+            RequestField::Path(f) => field_schema(f, "Path", None, ruma_api),
+            RequestField::Query(f) => field_schema(f, "Query", None, ruma_api),
+            RequestField::QueryMap(f) => field_schema(f, "QueryMap", None, ruma_api),
+        })
+        .collect();
+
+    let response_fields: Vec<_> = api
+        .response
+        .iter()
+        .flat_map(|res| &res.fields)
+        .map(|field| match field {
+            ResponseField::Body(f) => field_schema(f, "Body", None, ruma_api),
+            ResponseField::Header(f, header) => field_schema(f, "Header", Some(header), ruma_api),
+            ResponseField::NewtypeBody(f) => field_schema(f, "NewtypeBody", None, ruma_api),
+            ResponseField::NewtypeRawBody(f) => field_schema(f, "RawBody", None, ruma_api),
+        })
+        .collect();
+
+    let status = api.response.as_ref().and_then(|res| res.status.as_ref());
+    let status = match status {
+        Some(status) => quote! { #ruma_api::exports::http::StatusCode::#status },
+        None => quote! { #ruma_api::exports::http::StatusCode::OK },
+    };
+
+    let schema_doc = format!("Schema of the `{}` API endpoint.", api.metadata.name.value());
+
+    quote! {
+        #[doc = #schema_doc]
+        #[cfg(feature = "schema")]
+        pub const SCHEMA: #ruma_api::schema::EndpointSchema = #ruma_api::schema::EndpointSchema {
+            metadata: self::METADATA,
+            request: &[#( #request_fields ),*],
+            response: &[#( #response_fields ),*],
+            status: #status,
+        };
+    }
+}
+
+fn field_schema(
+    field: &Field,
+    location: &str,
+    header: Option<&HeaderName>,
+    ruma_api: &TokenStream,
+) -> TokenStream {
+    let cfg_attrs = field.attrs.iter().filter(|attr| util::is_cfg_attribute(attr));
+    let rust_name = field.ident.as_ref().expect("expected field to have an identifier").to_string();
+
+    let name = match header {
+        Some(header) => header.name(),
+        None if location == "Body" || location == "Query" => serde_name(field).unwrap_or(rust_name),
+        None => rust_name,
+    };
+    let ty = type_to_string(&field.ty);
+    let required = location == "Path" || (!is_option(&field.ty) && !has_serde_default(field));
+    let description = docs(field);
+    let location = Ident::new(location, Span::call_site());
+
+    quote! {
+        #( #cfg_attrs )*
+        #ruma_api::schema::FieldSchema {
+            name: #name,
+            location: #ruma_api::schema::FieldLocation::#location,
+            ty: #ty,
+            required: #required,
+            description: #description,
+        }
+    }
+}
+
+/// Returns the value of the `rename` argument of the field's `serde` attributes, if any.
+fn serde_name(field: &Field) -> Option<String> {
+    serde_args(field).find_map(|meta| match meta {
+        Meta::NameValue(nv) if nv.path.is_ident("rename") => match nv.lit {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Whether the field has a `default` argument in its `serde` attributes.
+fn has_serde_default(field: &Field) -> bool {
+    serde_args(field).any(|meta| meta.path().is_ident("default"))
+}
+
+fn serde_args(field: &Field) -> impl Iterator<Item = Meta> + '_ {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => Some(meta),
+            NestedMeta::Lit(_) => None,
+        })
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map_or(false, |s| s.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn docs(field: &Field) -> String {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(nv)) => match nv.lit {
+                Lit::Str(s) => Some(s.value().trim().to_owned()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts the given type to a string without the spaces `ToTokens` inserts between all tokens.
+fn type_to_string(ty: &Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let mut res = String::with_capacity(tokens.len());
+    let mut chars = tokens.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let prev_is_ident = res.chars().last().map_or(false, is_ident_char);
+            let next_is_ident = chars.peek().copied().map_or(false, is_ident_char);
+            if (prev_is_ident && next_is_ident) || res.ends_with(',') {
+                res.push(' ');
+            }
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
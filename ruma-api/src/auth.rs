@@ -0,0 +1,243 @@
+//! Extraction of authentication data from incoming requests.
+
+use std::convert::TryFrom;
+
+use http::header::AUTHORIZATION;
+use ruma_identifiers::{DeviceIdBox, UserId};
+
+use crate::{error::AuthError, AuthScheme};
+
+/// Authentication data of an incoming request.
+///
+/// Created with `IncomingAuth::extract` or `IncomingRequest::extract_auth`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IncomingAuth {
+    /// The access token sent with the request.
+    ///
+    /// Always `Some(_)` for endpoints using `AuthScheme::AccessToken` or
+    /// `AuthScheme::QueryOnlyAccessToken`, and `None` otherwise.
+    pub access_token: Option<String>,
+
+    /// The user an application service asserts to act as, from the `user_id` query parameter.
+    ///
+    /// Only extracted for endpoints using `AuthScheme::AccessToken`. The server has to check that
+    /// the access token belongs to an application service whose namespace contains this user.
+    pub user_id: Option<UserId>,
+
+    /// The device of `user_id` an application service asserts to act as, from the `device_id`
+    /// query parameter (or its unstable name `org.matrix.msc3202.device_id`).
+    ///
+    /// Only extracted for endpoints using `AuthScheme::AccessToken`.
+    pub device_id: Option<DeviceIdBox>,
+}
+
+impl IncomingAuth {
+    /// Extracts the authentication data of the given request, according to the given
+    /// authentication scheme.
+    ///
+    /// For `AuthScheme::AccessToken`, the access token is taken from the `Authorization` header
+    /// if it contains a bearer token, and from the `access_token` query parameter otherwise. For
+    /// `AuthScheme::QueryOnlyAccessToken`, only the query parameter is considered.
+    ///
+    /// Returns `AuthError::MissingToken` if the scheme requires an access token but none was
+    /// given. Nothing is extracted for `AuthScheme::None` and `AuthScheme::ServerSignatures`, so
+    /// credentials meant for something else are ignored.
+    pub fn extract<T>(request: &http::Request<T>, scheme: AuthScheme) -> Result<Self, AuthError> {
+        let query = match scheme {
+            AuthScheme::None | AuthScheme::ServerSignatures => return Ok(Self::default()),
+            AuthScheme::AccessToken | AuthScheme::QueryOnlyAccessToken => {
+                AuthQuery::parse(request.uri().query().unwrap_or(""))?
+            }
+        };
+
+        if let AuthScheme::QueryOnlyAccessToken = scheme {
+            return Ok(Self {
+                access_token: Some(query.access_token.ok_or(AuthError::MissingToken)?),
+                ..Self::default()
+            });
+        }
+
+        let access_token = match (authorization_header(request), query.access_token) {
+            (AuthorizationHeader::Bearer(token), _) | (_, Some(token)) => token,
+            (AuthorizationHeader::Other, None) => {
+                return Err(AuthError::InvalidAuthorizationHeader)
+            }
+            (AuthorizationHeader::Missing, None) => return Err(AuthError::MissingToken),
+        };
+
+        Ok(Self {
+            access_token: Some(access_token),
+            user_id: query
+                .user_id
+                .map(|id| UserId::try_from(id).map_err(AuthError::InvalidUserId))
+                .transpose()?,
+            device_id: query.device_id.map(Into::into),
+        })
+    }
+}
+
+/// The authentication-related query parameters of a request.
+#[derive(Default)]
+struct AuthQuery {
+    access_token: Option<String>,
+    user_id: Option<String>,
+    device_id: Option<String>,
+}
+
+impl AuthQuery {
+    fn parse(query: &str) -> Result<Self, AuthError> {
+        let pairs: Vec<(String, String)> = ruma_serde::urlencoded::from_str(query)?;
+
+        let mut res = Self::default();
+        for (key, value) in pairs {
+            let field = match key.as_str() {
+                "access_token" => &mut res.access_token,
+                "user_id" => &mut res.user_id,
+                "device_id" | "org.matrix.msc3202.device_id" => &mut res.device_id,
+                _ => continue,
+            };
+
+            if field.is_none() {
+                *field = Some(value);
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// The `Authorization` header of a request.
+enum AuthorizationHeader {
+    /// There is no `Authorization` header.
+    Missing,
+
+    /// The header contains a bearer token.
+    Bearer(String),
+
+    /// The header uses another scheme, like `X-Matrix`, or is invalid.
+    Other,
+}
+
+fn authorization_header<T>(request: &http::Request<T>) -> AuthorizationHeader {
+    let header = match request.headers().get(AUTHORIZATION) {
+        Some(header) => header,
+        None => return AuthorizationHeader::Missing,
+    };
+
+    header
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map_or(AuthorizationHeader::Other, |token| AuthorizationHeader::Bearer(token.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_identifiers::{device_id, user_id};
+
+    use super::IncomingAuth;
+    use crate::{error::AuthError, AuthScheme};
+
+    fn request(uri: &str, authorization: Option<&str>) -> http::Request<()> {
+        let mut builder = http::Request::builder().uri(uri);
+        if let Some(authorization) = authorization {
+            builder = builder.header(http::header::AUTHORIZATION, authorization);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn access_token_from_header_or_query() {
+        let req = request("/foo", Some("Bearer abc"));
+        let auth = IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("abc"));
+
+        let req = request("/foo?access_token=def", None);
+        let auth = IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("def"));
+
+        let req = request("/foo?access_token=def", Some("Bearer abc"));
+        let auth = IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn query_only_access_token() {
+        let req = request("/foo", Some("Bearer abc"));
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::QueryOnlyAccessToken),
+            Err(AuthError::MissingToken)
+        ));
+
+        let req = request("/foo?access_token=def", Some("Bearer abc"));
+        let auth = IncomingAuth::extract(&req, AuthScheme::QueryOnlyAccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("def"));
+    }
+
+    #[test]
+    fn missing_or_invalid_token() {
+        let req = request("/foo", None);
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::AccessToken),
+            Err(AuthError::MissingToken)
+        ));
+        assert_eq!(IncomingAuth::extract(&req, AuthScheme::None).unwrap(), IncomingAuth::default());
+
+        let req = request("/foo", Some("Basic abc"));
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::AccessToken),
+            Err(AuthError::InvalidAuthorizationHeader)
+        ));
+    }
+
+    #[test]
+    fn other_authorization_scheme() {
+        let x_matrix = r#"X-Matrix origin=origin.example.com,key="ed25519:key1",sig="ABCDEF...""#;
+
+        let req = request("/foo", Some(x_matrix));
+        assert_eq!(IncomingAuth::extract(&req, AuthScheme::None).unwrap(), IncomingAuth::default());
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::AccessToken),
+            Err(AuthError::InvalidAuthorizationHeader)
+        ));
+
+        let req = request("/foo?access_token=def", Some("Basic YWxhZGRpbjpvcGVuc2VzYW1l"));
+        assert_eq!(IncomingAuth::extract(&req, AuthScheme::None).unwrap(), IncomingAuth::default());
+        let auth = IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("def"));
+    }
+
+    #[test]
+    fn unauthenticated_endpoint_ignores_query() {
+        let req = request("/foo?access_token=abc&access_token=def", None);
+        assert_eq!(IncomingAuth::extract(&req, AuthScheme::None).unwrap(), IncomingAuth::default());
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::AccessToken),
+            Err(AuthError::Query(_))
+        ));
+
+        let req = request("/foo?access_token=abc", Some("Bearer def"));
+        assert_eq!(IncomingAuth::extract(&req, AuthScheme::None).unwrap(), IncomingAuth::default());
+    }
+
+    #[test]
+    fn appservice_identity_assertion() {
+        let req = request(
+            "/foo?user_id=%40carl%3Aexample.org&org.matrix.msc3202.device_id=ABCDEF&limit=5",
+            Some("Bearer as_token"),
+        );
+        let auth = IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap();
+        assert_eq!(auth.access_token.as_deref(), Some("as_token"));
+        assert_eq!(auth.user_id, Some(user_id!("@carl:example.org")));
+        assert_eq!(auth.device_id, Some(device_id!("ABCDEF")));
+
+        let req = request("/foo?user_id=carl", Some("Bearer as_token"));
+        assert!(matches!(
+            IncomingAuth::extract(&req, AuthScheme::AccessToken),
+            Err(AuthError::InvalidUserId(_))
+        ));
+    }
+}
@@ -6,7 +6,12 @@ use std::{error::Error as StdError, fmt};
 
 use thiserror::Error;
 
-use crate::EndpointError;
+use crate::{EndpointError, MatrixVersion};
+
+mod kind_serde;
+mod matrix;
+
+pub use matrix::{ErrorBody, ErrorKind, MatrixError};
 
 // FIXME when `!` becomes stable use it
 /// Default `EndpointError` for `ruma_api!` macro
@@ -29,6 +34,12 @@ impl fmt::Display for Void {
 
 impl StdError for Void {}
 
+impl From<Void> for http::Response<Vec<u8>> {
+    fn from(v: Void) -> Self {
+        match v {}
+    }
+}
+
 /// An error when converting one of ruma's endpoint-specific request or response
 /// types to the corresponding http type.
 #[derive(Debug, Error)]
@@ -56,6 +67,16 @@ pub enum IntoHttpError {
     /// HTTP request construction failed.
     #[error("HTTP request construction failed: {0}")]
     Http(#[from] http::Error),
+
+    /// All versions supported by the server are equal to or newer than the version that removed
+    /// the endpoint.
+    #[error("The endpoint was removed in Matrix {0}, which the server supports exclusively")]
+    EndpointRemoved(MatrixVersion),
+
+    /// The `raw_body` field of a request or response was not empty while its body was given
+    /// separately.
+    #[error("The raw_body field must be empty when the body is given separately")]
+    RawBodyNotEmpty,
 }
 
 /// An error when converting a http request to one of ruma's endpoint-specific request types.
@@ -76,6 +97,27 @@ pub enum FromHttpRequestError {
     },
 }
 
+/// An error when extracting the authentication data of an incoming request.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AuthError {
+    /// The endpoint requires an access token, but none was given.
+    #[error("no access token was given")]
+    MissingToken,
+
+    /// The `Authorization` header doesn't contain a bearer token.
+    #[error("the Authorization header doesn't contain a bearer token")]
+    InvalidAuthorizationHeader,
+
+    /// The query string couldn't be deserialized.
+    #[error("query string deserialization failed: {0}")]
+    Query(#[from] ruma_serde::urlencoded::de::Error),
+
+    /// The `user_id` query parameter is not a valid user ID.
+    #[error("invalid user_id query parameter: {0}")]
+    InvalidUserId(ruma_identifiers::Error),
+}
+
 /// An error that occurred when trying to deserialize a request.
 #[derive(Debug, Error)]
 #[error("{inner}")]
@@ -235,4 +277,14 @@ pub enum HeaderDeserializationError {
     /// The given required header is missing.
     #[error("Missing header `{0}`")]
     MissingHeader(String),
+
+    /// The value of the given header could not be parsed.
+    #[error("Invalid value for header `{header}`: {message}")]
+    InvalidHeader {
+        /// The name of the header.
+        header: String,
+
+        /// The error message of the parser.
+        message: String,
+    },
 }
@@ -12,7 +12,7 @@ use serde::{
     de::{self, Deserialize, Deserializer, MapAccess, Visitor},
     ser::{self, Serialize, SerializeMap, Serializer},
 };
-use serde_json::from_value as from_json_value;
+use serde_json::{from_value as from_json_value, Value as JsonValue};
 
 use super::ErrorKind;
 
@@ -22,6 +22,7 @@ enum Field<'de> {
     RetryAfterMs,
     RoomVersion,
     AdminContact,
+    CurrentVersion,
     Other(Cow<'de, str>),
 }
 
@@ -33,6 +34,7 @@ impl<'de> Field<'de> {
             "retry_after_ms" => Self::RetryAfterMs,
             "room_version" => Self::RoomVersion,
             "admin_contact" => Self::AdminContact,
+            "current_version" => Self::CurrentVersion,
             _ => Self::Other(s),
         }
     }
@@ -48,7 +50,7 @@ impl<'de> Deserialize<'de> for Field<'de> {
         impl<'de> Visitor<'de> for FieldVisitor {
             type Value = Field<'de>;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("any struct field")
             }
 
@@ -83,7 +85,7 @@ struct ErrorKindVisitor;
 impl<'de> Visitor<'de> for ErrorKindVisitor {
     type Value = ErrorKind;
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         formatter.write_str("enum ErrorKind")
     }
 
@@ -92,21 +94,31 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
         V: MapAccess<'de>,
     {
         let mut errcode = None;
-        let mut soft_logout = None;
-        let mut retry_after_ms = None;
-        let mut room_version = None;
-        let mut admin_contact = None;
+        let mut soft_logout: Option<JsonValue> = None;
+        let mut retry_after_ms: Option<JsonValue> = None;
+        let mut room_version: Option<JsonValue> = None;
+        let mut admin_contact: Option<JsonValue> = None;
+        let mut current_version: Option<JsonValue> = None;
         let mut extra = BTreeMap::new();
 
         macro_rules! set_field {
             (errcode) => {
-                set_field!(@inner errcode);
+                set_field!(@inner errcode)
             };
             ($field:ident) => {
                 match errcode {
                     Some(set_field!(@variant_containing $field)) | None => {
                         set_field!(@inner $field);
                     }
+                    // custom error codes keep all the fields they don't know about.
+                    Some(ErrCode::_Custom(_)) => match extra.entry(stringify!($field).to_owned()) {
+                        Entry::Vacant(v) => {
+                            v.insert(map.next_value()?);
+                        }
+                        Entry::Occupied(_) => {
+                            return Err(de::Error::duplicate_field(stringify!($field)));
+                        }
+                    },
                     // if we already know we're deserializing a different variant to the one
                     // containing this field, ignore its value.
                     Some(_) => {
@@ -118,6 +130,7 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
             (@variant_containing retry_after_ms) => { ErrCode::LimitExceeded };
             (@variant_containing room_version) => { ErrCode::IncompatibleRoomVersion };
             (@variant_containing admin_contact) => { ErrCode::ResourceLimitExceeded };
+            (@variant_containing current_version) => { ErrCode::WrongRoomKeysVersion };
             (@inner $field:ident) => {
                 {
                     if $field.is_some() {
@@ -135,6 +148,7 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
                 Field::RetryAfterMs => set_field!(retry_after_ms),
                 Field::RoomVersion => set_field!(room_version),
                 Field::AdminContact => set_field!(admin_contact),
+                Field::CurrentVersion => set_field!(current_version),
                 Field::Other(other) => match extra.entry(other.into_owned()) {
                     Entry::Vacant(v) => {
                         v.insert(map.next_value()?);
@@ -180,6 +194,7 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
             ErrCode::ThreepidNotFound => ErrorKind::ThreepidNotFound,
             ErrCode::ThreepidAuthFailed => ErrorKind::ThreepidAuthFailed,
             ErrCode::ThreepidDenied => ErrorKind::ThreepidDenied,
+            ErrCode::ThreepidMediumNotSupported => ErrorKind::ThreepidMediumNotSupported,
             ErrCode::ServerNotTrusted => ErrorKind::ServerNotTrusted,
             ErrCode::UnsupportedRoomVersion => ErrorKind::UnsupportedRoomVersion,
             ErrCode::IncompatibleRoomVersion => ErrorKind::IncompatibleRoomVersion {
@@ -203,7 +218,46 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
                 .map_err(de::Error::custom)?,
             },
             ErrCode::CannotLeaveServerNoticeRoom => ErrorKind::CannotLeaveServerNoticeRoom,
-            ErrCode::_Custom(errcode) => ErrorKind::_Custom { errcode, extra },
+            ErrCode::WeakPassword => ErrorKind::WeakPassword,
+            ErrCode::WrongRoomKeysVersion => ErrorKind::WrongRoomKeysVersion {
+                current_version: current_version
+                    .map(from_json_value)
+                    .transpose()
+                    .map_err(de::Error::custom)?,
+            },
+            ErrCode::UnableToAuthorizeJoin => ErrorKind::UnableToAuthorizeJoin,
+            ErrCode::UnableToGrantJoin => ErrorKind::UnableToGrantJoin,
+            ErrCode::BadAlias => ErrorKind::BadAlias,
+            ErrCode::DuplicateAnnotation => ErrorKind::DuplicateAnnotation,
+            ErrCode::NotYetUploaded => ErrorKind::NotYetUploaded,
+            ErrCode::CannotOverwriteMedia => ErrorKind::CannotOverwriteMedia,
+            ErrCode::MissingParams => ErrorKind::MissingParams,
+            ErrCode::SessionNotValidated => ErrorKind::SessionNotValidated,
+            ErrCode::NoValidSession => ErrorKind::NoValidSession,
+            ErrCode::SessionExpired => ErrorKind::SessionExpired,
+            ErrCode::InvalidEmail => ErrorKind::InvalidEmail,
+            ErrCode::EmailSendError => ErrorKind::EmailSendError,
+            ErrCode::InvalidAddress => ErrorKind::InvalidAddress,
+            ErrCode::SendError => ErrorKind::SendError,
+            ErrCode::TermsNotSigned => ErrorKind::TermsNotSigned,
+            ErrCode::InvalidPepper => ErrorKind::InvalidPepper,
+            ErrCode::_Custom(errcode) => {
+                // Fields that were read before `errcode` were buffered for a known error code.
+                let buffered = vec![
+                    ("soft_logout", soft_logout),
+                    ("retry_after_ms", retry_after_ms),
+                    ("room_version", room_version),
+                    ("admin_contact", admin_contact),
+                    ("current_version", current_version),
+                ];
+                for (field, value) in buffered {
+                    if let Some(value) = value {
+                        extra.insert(field.to_owned(), value);
+                    }
+                }
+
+                ErrorKind::_Custom { errcode, extra }
+            }
         })
     }
 }
@@ -249,6 +303,8 @@ enum ErrCode {
     ThreepidAuthFailed,
     #[ruma_enum(rename = "M_THREEPID_DENIED")]
     ThreepidDenied,
+    #[ruma_enum(rename = "M_THREEPID_MEDIUM_NOT_SUPPORTED")]
+    ThreepidMediumNotSupported,
     #[ruma_enum(rename = "M_SERVER_NOT_TRUSTED")]
     ServerNotTrusted,
     #[ruma_enum(rename = "M_UNSUPPORTED_ROOM_VERSION")]
@@ -275,6 +331,42 @@ enum ErrCode {
     ResourceLimitExceeded,
     #[ruma_enum(rename = "M_CANNOT_LEAVE_SERVER_NOTICE_ROOM")]
     CannotLeaveServerNoticeRoom,
+    #[ruma_enum(rename = "M_WEAK_PASSWORD")]
+    WeakPassword,
+    #[ruma_enum(rename = "M_WRONG_ROOM_KEYS_VERSION")]
+    WrongRoomKeysVersion,
+    #[ruma_enum(rename = "M_UNABLE_TO_AUTHORISE_JOIN")]
+    UnableToAuthorizeJoin,
+    #[ruma_enum(rename = "M_UNABLE_TO_GRANT_JOIN")]
+    UnableToGrantJoin,
+    #[ruma_enum(rename = "M_BAD_ALIAS")]
+    BadAlias,
+    #[ruma_enum(rename = "M_DUPLICATE_ANNOTATION")]
+    DuplicateAnnotation,
+    #[ruma_enum(rename = "M_NOT_YET_UPLOADED")]
+    NotYetUploaded,
+    #[ruma_enum(rename = "M_CANNOT_OVERWRITE_MEDIA")]
+    CannotOverwriteMedia,
+    #[ruma_enum(rename = "M_MISSING_PARAMS")]
+    MissingParams,
+    #[ruma_enum(rename = "M_SESSION_NOT_VALIDATED")]
+    SessionNotValidated,
+    #[ruma_enum(rename = "M_NO_VALID_SESSION")]
+    NoValidSession,
+    #[ruma_enum(rename = "M_SESSION_EXPIRED")]
+    SessionExpired,
+    #[ruma_enum(rename = "M_INVALID_EMAIL")]
+    InvalidEmail,
+    #[ruma_enum(rename = "M_EMAIL_SEND_ERROR")]
+    EmailSendError,
+    #[ruma_enum(rename = "M_INVALID_ADDRESS")]
+    InvalidAddress,
+    #[ruma_enum(rename = "M_SEND_ERROR")]
+    SendError,
+    #[ruma_enum(rename = "M_TERMS_NOT_SIGNED")]
+    TermsNotSigned,
+    #[ruma_enum(rename = "M_INVALID_PEPPER")]
+    InvalidPepper,
     _Custom(String),
 }
 
@@ -310,6 +402,9 @@ impl Serialize for ErrorKind {
             Self::ResourceLimitExceeded { admin_contact } => {
                 st.serialize_entry("admin_contact", admin_contact)?;
             }
+            Self::WrongRoomKeysVersion { current_version: Some(current_version) } => {
+                st.serialize_entry("current_version", current_version)?;
+            }
             Self::_Custom { extra, .. } => {
                 for (k, v) in extra {
                     st.serialize_entry(k, v)?;
@@ -324,7 +419,7 @@ impl Serialize for ErrorKind {
 #[cfg(test)]
 mod tests {
     use ruma_identifiers::room_version_id;
-    use serde_json::{from_value as from_json_value, json};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::ErrorKind;
 
@@ -358,4 +453,57 @@ mod tests {
             ErrorKind::IncompatibleRoomVersion { room_version: room_version_id!("7") }
         );
     }
+
+    #[test]
+    fn deserialize_wrong_room_keys_version() {
+        let deserialized: ErrorKind = from_json_value(json!({
+            "errcode": "M_WRONG_ROOM_KEYS_VERSION",
+            "current_version": "42",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            deserialized,
+            ErrorKind::WrongRoomKeysVersion { current_version: Some("42".into()) }
+        );
+    }
+
+    #[test]
+    fn custom_errcode_keeps_known_field_names() {
+        let json = json!({
+            "errcode": "X_FOO",
+            "retry_after_ms": 5,
+            "admin_contact": "mailto:admin@example.org",
+        });
+
+        // `errcode` first.
+        let deserialized: ErrorKind = serde_json::from_str(
+            r#"{"errcode":"X_FOO","retry_after_ms":5,"admin_contact":"mailto:admin@example.org"}"#,
+        )
+        .unwrap();
+        assert_eq!(deserialized.as_ref(), "X_FOO");
+        assert_eq!(to_json_value(&deserialized).unwrap(), json);
+
+        // `errcode` last, so the other fields are read before the kind is known.
+        let deserialized: ErrorKind = serde_json::from_str(
+            r#"{"retry_after_ms":5,"admin_contact":"mailto:admin@example.org","errcode":"X_FOO"}"#,
+        )
+        .unwrap();
+        assert_eq!(deserialized.as_ref(), "X_FOO");
+        assert_eq!(to_json_value(&deserialized).unwrap(), json);
+    }
+
+    #[test]
+    fn serialize_resource_limit_exceeded() {
+        let kind =
+            ErrorKind::ResourceLimitExceeded { admin_contact: "mailto:admin@example.org".into() };
+
+        assert_eq!(
+            to_json_value(&kind).unwrap(),
+            json!({
+                "errcode": "M_RESOURCE_LIMIT_EXCEEDED",
+                "admin_contact": "mailto:admin@example.org",
+            })
+        );
+    }
 }
@@ -0,0 +1,565 @@
+//! The standard error type of the Matrix APIs.
+
+use std::{collections::BTreeMap, error::Error as StdError, fmt, time::Duration};
+
+use http::StatusCode;
+use ruma_identifiers::RoomVersionId;
+use serde::{Deserialize, Serialize};
+use serde_json::{from_slice as from_json_slice, to_vec as to_json_vec, Value as JsonValue};
+
+use super::{AuthError, ResponseDeserializationError};
+use crate::EndpointError;
+
+/// An enum for the error kind. Items may contain additional information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// M_FORBIDDEN
+    Forbidden,
+
+    /// M_UNKNOWN_TOKEN
+    UnknownToken {
+        /// If this is `true`, the client can acquire a new access token by specifying the device
+        /// ID it is already using to the login API. For more information, see [the spec].
+        ///
+        /// [the spec]: https://matrix.org/docs/spec/client_server/r0.6.1#soft-logout
+        soft_logout: bool,
+    },
+
+    /// M_MISSING_TOKEN
+    MissingToken,
+
+    /// M_BAD_JSON
+    BadJson,
+
+    /// M_NOT_JSON
+    NotJson,
+
+    /// M_NOT_FOUND
+    NotFound,
+
+    /// M_LIMIT_EXCEEDED
+    LimitExceeded {
+        /// How long a client should wait in milliseconds before they can try again.
+        retry_after_ms: Option<Duration>,
+    },
+
+    /// M_UNKNOWN
+    Unknown,
+
+    /// M_UNRECOGNIZED
+    Unrecognized,
+
+    /// M_UNAUTHORIZED
+    Unauthorized,
+
+    /// M_USER_DEACTIVATED
+    UserDeactivated,
+
+    /// M_USER_IN_USE
+    UserInUse,
+
+    /// M_INVALID_USERNAME
+    InvalidUsername,
+
+    /// M_ROOM_IN_USE
+    RoomInUse,
+
+    /// M_INVALID_ROOM_STATE
+    InvalidRoomState,
+
+    /// M_THREEPID_IN_USE
+    ThreepidInUse,
+
+    /// M_THREEPID_NOT_FOUND
+    ThreepidNotFound,
+
+    /// M_THREEPID_AUTH_FAILED
+    ThreepidAuthFailed,
+
+    /// M_THREEPID_DENIED
+    ThreepidDenied,
+
+    /// M_THREEPID_MEDIUM_NOT_SUPPORTED
+    ThreepidMediumNotSupported,
+
+    /// M_SERVER_NOT_TRUSTED
+    ServerNotTrusted,
+
+    /// M_UNSUPPORTED_ROOM_VERSION
+    UnsupportedRoomVersion,
+
+    /// M_INCOMPATIBLE_ROOM_VERSION
+    IncompatibleRoomVersion {
+        /// The room's version.
+        room_version: RoomVersionId,
+    },
+
+    /// M_BAD_STATE
+    BadState,
+
+    /// M_GUEST_ACCESS_FORBIDDEN
+    GuestAccessForbidden,
+
+    /// M_CAPTCHA_NEEDED
+    CaptchaNeeded,
+
+    /// M_CAPTCHA_INVALID
+    CaptchaInvalid,
+
+    /// M_MISSING_PARAM
+    MissingParam,
+
+    /// M_INVALID_PARAM
+    InvalidParam,
+
+    /// M_TOO_LARGE
+    TooLarge,
+
+    /// M_EXCLUSIVE
+    Exclusive,
+
+    /// M_RESOURCE_LIMIT_EXCEEDED
+    ResourceLimitExceeded {
+        /// A URI giving a contact method for the server administrator.
+        admin_contact: String,
+    },
+
+    /// M_CANNOT_LEAVE_SERVER_NOTICE_ROOM
+    CannotLeaveServerNoticeRoom,
+
+    /// M_WEAK_PASSWORD
+    WeakPassword,
+
+    /// M_WRONG_ROOM_KEYS_VERSION
+    WrongRoomKeysVersion {
+        /// The currently active backup version.
+        current_version: Option<String>,
+    },
+
+    /// M_UNABLE_TO_AUTHORISE_JOIN
+    UnableToAuthorizeJoin,
+
+    /// M_UNABLE_TO_GRANT_JOIN
+    UnableToGrantJoin,
+
+    /// M_BAD_ALIAS
+    BadAlias,
+
+    /// M_DUPLICATE_ANNOTATION
+    DuplicateAnnotation,
+
+    /// M_NOT_YET_UPLOADED
+    NotYetUploaded,
+
+    /// M_CANNOT_OVERWRITE_MEDIA
+    CannotOverwriteMedia,
+
+    /// M_MISSING_PARAMS
+    ///
+    /// Used by the identity service API instead of `M_MISSING_PARAM`.
+    MissingParams,
+
+    /// M_SESSION_NOT_VALIDATED
+    SessionNotValidated,
+
+    /// M_NO_VALID_SESSION
+    NoValidSession,
+
+    /// M_SESSION_EXPIRED
+    SessionExpired,
+
+    /// M_INVALID_EMAIL
+    InvalidEmail,
+
+    /// M_EMAIL_SEND_ERROR
+    EmailSendError,
+
+    /// M_INVALID_ADDRESS
+    InvalidAddress,
+
+    /// M_SEND_ERROR
+    SendError,
+
+    /// M_TERMS_NOT_SIGNED
+    TermsNotSigned,
+
+    /// M_INVALID_PEPPER
+    InvalidPepper,
+
+    #[doc(hidden)]
+    _Custom { errcode: String, extra: BTreeMap<String, JsonValue> },
+}
+
+impl ErrorKind {
+    /// The HTTP status code servers should respond with for this kind of error, unless the
+    /// endpoint specifies a different one.
+    ///
+    /// Unknown error codes default to `400 Bad Request`.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden
+            | Self::UserDeactivated
+            | Self::ThreepidDenied
+            | Self::ServerNotTrusted
+            | Self::GuestAccessForbidden
+            | Self::ResourceLimitExceeded { .. }
+            | Self::CannotLeaveServerNoticeRoom
+            | Self::WrongRoomKeysVersion { .. }
+            | Self::TermsNotSigned => StatusCode::FORBIDDEN,
+            Self::UnknownToken { .. }
+            | Self::MissingToken
+            | Self::Unauthorized
+            | Self::ThreepidAuthFailed
+            | Self::CaptchaNeeded => StatusCode::UNAUTHORIZED,
+            Self::NotFound | Self::ThreepidNotFound | Self::NoValidSession => StatusCode::NOT_FOUND,
+            Self::LimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::CannotOverwriteMedia => StatusCode::CONFLICT,
+            Self::NotYetUploaded => StatusCode::GATEWAY_TIMEOUT,
+            Self::UnableToGrantJoin => StatusCode::BAD_GATEWAY,
+            Self::Unknown | Self::EmailSendError | Self::SendError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            Self::BadJson
+            | Self::NotJson
+            | Self::Unrecognized
+            | Self::UserInUse
+            | Self::InvalidUsername
+            | Self::RoomInUse
+            | Self::InvalidRoomState
+            | Self::ThreepidInUse
+            | Self::ThreepidMediumNotSupported
+            | Self::UnsupportedRoomVersion
+            | Self::IncompatibleRoomVersion { .. }
+            | Self::BadState
+            | Self::CaptchaInvalid
+            | Self::MissingParam
+            | Self::InvalidParam
+            | Self::Exclusive
+            | Self::WeakPassword
+            | Self::UnableToAuthorizeJoin
+            | Self::BadAlias
+            | Self::DuplicateAnnotation
+            | Self::MissingParams
+            | Self::SessionNotValidated
+            | Self::SessionExpired
+            | Self::InvalidEmail
+            | Self::InvalidAddress
+            | Self::InvalidPepper
+            | Self::_Custom { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl AsRef<str> for ErrorKind {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Forbidden => "M_FORBIDDEN",
+            Self::UnknownToken { .. } => "M_UNKNOWN_TOKEN",
+            Self::MissingToken => "M_MISSING_TOKEN",
+            Self::BadJson => "M_BAD_JSON",
+            Self::NotJson => "M_NOT_JSON",
+            Self::NotFound => "M_NOT_FOUND",
+            Self::LimitExceeded { .. } => "M_LIMIT_EXCEEDED",
+            Self::Unknown => "M_UNKNOWN",
+            Self::Unrecognized => "M_UNRECOGNIZED",
+            Self::Unauthorized => "M_UNAUTHORIZED",
+            Self::UserDeactivated => "M_USER_DEACTIVATED",
+            Self::UserInUse => "M_USER_IN_USE",
+            Self::InvalidUsername => "M_INVALID_USERNAME",
+            Self::RoomInUse => "M_ROOM_IN_USE",
+            Self::InvalidRoomState => "M_INVALID_ROOM_STATE",
+            Self::ThreepidInUse => "M_THREEPID_IN_USE",
+            Self::ThreepidNotFound => "M_THREEPID_NOT_FOUND",
+            Self::ThreepidAuthFailed => "M_THREEPID_AUTH_FAILED",
+            Self::ThreepidDenied => "M_THREEPID_DENIED",
+            Self::ThreepidMediumNotSupported => "M_THREEPID_MEDIUM_NOT_SUPPORTED",
+            Self::ServerNotTrusted => "M_SERVER_NOT_TRUSTED",
+            Self::UnsupportedRoomVersion => "M_UNSUPPORTED_ROOM_VERSION",
+            Self::IncompatibleRoomVersion { .. } => "M_INCOMPATIBLE_ROOM_VERSION",
+            Self::BadState => "M_BAD_STATE",
+            Self::GuestAccessForbidden => "M_GUEST_ACCESS_FORBIDDEN",
+            Self::CaptchaNeeded => "M_CAPTCHA_NEEDED",
+            Self::CaptchaInvalid => "M_CAPTCHA_INVALID",
+            Self::MissingParam => "M_MISSING_PARAM",
+            Self::InvalidParam => "M_INVALID_PARAM",
+            Self::TooLarge => "M_TOO_LARGE",
+            Self::Exclusive => "M_EXCLUSIVE",
+            Self::ResourceLimitExceeded { .. } => "M_RESOURCE_LIMIT_EXCEEDED",
+            Self::CannotLeaveServerNoticeRoom => "M_CANNOT_LEAVE_SERVER_NOTICE_ROOM",
+            Self::WeakPassword => "M_WEAK_PASSWORD",
+            Self::WrongRoomKeysVersion { .. } => "M_WRONG_ROOM_KEYS_VERSION",
+            Self::UnableToAuthorizeJoin => "M_UNABLE_TO_AUTHORISE_JOIN",
+            Self::UnableToGrantJoin => "M_UNABLE_TO_GRANT_JOIN",
+            Self::BadAlias => "M_BAD_ALIAS",
+            Self::DuplicateAnnotation => "M_DUPLICATE_ANNOTATION",
+            Self::NotYetUploaded => "M_NOT_YET_UPLOADED",
+            Self::CannotOverwriteMedia => "M_CANNOT_OVERWRITE_MEDIA",
+            Self::MissingParams => "M_MISSING_PARAMS",
+            Self::SessionNotValidated => "M_SESSION_NOT_VALIDATED",
+            Self::NoValidSession => "M_NO_VALID_SESSION",
+            Self::SessionExpired => "M_SESSION_EXPIRED",
+            Self::InvalidEmail => "M_INVALID_EMAIL",
+            Self::EmailSendError => "M_EMAIL_SEND_ERROR",
+            Self::InvalidAddress => "M_INVALID_ADDRESS",
+            Self::SendError => "M_SEND_ERROR",
+            Self::TermsNotSigned => "M_TERMS_NOT_SIGNED",
+            Self::InvalidPepper => "M_INVALID_PEPPER",
+            Self::_Custom { errcode, .. } => errcode,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// A Matrix Error without a status code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// A value which can be used to handle an error message
+    #[serde(flatten)]
+    pub kind: ErrorKind,
+
+    /// A human-readable error message, usually a sentence explaining what went wrong.
+    #[serde(default, rename = "error")]
+    pub message: String,
+}
+
+impl ErrorBody {
+    /// Convert the ErrorBody into an Error by adding the http status code.
+    pub fn into_error(self, status_code: StatusCode) -> MatrixError {
+        MatrixError { kind: self.kind, message: self.message, status_code }
+    }
+}
+
+/// A standard Matrix error, as returned by servers of all Matrix APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixError {
+    /// A value which can be used to handle an error message
+    pub kind: ErrorKind,
+
+    /// A human-readable error message, usually a sentence explaining what went wrong.
+    pub message: String,
+
+    /// The http status code
+    pub status_code: StatusCode,
+}
+
+impl MatrixError {
+    /// Creates a new `MatrixError` with the given kind and message, and the default status code of
+    /// the kind.
+    pub fn new(kind: ErrorKind, message: String) -> Self {
+        let status_code = kind.status_code();
+        Self { kind, message, status_code }
+    }
+
+    /// Creates the `M_UNKNOWN_TOKEN` error to return when the access token of a request is not
+    /// recognised, for example because it was revoked.
+    ///
+    /// The conversion from `AuthError` never produces this error, since only the server can look
+    /// up the access token extracted by `IncomingAuth::extract`.
+    ///
+    /// `soft_logout` is `true` if the client can log in again without losing its data.
+    pub fn unknown_token(soft_logout: bool) -> Self {
+        Self::new(ErrorKind::UnknownToken { soft_logout }, "Unknown access token".to_owned())
+    }
+}
+
+impl EndpointError for MatrixError {
+    fn try_from_response(
+        response: http::Response<Vec<u8>>,
+    ) -> Result<Self, ResponseDeserializationError> {
+        let mut error = match from_json_slice::<ErrorBody>(response.body()) {
+            Ok(error_body) => error_body.into_error(response.status()),
+            Err(de_error) => return Err(ResponseDeserializationError::new(de_error, response)),
+        };
+
+        // Fall back to the `Retry-After` header, which is in seconds.
+        if let ErrorKind::LimitExceeded { retry_after_ms: retry_after_ms @ None } = &mut error.kind
+        {
+            *retry_after_ms = response
+                .headers()
+                .get(http::header::RETRY_AFTER)
+                .and_then(|header| header.to_str().ok()?.trim().parse().ok())
+                .map(Duration::from_secs);
+        }
+
+        Ok(error)
+    }
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} / {}] {}", self.status_code.as_u16(), self.kind, self.message)
+    }
+}
+
+impl StdError for MatrixError {}
+
+impl From<MatrixError> for ErrorBody {
+    fn from(error: MatrixError) -> Self {
+        Self { kind: error.kind, message: error.message }
+    }
+}
+
+impl From<AuthError> for MatrixError {
+    /// Converts an error from extracting the authentication data of an incoming request into the
+    /// corresponding Matrix error, like `M_MISSING_TOKEN` for a missing access token.
+    fn from(error: AuthError) -> Self {
+        let message = error.to_string();
+        match error {
+            // An access token that can't be read is as good as a missing one. `M_UNKNOWN_TOKEN`
+            // is left to the server, when it fails to look up a well-formed access token.
+            AuthError::MissingToken | AuthError::InvalidAuthorizationHeader => {
+                Self::new(ErrorKind::MissingToken, message)
+            }
+            AuthError::InvalidUserId(_) => Self::new(ErrorKind::InvalidParam, message),
+            _ => {
+                ErrorBody { kind: ErrorKind::Unknown, message }.into_error(StatusCode::BAD_REQUEST)
+            }
+        }
+    }
+}
+
+impl From<MatrixError> for http::Response<Vec<u8>> {
+    fn from(error: MatrixError) -> http::Response<Vec<u8>> {
+        http::Response::builder()
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .status(error.status_code)
+            .body(to_json_vec(&ErrorBody::from(error)).unwrap())
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::StatusCode;
+    use serde_json::{
+        from_slice as from_json_slice, from_value as from_json_value, json, to_vec as to_json_vec,
+        Value as JsonValue,
+    };
+
+    use super::{ErrorBody, ErrorKind, MatrixError};
+    use crate::{auth::IncomingAuth, AuthScheme, EndpointError};
+
+    fn response(status: StatusCode, body: &JsonValue) -> http::Response<Vec<u8>> {
+        http::Response::builder().status(status).body(to_json_vec(body).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn deserialize_forbidden() {
+        let deserialized: ErrorBody = from_json_value(json!({
+            "errcode": "M_FORBIDDEN",
+            "error": "You are not authorized to ban users in this room.",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            deserialized,
+            ErrorBody {
+                kind: ErrorKind::Forbidden,
+                message: "You are not authorized to ban users in this room.".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let body = json!({
+            "errcode": "M_LIMIT_EXCEEDED",
+            "error": "Too many requests",
+            "retry_after_ms": 2000,
+        });
+
+        let error =
+            MatrixError::try_from_response(response(StatusCode::TOO_MANY_REQUESTS, &body)).unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::LimitExceeded { retry_after_ms: Some(Duration::from_secs(2)) }
+        );
+        assert_eq!(error.message, "Too many requests");
+
+        let response: http::Response<Vec<u8>> = error.into();
+        assert_eq!(from_json_slice::<JsonValue>(response.body()).unwrap(), body);
+    }
+
+    #[test]
+    fn retry_after_header() {
+        let mut response =
+            response(StatusCode::TOO_MANY_REQUESTS, &json!({ "errcode": "M_LIMIT_EXCEEDED" }));
+        response.headers_mut().insert(http::header::RETRY_AFTER, "5".parse().unwrap());
+
+        let error = MatrixError::try_from_response(response).unwrap();
+        assert_eq!(error.message, "");
+        assert_eq!(
+            error.kind,
+            ErrorKind::LimitExceeded { retry_after_ms: Some(Duration::from_secs(5)) }
+        );
+    }
+
+    #[test]
+    fn custom_error_keeps_extra_fields() {
+        let body = json!({
+            "errcode": "ORG_EXAMPLE_QUOTA",
+            "error": "Quota exceeded",
+            "org.example.quota": { "used": 10, "limit": 10 },
+        });
+
+        let error = MatrixError::try_from_response(response(StatusCode::FORBIDDEN, &body)).unwrap();
+        assert_eq!(error.kind.as_ref(), "ORG_EXAMPLE_QUOTA");
+        assert_eq!(error.status_code, StatusCode::FORBIDDEN);
+
+        let response: http::Response<Vec<u8>> = error.into();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(from_json_slice::<JsonValue>(response.body()).unwrap(), body);
+    }
+
+    #[test]
+    fn not_a_matrix_error() {
+        let response = http::Response::builder()
+            .status(StatusCode::BAD_GATEWAY)
+            .body(b"<html>Bad Gateway</html>".to_vec())
+            .unwrap();
+
+        assert!(MatrixError::try_from_response(response).is_err());
+    }
+
+    #[test]
+    fn default_status_codes() {
+        let error = MatrixError::new(ErrorKind::NotFound, "Unknown room".into());
+        assert_eq!(error.status_code, StatusCode::NOT_FOUND);
+
+        assert_eq!(ErrorKind::LimitExceeded { retry_after_ms: None }.status_code(), 429);
+        assert_eq!(ErrorKind::WrongRoomKeysVersion { current_version: None }.status_code(), 403);
+        assert_eq!(ErrorKind::UserDeactivated.status_code(), 403);
+        assert_eq!(ErrorKind::TooLarge.status_code(), 413);
+    }
+
+    #[test]
+    fn auth_errors() {
+        let req = http::Request::builder().uri("/foo").body(()).unwrap();
+        let err: MatrixError =
+            IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap_err().into();
+        assert_eq!(err.kind, ErrorKind::MissingToken);
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+
+        let req = http::Request::builder()
+            .uri("/foo")
+            .header(http::header::AUTHORIZATION, "Basic abc")
+            .body(())
+            .unwrap();
+        let err: MatrixError =
+            IncomingAuth::extract(&req, AuthScheme::AccessToken).unwrap_err().into();
+        assert_eq!(err.kind, ErrorKind::MissingToken);
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+
+        let err = MatrixError::unknown_token(true);
+        assert_eq!(err.kind, ErrorKind::UnknownToken { soft_logout: true });
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -62,6 +62,13 @@ use http::Method;
 /// implementations to convert the request into a `http::Request` and to create a response from
 /// a `http::Response` and vice versa.
 ///
+/// Some items are only generated when a Cargo feature of the crate using the macro is enabled:
+///
+/// * `schema`: A `SCHEMA` constant describing the endpoint, see the `schema` module.
+///
+/// With the `registry` feature of this crate, the metadata of the endpoint is also registered
+/// in `registry::ENDPOINTS`, along with its schema if the `schema` feature is enabled.
+///
 /// The details of each of the three sections of the macros are documented below.
 ///
 /// ## Metadata
@@ -75,7 +82,21 @@ use http::Method;
 /// * `path`: The path component of the URL for the endpoint, e.g. "/foo/bar". Components of
 ///   the path that are parameterized can indicate a variable by using a Rust identifier
 ///   prefixed with a colon, e.g. `/foo/:some_parameter`. A corresponding query string
-///   parameter will be expected in the request struct (see below for details).
+///   parameter will be expected in the request struct (see below for details). Can be omitted
+///   if at least one of the versioned paths below is given, in which case the first stable path
+///   or else the first unstable path is used.
+///
+/// Endpoints that are available under different paths depending on the version of the
+/// specification supported by the server can additionally declare the following fields. All of
+/// the paths must use the same path parameters, in the same order.
+///
+/// * `unstable_paths`: A list of paths of the endpoint before it was added to the specification,
+///   e.g. `["/_matrix/client/unstable/org.matrix.msc1234/foo"]`.
+/// * `stable_paths`: A list of paths of the endpoint in the specification, each with the version
+///   that introduced it, in ascending order of versions, e.g.
+///   `[1.0 => "/_matrix/client/r0/foo", 1.1 => "/_matrix/client/v3/foo"]`.
+/// * `deprecated`: The version of the specification that deprecated the endpoint.
+/// * `removed`: The version of the specification that removed the endpoint.
 /// * `rate_limited`: Whether or not the endpoint enforces rate limiting on requests.
 /// * `authentication`: What authentication scheme the endpoint uses.
 ///
@@ -87,9 +108,11 @@ use http::Method;
 /// into a `http::Request`:
 ///
 /// * `#[ruma_api(header = HEADER_NAME)]`: Fields with this attribute will be treated as HTTP
-///   headers on the request. The value must implement `AsRef<str>`. Generally this is a
-///   `String`. The attribute value shown above as `HEADER_NAME` must be a header name constant
-///   from `http::header`, e.g. `CONTENT_TYPE`.
+///   headers on the request. The value must implement `Display` and `FromStr`. Generally this
+///   is a `String`. The attribute value shown above as `HEADER_NAME` must be a header name
+///   constant from `http::header`, e.g. `CONTENT_TYPE`, or a lowercase string literal for
+///   extension headers, e.g. `"x-request-id"`. `Option` fields are only sent if they are
+///   `Some`.
 /// * `#[ruma_api(path)]`: Fields with this attribute will be inserted into the matching path
 ///   component of the request URL.
 /// * `#[ruma_api(query)]`: Fields with this attribute will be inserting into the URL's query
@@ -109,13 +132,17 @@ use http::Method;
 /// `http::Request`:
 ///
 /// * `#[ruma_api(header = HEADER_NAME)]`: Fields with this attribute will be treated as HTTP
-///   headers on the response. The value must implement `AsRef<str>`. Generally this is a
-///   `String`. The attribute value shown above as `HEADER_NAME` must be a header name constant
-///   from `http::header`, e.g. `CONTENT_TYPE`.
+///   headers on the response. Like for the request, the value must implement `Display` and
+///   `FromStr`, and `HEADER_NAME` is either a header name constant from `http::header` or a
+///   lowercase string literal.
 ///
 /// Any field that does not include the above attribute will be expected in the response's JSON
 /// body.
 ///
+/// The response block itself accepts a `#[ruma_api(status = STATUS_CODE)]` attribute, where
+/// `STATUS_CODE` is a status code constant from `http::StatusCode`, e.g. `CREATED`. It sets
+/// the status code of successful responses, which is `OK` by default.
+///
 /// ## Newtype bodies
 ///
 /// Both the request and response block also support "newtype bodies" by using the
@@ -200,19 +227,45 @@ use http::Method;
 /// ```
 pub use ruma_api_macros::ruma_api;
 
+pub mod auth;
 pub mod error;
+#[doc(hidden)]
+pub mod path;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod router;
+pub mod schema;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+mod version;
+
+pub use version::{MatrixVersion, UnknownVersionError};
+
 /// This module is used to support the generated code from ruma-api-macros.
 /// It is not considered part of ruma-api's public API.
 #[doc(hidden)]
 pub mod exports {
     pub use http;
+    #[cfg(feature = "registry")]
+    pub use linkme;
     pub use percent_encoding;
     pub use ruma_serde;
     pub use serde;
     pub use serde_json;
+
+    pub use crate::__register_endpoint as register_endpoint;
 }
 
-use error::{FromHttpRequestError, FromHttpResponseError, IntoHttpError};
+/// Registers the metadata of an endpoint, which does nothing without the `registry` feature.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_endpoint {
+    ($metadata:expr, $schema:expr) => {};
+}
+
+use auth::IncomingAuth;
+use error::{AuthError, FromHttpRequestError, FromHttpResponseError, IntoHttpError};
 
 /// Gives users the ability to define their own serializable / deserializable errors.
 pub trait EndpointError: StdError + Sized + 'static {
@@ -252,6 +305,27 @@ pub trait OutgoingRequest {
         base_url: &str,
         access_token: Option<&str>,
     ) -> Result<http::Request<Vec<u8>>, IntoHttpError>;
+
+    /// Tries to convert this request into an `http::Request`, using the path that matches the
+    /// given versions of the specification best.
+    ///
+    /// `considering_versions` should be the versions the server supports, as advertised in the
+    /// response to the `GET /_matrix/client/versions` endpoint. If it is empty, the default
+    /// `path` of the endpoint's metadata is used. See `Metadata::select_path` for details.
+    ///
+    /// The default implementation ignores the versions and calls `try_into_http_request`.
+    fn try_into_http_request_with_versions(
+        self,
+        base_url: &str,
+        access_token: Option<&str>,
+        considering_versions: &[MatrixVersion],
+    ) -> Result<http::Request<Vec<u8>>, IntoHttpError>
+    where
+        Self: Sized,
+    {
+        let _ = considering_versions;
+        self.try_into_http_request(base_url, access_token)
+    }
 }
 
 /// A request type for a Matrix API endpoint, used for receiving requests.
@@ -267,6 +341,14 @@ pub trait IncomingRequest: Sized {
 
     /// Tries to turn the given `http::Request` into this request type.
     fn try_from_http_request(req: http::Request<Vec<u8>>) -> Result<Self, FromHttpRequestError>;
+
+    /// Extracts the authentication data of the given `http::Request`, according to the
+    /// authentication scheme of this endpoint.
+    ///
+    /// See `IncomingAuth::extract` for details.
+    fn extract_auth<T>(req: &http::Request<T>) -> Result<IncomingAuth, AuthError> {
+        IncomingAuth::extract(req, Self::METADATA.authentication)
+    }
 }
 
 /// Marker trait for requests that don't require authentication, for the client side.
@@ -275,6 +357,79 @@ pub trait OutgoingNonAuthRequest: OutgoingRequest {}
 /// Marker trait for requests that don't require authentication, for the server side.
 pub trait IncomingNonAuthRequest: IncomingRequest {}
 
+/// A request with a `raw_body` field, for the client side.
+///
+/// This allows sending the body of the request from a source other than the request type, for
+/// example a stream that reads a file in chunks, instead of having it in memory as a `Vec<u8>`.
+pub trait OutgoingRawBodyRequest: OutgoingRequest + Sized {
+    /// Tries to convert this request into an `http::Request` with the given body.
+    ///
+    /// The body can be of any type, for example `bytes::Bytes` or a stream. It is used as-is,
+    /// without going through a `Vec<u8>`.
+    ///
+    /// The `raw_body` field of the request must be left empty, otherwise
+    /// `IntoHttpError::RawBodyNotEmpty` is returned.
+    fn try_into_http_request_with_body<B>(
+        self,
+        base_url: &str,
+        access_token: Option<&str>,
+        considering_versions: &[MatrixVersion],
+        body: B,
+    ) -> Result<http::Request<B>, IntoHttpError>;
+}
+
+/// A request with a `raw_body` field, for the server side.
+///
+/// This allows handling the body of the request separately, for example by streaming it to
+/// storage instead of collecting it into a `Vec<u8>` first.
+pub trait IncomingRawBodyRequest: IncomingRequest {
+    /// Tries to turn the head of the given `http::Request` into this request type.
+    ///
+    /// The `raw_body` field of the resulting request is left empty.
+    fn try_from_http_request_head(req: http::Request<()>) -> Result<Self, FromHttpRequestError> {
+        Self::try_from_http_request(req.map(|()| Vec::new()))
+    }
+}
+
+/// A response with a `raw_body` field, for the client side.
+///
+/// This allows handling the body of the response separately, for example by streaming it to
+/// storage instead of collecting it into a `Vec<u8>` first.
+pub trait IncomingRawBodyResponse<E: EndpointError>:
+    TryFrom<http::Response<Vec<u8>>, Error = FromHttpResponseError<E>>
+{
+    /// Tries to turn the head of the given successful `http::Response` into this response type.
+    ///
+    /// The `raw_body` field of the resulting response is left empty.
+    ///
+    /// Error responses should not be passed to this function, since their body is required to
+    /// deserialize the error. Use the `TryFrom<http::Response<Vec<u8>>>` implementation for them
+    /// instead.
+    fn try_from_http_response_head(
+        response: http::Response<()>,
+    ) -> Result<Self, FromHttpResponseError<E>> {
+        Self::try_from(response.map(|()| Vec::new()))
+    }
+}
+
+/// A response with a `raw_body` field, for the server side.
+///
+/// This allows sending the body of the response from a source other than the response type, for
+/// example a stream that reads a file in chunks, instead of having it in memory as a `Vec<u8>`.
+pub trait OutgoingRawBodyResponse: TryInto<http::Response<Vec<u8>>, Error = IntoHttpError> {
+    /// Tries to convert this response into an `http::Response` with the given body.
+    ///
+    /// The body can be of any type, for example `bytes::Bytes` or a stream. It is used as-is,
+    /// without going through a `Vec<u8>`.
+    ///
+    /// The `raw_body` field of the response must be left empty, otherwise
+    /// `IntoHttpError::RawBodyNotEmpty` is returned.
+    fn try_into_http_response_with_body<B>(
+        self,
+        body: B,
+    ) -> Result<http::Response<B>, IntoHttpError>;
+}
+
 /// Authentication scheme used by the endpoint.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AuthScheme {
@@ -309,8 +464,23 @@ pub struct Metadata {
 
     /// The path of this endpoint's URL, with variable names where path parameters should be filled
     /// in during a request.
+    ///
+    /// This is the path used when the versions supported by the server are unknown.
     pub path: &'static str,
 
+    /// The paths of this endpoint before it was added to the specification.
+    pub unstable_paths: &'static [&'static str],
+
+    /// The paths of this endpoint in the specification, with the version of the specification
+    /// that introduced each of them, in ascending order of versions.
+    pub stable_paths: &'static [(MatrixVersion, &'static str)],
+
+    /// The version of the specification that deprecated this endpoint.
+    pub deprecated: Option<MatrixVersion>,
+
+    /// The version of the specification that removed this endpoint.
+    pub removed: Option<MatrixVersion>,
+
     /// Whether or not this endpoint is rate limited by the server.
     pub rate_limited: bool,
 
@@ -318,6 +488,57 @@ pub struct Metadata {
     pub authentication: AuthScheme,
 }
 
+impl Metadata {
+    /// Returns all the paths this endpoint is available under, starting with the default `path`.
+    pub fn paths(&self) -> impl Iterator<Item = &'static str> {
+        let path = self.path;
+
+        std::iter::once(path).chain(
+            self.unstable_paths
+                .iter()
+                .copied()
+                .chain(self.stable_paths.iter().map(|(_, p)| *p))
+                .filter(move |p| *p != path),
+        )
+    }
+
+    /// Selects the path to use for a request to a server supporting the given versions of the
+    /// specification.
+    ///
+    /// * If `considering_versions` is empty, the default `path` is used.
+    /// * If all of the versions are equal to or newer than the version that removed the endpoint,
+    ///   an error is returned.
+    /// * Otherwise the stable path introduced by the newest version that is equal to or older than
+    ///   any of the versions is used.
+    /// * If there is no such stable path, the last of the unstable paths is used if there are
+    ///   any, the default `path` otherwise.
+    pub fn select_path(
+        &self,
+        considering_versions: &[MatrixVersion],
+    ) -> Result<&'static str, IntoHttpError> {
+        if considering_versions.is_empty() {
+            return Ok(self.path);
+        }
+
+        if let Some(removed) = self.removed {
+            if considering_versions.iter().all(|v| *v >= removed) {
+                return Err(IntoHttpError::EndpointRemoved(removed));
+            }
+        }
+
+        let stable_path = self
+            .stable_paths
+            .iter()
+            .rev()
+            .find(|(added, _)| considering_versions.iter().any(|v| v >= added));
+        if let Some((_, path)) = stable_path {
+            return Ok(path);
+        }
+
+        Ok(self.unstable_paths.last().copied().unwrap_or(self.path))
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! try_deserialize {
@@ -0,0 +1,88 @@
+//! Helpers for the code generated by `ruma_api!` and the router to deal with endpoint paths.
+
+use std::{borrow::Cow, mem, str::Utf8Error};
+
+use percent_encoding::percent_decode_str;
+
+use crate::Metadata;
+
+/// Fills in the `:param` segments of the given path template with the given (already
+/// percent-encoded) arguments, in order.
+pub fn fill(template: &str, args: &[String]) -> String {
+    let mut args = args.iter();
+    let mut res = String::with_capacity(template.len());
+
+    for segment in template[1..].split('/') {
+        res.push('/');
+        if segment.starts_with(':') {
+            res.push_str(args.next().expect("not enough arguments for path template"));
+        } else {
+            res.push_str(segment);
+        }
+    }
+
+    res
+}
+
+/// Splits the given request path into its percent-decoded segments.
+///
+/// Returns an error if a segment is not valid UTF-8 once decoded.
+pub fn decode_segments(request_path: &str) -> Result<Vec<Cow<'_, str>>, Utf8Error> {
+    request_path
+        .trim_start_matches('/')
+        .split('/')
+        .map(|s| percent_decode_str(s).decode_utf8())
+        .collect()
+}
+
+/// Checks whether the given percent-decoded request path segments match the given path template.
+///
+/// Returns the number of literal segments of the template if it matches.
+pub fn match_template(template: &str, segments: &[Cow<'_, str>]) -> Option<usize> {
+    let template_segments: Vec<_> = template[1..].split('/').collect();
+    if template_segments.len() != segments.len() {
+        return None;
+    }
+
+    let mut literal_segments = 0;
+    for (template_segment, segment) in template_segments.iter().zip(segments) {
+        if template_segment.starts_with(':') {
+            // Path parameters can't be empty.
+            if segment.is_empty() {
+                return None;
+            }
+        } else if template_segment != segment {
+            return None;
+        } else {
+            literal_segments += 1;
+        }
+    }
+
+    Some(literal_segments)
+}
+
+/// Extracts the percent-decoded segments of the given request path that correspond to path
+/// parameters of the endpoint, in order.
+///
+/// All paths of the endpoint are tried. If none of them matches, the parameters are extracted
+/// at the positions given by the default path.
+///
+/// Returns an error if a segment of the request path is not valid UTF-8 once decoded.
+pub fn extract<'a>(
+    metadata: &Metadata,
+    request_path: &'a str,
+) -> Result<Vec<Cow<'a, str>>, Utf8Error> {
+    let mut segments = decode_segments(request_path)?;
+
+    let template = metadata
+        .paths()
+        .find(|template| match_template(template, &segments).is_some())
+        .unwrap_or(metadata.path);
+
+    Ok(template[1..]
+        .split('/')
+        .enumerate()
+        .filter(|(_, s)| s.starts_with(':'))
+        .map(|(i, _)| segments.get_mut(i).map(mem::take).unwrap_or_default())
+        .collect())
+}
@@ -0,0 +1,69 @@
+//! A registry of the metadata of all the endpoints defined with `ruma_api!`.
+//!
+//! Every endpoint of the crates that are linked into the final binary is registered in
+//! `ENDPOINTS`, which relies on `linkme` and is not supported on all platforms.
+
+use crate::{schema::EndpointSchema, Metadata};
+
+/// An endpoint registered in `ENDPOINTS`.
+#[derive(Clone, Debug)]
+pub struct RegisteredEndpoint {
+    /// The path of the module in which the endpoint is defined, like
+    /// `ruma_client_api::r0::sync::sync_events`.
+    pub module_path: &'static str,
+
+    /// The metadata of the endpoint.
+    pub metadata: Metadata,
+
+    /// The schema of the endpoint, if the `schema` feature of its crate is enabled.
+    pub schema: Option<EndpointSchema>,
+}
+
+impl RegisteredEndpoint {
+    /// The name of the crate in which the endpoint is defined, like `ruma_client_api`.
+    pub fn crate_name(&self) -> &'static str {
+        self.module_path.split("::").next().unwrap_or(self.module_path)
+    }
+}
+
+/// All the registered endpoints.
+///
+/// Endpoints behind disabled features are not included.
+#[linkme::distributed_slice]
+pub static ENDPOINTS: [RegisteredEndpoint] = [..];
+
+/// The metadata of the registered endpoints defined in the crate with the given name, like
+/// `ruma_client_api`.
+pub fn endpoints_of(crate_name: &str) -> impl Iterator<Item = &'static Metadata> + '_ {
+    ENDPOINTS
+        .iter()
+        .filter(move |endpoint| endpoint.crate_name() == crate_name)
+        .map(|endpoint| &endpoint.metadata)
+}
+
+/// The schemas of the registered endpoints defined in the crate with the given name, like
+/// `ruma_client_api`.
+///
+/// This is empty if the `schema` feature of the crate is disabled.
+pub fn schemas_of(crate_name: &str) -> impl Iterator<Item = &'static EndpointSchema> + '_ {
+    ENDPOINTS
+        .iter()
+        .filter(move |endpoint| endpoint.crate_name() == crate_name)
+        .filter_map(|endpoint| endpoint.schema.as_ref())
+}
+
+/// Registers the metadata and the optional schema of an endpoint in `ENDPOINTS`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_endpoint {
+    ($metadata:expr, $schema:expr) => {
+        #[$crate::exports::linkme::distributed_slice($crate::registry::ENDPOINTS)]
+        #[linkme(crate = $crate::exports::linkme)]
+        static REGISTERED_ENDPOINT: $crate::registry::RegisteredEndpoint =
+            $crate::registry::RegisteredEndpoint {
+                module_path: module_path!(),
+                metadata: $metadata,
+                schema: $schema,
+            };
+    };
+}
@@ -0,0 +1,213 @@
+//! A framework-agnostic router for incoming Matrix API requests.
+
+use std::{
+    convert::TryInto,
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+};
+
+use http::{Method, StatusCode};
+
+use crate::{
+    error::{ErrorBody, ErrorKind, IntoHttpError, MatrixError},
+    path, IncomingRequest, Metadata,
+};
+
+/// The future returned by `Router::handle`.
+pub type ResponseFuture = Pin<Box<dyn Future<Output = http::Response<Vec<u8>>> + Send>>;
+
+type BoxedHandler = Box<dyn Fn(http::Request<Vec<u8>>) -> ResponseFuture + Send + Sync>;
+
+/// A router that dispatches `http::Request`s to handlers of the matching endpoint.
+///
+/// Handlers are registered with `Router::route` for a type implementing `IncomingRequest`, and the
+/// request is matched against all of the endpoint's paths, as given by `Metadata::paths`. Handler
+/// errors are converted to `http::Response`s using the endpoint's `EndpointError` type.
+///
+/// Requests that don't match any route are answered with a `404 Not Found` (or `405 Method Not
+/// Allowed` if only the method doesn't match) and an `M_UNRECOGNIZED` error, and requests that
+/// can't be deserialized with a `400 Bad Request` and an `M_BAD_JSON` error.
+///
+/// ```no_run
+/// # mod some_endpoint {
+/// #     ruma_api::ruma_api! {
+/// #         metadata: {
+/// #             description: "Does something.",
+/// #             method: GET,
+/// #             name: "some_endpoint",
+/// #             path: "/_matrix/some/endpoint/:baz",
+/// #             rate_limited: false,
+/// #             authentication: None,
+/// #         }
+/// #
+/// #         request: {
+/// #             #[ruma_api(path)]
+/// #             pub baz: String,
+/// #         }
+/// #
+/// #         response: {}
+/// #     }
+/// # }
+/// use ruma_api::router::Router;
+/// use some_endpoint::{Request, Response};
+///
+/// # async {
+/// let router = Router::new().route(|request: Request| async move {
+///     println!("got a request for {}", request.baz);
+///     Ok(Response {})
+/// });
+///
+/// # let http_request = http::Request::new(Vec::new());
+/// let http_response = router.handle(http_request).await;
+/// # };
+/// ```
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+struct Route {
+    metadata: Metadata,
+    handler: BoxedHandler,
+}
+
+impl Router {
+    /// Creates an empty `Router`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for the endpoint `R`.
+    ///
+    /// If several routes match a request, the one whose path has the most literal (non-parameter)
+    /// segments is used. Among those, the route that was added first wins.
+    pub fn route<R, F, Fut>(mut self, handler: F) -> Self
+    where
+        R: IncomingRequest + Send + 'static,
+        R::EndpointError: Into<http::Response<Vec<u8>>> + Send,
+        R::OutgoingResponse: Send,
+        F: Fn(R) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R::OutgoingResponse, R::EndpointError>> + Send + 'static,
+    {
+        let handler: BoxedHandler = Box::new(move |http_request| {
+            let request = match R::try_from_http_request(http_request) {
+                Ok(request) => request,
+                Err(err) => {
+                    let response = MatrixError::new(ErrorKind::BadJson, err.to_string()).into();
+                    return Box::pin(async move { response });
+                }
+            };
+
+            let response = handler(request);
+            Box::pin(async move {
+                match response.await {
+                    Ok(response) => response.try_into().unwrap_or_else(|err: IntoHttpError| {
+                        MatrixError::new(ErrorKind::Unknown, err.to_string()).into()
+                    }),
+                    Err(err) => err.into(),
+                }
+            })
+        });
+
+        self.routes.push(Route { metadata: R::METADATA, handler });
+        self
+    }
+
+    /// Returns the metadata of the endpoint that matches the given method and path, if any.
+    pub fn find(&self, method: &Method, path: &str) -> Option<&Metadata> {
+        self.find_route(method, path).ok().map(|route| &route.metadata)
+    }
+
+    /// Dispatches the given request to the handler of the matching endpoint.
+    pub fn handle(&self, request: http::Request<Vec<u8>>) -> ResponseFuture {
+        match self.find_route(request.method(), request.uri().path()) {
+            Ok(route) => (route.handler)(request),
+            Err(status) => {
+                let response = ErrorBody {
+                    kind: ErrorKind::Unrecognized,
+                    message: "Unrecognized request".to_owned(),
+                }
+                .into_error(status)
+                .into();
+                Box::pin(async move { response })
+            }
+        }
+    }
+
+    /// Wraps this router in a type implementing `tower::Service`.
+    #[cfg(feature = "tower")]
+    pub fn into_service(self) -> RouterService {
+        RouterService(std::sync::Arc::new(self))
+    }
+
+    fn find_route(&self, method: &Method, path: &str) -> Result<&Route, StatusCode> {
+        // Paths that aren't valid UTF-8 once decoded can't match any route.
+        let segments = path::decode_segments(path).map_err(|_| StatusCode::NOT_FOUND)?;
+
+        let mut path_matched = false;
+        let mut best_match: Option<(usize, &Route)> = None;
+
+        for route in &self.routes {
+            let literal_segments = match route
+                .metadata
+                .paths()
+                .filter_map(|template| path::match_template(template, &segments))
+                .max()
+            {
+                Some(count) => count,
+                None => continue,
+            };
+
+            path_matched = true;
+            if route.metadata.method != method {
+                continue;
+            }
+
+            match best_match {
+                Some((count, _)) if count >= literal_segments => {}
+                _ => best_match = Some((literal_segments, route)),
+            }
+        }
+
+        match best_match {
+            Some((_, route)) => Ok(route),
+            None if path_matched => Err(StatusCode::METHOD_NOT_ALLOWED),
+            None => Err(StatusCode::NOT_FOUND),
+        }
+    }
+}
+
+impl Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("routes", &self.routes.iter().map(|r| r.metadata.name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A `Router` wrapped in a type implementing `tower::Service`.
+///
+/// Created with `Router::into_service`.
+#[cfg(feature = "tower")]
+#[derive(Clone, Debug)]
+pub struct RouterService(std::sync::Arc<Router>);
+
+#[cfg(feature = "tower")]
+impl tower_service::Service<http::Request<Vec<u8>>> for RouterService {
+    type Response = http::Response<Vec<u8>>;
+    type Error = std::convert::Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Vec<u8>>) -> Self::Future {
+        let response = self.0.handle(request);
+        Box::pin(async move { Ok(response.await) })
+    }
+}
@@ -0,0 +1,351 @@
+//! Descriptions of endpoints for generating documentation, like OpenAPI documents.
+//!
+//! When the `schema` feature of a crate containing `ruma_api!` invocations is enabled, every
+//! endpoint module contains a `SCHEMA` constant of type `EndpointSchema`.
+
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+use crate::{AuthScheme, Metadata};
+
+/// The description of an endpoint.
+#[derive(Clone, Debug)]
+pub struct EndpointSchema {
+    /// The metadata of the endpoint.
+    pub metadata: Metadata,
+
+    /// The fields of the request.
+    pub request: &'static [FieldSchema],
+
+    /// The fields of the response.
+    pub response: &'static [FieldSchema],
+
+    /// The HTTP status code of successful responses.
+    pub status: http::StatusCode,
+}
+
+/// The description of a field of a request or response.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldSchema {
+    /// The name of the field in the HTTP message.
+    ///
+    /// This is the serialized name for path, query and body fields, the lowercase header name for
+    /// header fields and the Rust name of the field otherwise.
+    pub name: &'static str,
+
+    /// Where the field is located in the HTTP message.
+    pub location: FieldLocation,
+
+    /// The Rust type of the field.
+    pub ty: &'static str,
+
+    /// Whether the field is required.
+    pub required: bool,
+
+    /// The documentation of the field.
+    pub description: &'static str,
+}
+
+/// The location of a field in an HTTP message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldLocation {
+    /// A path parameter.
+    Path,
+
+    /// A query parameter.
+    Query,
+
+    /// All query parameters, as dynamic key-value pairs.
+    QueryMap,
+
+    /// An HTTP header.
+    Header,
+
+    /// A field of the JSON body.
+    Body,
+
+    /// The whole JSON body.
+    NewtypeBody,
+
+    /// The whole body, as arbitrary bytes.
+    RawBody,
+}
+
+/// Generates an [OpenAPI 3](https://spec.openapis.org/oas/v3.0.3) document describing the given
+/// endpoints.
+///
+/// Every path of an endpoint is included. The Rust type of each field is available in the
+/// `x-rust-type` extension of its schema.
+pub fn openapi_document(title: &str, version: &str, endpoints: &[EndpointSchema]) -> JsonValue {
+    let mut paths = JsonMap::new();
+
+    for endpoint in endpoints {
+        let operation = operation(endpoint);
+        let method = endpoint.metadata.method.as_str().to_lowercase();
+
+        for path in endpoint.metadata.paths() {
+            let path_item = paths.entry(openapi_path(path)).or_insert_with(|| json!({}));
+            path_item[&method] = operation.clone();
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": title,
+            "version": version,
+        },
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "accessToken": {
+                    "type": "http",
+                    "scheme": "bearer",
+                },
+                "accessTokenQuery": {
+                    "type": "apiKey",
+                    "in": "query",
+                    "name": "access_token",
+                },
+            },
+        },
+    })
+}
+
+/// Converts a path with `:param` segments to one with `{param}` segments.
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(param) => format!("{{{}}}", param),
+            None => segment.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter_map(|segment| segment.strip_prefix(':'))
+}
+
+fn operation(endpoint: &EndpointSchema) -> JsonValue {
+    let metadata = &endpoint.metadata;
+
+    let mut parameters: Vec<_> = endpoint
+        .request
+        .iter()
+        .filter_map(|field| {
+            let location = match field.location {
+                FieldLocation::Path => "path",
+                FieldLocation::Query => "query",
+                FieldLocation::Header => "header",
+                _ => return None,
+            };
+
+            Some(json!({
+                "name": field.name,
+                "in": location,
+                "required": field.required || field.location == FieldLocation::Path,
+                "description": field.description,
+                "schema": type_schema(field.ty),
+            }))
+        })
+        .collect();
+
+    // Endpoints with a manual `Request` type have no request fields, but their path parameters
+    // still need to be declared.
+    for name in path_params(metadata.path) {
+        if !endpoint.request.iter().any(|f| f.location == FieldLocation::Path && f.name == name) {
+            parameters.push(json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            }));
+        }
+    }
+
+    let mut responses = JsonMap::new();
+    responses.insert(
+        endpoint.status.as_str().to_owned(),
+        response(endpoint.response, "The request was successful."),
+    );
+    if metadata.rate_limited {
+        responses
+            .insert("429".to_owned(), json!({ "description": "This request was rate-limited." }));
+    }
+
+    let mut operation = json!({
+        "operationId": metadata.name,
+        "summary": metadata.description,
+        "parameters": parameters,
+        "responses": responses,
+    });
+
+    if let Some(body) = body(endpoint.request) {
+        operation["requestBody"] = body;
+    }
+
+    match metadata.authentication {
+        AuthScheme::AccessToken => {
+            operation["security"] = json!([{ "accessToken": [] }, { "accessTokenQuery": [] }]);
+        }
+        AuthScheme::QueryOnlyAccessToken => {
+            operation["security"] = json!([{ "accessTokenQuery": [] }]);
+        }
+        AuthScheme::None | AuthScheme::ServerSignatures => {}
+    }
+
+    if metadata.deprecated.is_some() {
+        operation["deprecated"] = json!(true);
+    }
+
+    operation
+}
+
+fn response(fields: &[FieldSchema], description: &str) -> JsonValue {
+    let headers: JsonMap<_, _> = fields
+        .iter()
+        .filter(|field| field.location == FieldLocation::Header)
+        .map(|field| {
+            let header = json!({
+                "description": field.description,
+                "schema": type_schema(field.ty),
+            });
+            (field.name.to_owned(), header)
+        })
+        .collect();
+
+    let mut response = json!({ "description": description });
+    if !headers.is_empty() {
+        response["headers"] = headers.into();
+    }
+    if let Some(body) = body(fields) {
+        response["content"] = body["content"].clone();
+    }
+
+    response
+}
+
+/// Generates the OpenAPI request body for the given fields, if they contain body fields.
+fn body(fields: &[FieldSchema]) -> Option<JsonValue> {
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+
+    for field in fields {
+        match field.location {
+            FieldLocation::Body => {
+                let mut schema = type_schema(field.ty);
+                schema["description"] = field.description.into();
+                properties.insert(field.name.to_owned(), schema);
+                if field.required {
+                    required.push(field.name);
+                }
+            }
+            FieldLocation::NewtypeBody => {
+                let mut schema = type_schema(field.ty);
+                schema["description"] = field.description.into();
+                return Some(json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": schema } },
+                }));
+            }
+            FieldLocation::RawBody => {
+                return Some(json!({
+                    "required": true,
+                    "content": {
+                        "application/octet-stream": {
+                            "schema": { "type": "string", "format": "binary" },
+                        },
+                    },
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    if properties.is_empty() {
+        return None;
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = required.into();
+    }
+
+    Some(json!({
+        "required": true,
+        "content": { "application/json": { "schema": schema } },
+    }))
+}
+
+/// Generates a JSON schema for the given Rust type.
+///
+/// Only the outermost type is taken into account. The full Rust type is available in the
+/// `x-rust-type` extension.
+fn type_schema(ty: &str) -> JsonValue {
+    let mut inner = ty.strip_prefix("Option<").and_then(|ty| ty.strip_suffix('>')).unwrap_or(ty);
+    if let Some(referenced) = inner.strip_prefix('&') {
+        inner = match referenced.strip_prefix('\'') {
+            // Skip the lifetime.
+            Some(lifetime) => {
+                lifetime.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+            }
+            None => referenced,
+        }
+        .trim_start();
+    }
+    let name = inner.split('<').next().unwrap_or(inner).rsplit("::").next().unwrap_or(inner);
+
+    let json_type = match name {
+        "bool" => Some("boolean"),
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize"
+        | "UInt" | "Int" => Some("integer"),
+        "f32" | "f64" => Some("number"),
+        "Vec" | "BTreeSet" | "HashSet" => Some("array"),
+        "BTreeMap" | "HashMap" | "JsonValue" => Some("object"),
+        "str" | "String" | "Cow" | "MxcUri" | "ServerName" | "ServerNameBox" | "RoomVersionId"
+        | "DeviceId" | "DeviceIdBox" | "DeviceKeyId" => Some("string"),
+        _ if name.starts_with('[') => Some("array"),
+        _ if name.ends_with("Id") => Some("string"),
+        _ => None,
+    };
+
+    let mut schema = json!({ "x-rust-type": ty });
+    if let Some(json_type) = json_type {
+        schema["type"] = json_type.into();
+    }
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{openapi_path, type_schema};
+
+    #[test]
+    fn convert_path() {
+        assert_eq!(
+            openapi_path("/_matrix/client/r0/rooms/:room_id/state/:event_type"),
+            "/_matrix/client/r0/rooms/{room_id}/state/{event_type}"
+        );
+    }
+
+    #[test]
+    fn type_schemas() {
+        assert_eq!(type_schema("bool"), json!({ "type": "boolean", "x-rust-type": "bool" }));
+        assert_eq!(
+            type_schema("Option<&'a str>"),
+            json!({ "type": "string", "x-rust-type": "Option<&'a str>" })
+        );
+        assert_eq!(
+            type_schema("&'a RoomId"),
+            json!({ "type": "string", "x-rust-type": "&'a RoomId" })
+        );
+        assert_eq!(
+            type_schema("Vec<Raw<AnyStateEvent>>"),
+            json!({ "type": "array", "x-rust-type": "Vec<Raw<AnyStateEvent>>" })
+        );
+        assert_eq!(type_schema("Filter<'a>"), json!({ "x-rust-type": "Filter<'a>" }));
+    }
+}
@@ -0,0 +1,285 @@
+//! Helpers for testing the HTTP (de)serialization of endpoints against recorded examples.
+//!
+//! Only available with the `test-utils` feature.
+//!
+//! A [`Fixture`] is a recorded request and response of an endpoint, usually taken from the
+//! examples of the Matrix specification. The functions of this module check that the types of an
+//! endpoint produce and accept exactly these HTTP messages, and that converting a value to an HTTP
+//! message and back yields the same value.
+//!
+//! Values are compared through their HTTP serialization: a value that is converted back to an HTTP
+//! message must yield the same method, path, query string, headers and JSON body.
+//!
+//! All functions panic if a check fails.
+
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+    fmt::Debug,
+};
+
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde_json::{from_slice as from_json_slice, to_vec as to_json_vec, Value as JsonValue};
+
+use crate::{
+    error::{FromHttpResponseError, IntoHttpError},
+    EndpointError, IncomingRequest, OutgoingRequest,
+};
+
+/// The base URL used for outgoing requests.
+const BASE_URL: &str = "https://homeserver.tld";
+
+/// The access token used for outgoing requests.
+///
+/// It is not compared against the recorded request.
+const ACCESS_TOKEN: &str = "access_token";
+
+/// A recorded request and response of an endpoint.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Fixture {
+    /// The request.
+    pub request: RequestFixture,
+
+    /// The successful response to the request.
+    pub response: ResponseFixture,
+}
+
+impl Fixture {
+    /// Deserializes a fixture from JSON, usually included with `include_str!`.
+    ///
+    /// Panics if the JSON is not a valid fixture.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).expect("invalid fixture")
+    }
+}
+
+/// A recorded HTTP request.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RequestFixture {
+    /// The HTTP method, like `GET`.
+    pub method: String,
+
+    /// The path and query string of the request.
+    pub uri: String,
+
+    /// Headers of the request.
+    ///
+    /// Headers of the actual request that are not listed here are ignored.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// The JSON body of the request.
+    #[serde(default)]
+    pub body: Option<JsonValue>,
+
+    /// The body of the request, for endpoints whose body is not JSON.
+    #[serde(default)]
+    pub raw_body: Option<String>,
+}
+
+impl RequestFixture {
+    /// Converts the fixture to an `http::Request`.
+    pub fn to_http_request(&self) -> http::Request<Vec<u8>> {
+        let mut builder = http::Request::builder()
+            .method(self.method.as_str())
+            .uri(format!("{}{}", BASE_URL, self.uri));
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder.body(fixture_body(&self.body, &self.raw_body)).expect("invalid request fixture")
+    }
+}
+
+/// A recorded HTTP response.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ResponseFixture {
+    /// The HTTP status code.
+    #[serde(default = "default_status")]
+    pub status: u16,
+
+    /// Headers of the response.
+    ///
+    /// Headers of the actual response that are not listed here are ignored.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// The JSON body of the response.
+    #[serde(default)]
+    pub body: Option<JsonValue>,
+
+    /// The body of the response, for endpoints whose body is not JSON.
+    #[serde(default)]
+    pub raw_body: Option<String>,
+}
+
+impl ResponseFixture {
+    /// Converts the fixture to an `http::Response`.
+    pub fn to_http_response(&self) -> http::Response<Vec<u8>> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder.body(fixture_body(&self.body, &self.raw_body)).expect("invalid response fixture")
+    }
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+fn fixture_body(body: &Option<JsonValue>, raw_body: &Option<String>) -> Vec<u8> {
+    match (body, raw_body) {
+        (Some(body), _) => to_json_vec(body).unwrap(),
+        (None, Some(raw_body)) => raw_body.as_bytes().to_vec(),
+        (None, None) => Vec::new(),
+    }
+}
+
+/// Checks that the given outgoing request is converted to the recorded request, and that both the
+/// resulting `http::Request` and the recorded request are accepted by the given incoming request
+/// type.
+///
+/// Returns the incoming request converted from the `http::Request` for further checks.
+pub fn check_request<R, I>(request: R, fixture: &RequestFixture) -> I
+where
+    R: OutgoingRequest,
+    I: IncomingRequest,
+{
+    let http_request = request
+        .try_into_http_request(BASE_URL, Some(ACCESS_TOKEN))
+        .expect("failed to convert outgoing request to http::Request");
+    assert_request_matches(&http_request, fixture);
+
+    let _: I = check_incoming_request(fixture);
+    I::try_from_http_request(http_request)
+        .expect("failed to convert http::Request to incoming request")
+}
+
+/// Checks that the recorded request is accepted by the given incoming request type.
+///
+/// Returns the incoming request for further checks.
+pub fn check_incoming_request<I: IncomingRequest>(fixture: &RequestFixture) -> I {
+    I::try_from_http_request(fixture.to_http_request())
+        .expect("failed to convert recorded request to incoming request")
+}
+
+/// Checks that the recorded request is accepted by the given request type, and that converting
+/// it back to an `http::Request` yields the recorded request.
+///
+/// Only works for endpoints whose request type is used for both outgoing and incoming requests,
+/// i.e. whose request doesn't borrow any data.
+pub fn round_trip_request<R>(fixture: &RequestFixture) -> R
+where
+    R: IncomingRequest + OutgoingRequest,
+{
+    let request: R = check_incoming_request(fixture);
+    let request: R = check_request(request, fixture);
+
+    // Converting the request again checks that no data was lost in the round trip.
+    let http_request = request
+        .try_into_http_request(BASE_URL, Some(ACCESS_TOKEN))
+        .expect("failed to convert request to http::Request");
+    assert_request_matches(&http_request, fixture);
+
+    R::try_from_http_request(http_request).expect("failed to convert http::Request back to request")
+}
+
+/// Checks that the recorded response is accepted by the given response type, and that converting
+/// it back to an `http::Response` yields the recorded response.
+///
+/// Returns the response for further checks.
+pub fn round_trip_response<R, E>(fixture: &ResponseFixture) -> R
+where
+    R: TryFrom<http::Response<Vec<u8>>, Error = FromHttpResponseError<E>>
+        + TryInto<http::Response<Vec<u8>>, Error = IntoHttpError>,
+    E: EndpointError + Debug,
+{
+    let mut response = R::try_from(fixture.to_http_response())
+        .expect("failed to convert recorded response to response");
+
+    // Converting the response twice checks that no data was lost in the round trip.
+    for _ in 0..2 {
+        let http_response: http::Response<Vec<u8>> =
+            response.try_into().expect("failed to convert response to http::Response");
+        assert_eq!(http_response.status().as_u16(), fixture.status, "status code mismatch");
+        assert_headers_match(http_response.headers(), &fixture.headers);
+        assert_body_matches(http_response.body(), &fixture.body, &fixture.raw_body);
+
+        response =
+            R::try_from(http_response).expect("failed to convert http::Response back to response");
+    }
+
+    response
+}
+
+/// Checks that the given `http::Request` matches the recorded request.
+pub fn assert_request_matches(http_request: &http::Request<Vec<u8>>, fixture: &RequestFixture) {
+    assert_eq!(http_request.method().as_str(), fixture.method, "method mismatch");
+
+    let expected_uri: http::Uri = fixture.uri.parse().expect("invalid URI in request fixture");
+    assert_eq!(
+        decode_path(http_request.uri().path()),
+        decode_path(expected_uri.path()),
+        "path mismatch"
+    );
+
+    let expected_query = query_pairs(expected_uri.query());
+    let mut actual_query = query_pairs(http_request.uri().query());
+    if !expected_query.iter().any(|(key, _)| key == "access_token") {
+        actual_query.retain(|(key, _)| key != "access_token");
+    }
+    assert_eq!(actual_query, expected_query, "query string mismatch");
+
+    assert_headers_match(http_request.headers(), &fixture.headers);
+    assert_body_matches(http_request.body(), &fixture.body, &fixture.raw_body);
+}
+
+fn decode_path(path: &str) -> Vec<String> {
+    path.split('/').map(|segment| percent_decode_str(segment).decode_utf8_lossy().into()).collect()
+}
+
+/// Splits the given query string into sorted, decoded key-value pairs.
+fn query_pairs(query: Option<&str>) -> Vec<(String, String)> {
+    let decode = |s: &str| percent_decode_str(&s.replace('+', " ")).decode_utf8_lossy().into();
+
+    let mut pairs: Vec<(String, String)> = query
+        .unwrap_or("")
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = parts.next().unwrap();
+            (decode(key), decode(parts.next().unwrap_or("")))
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+fn assert_headers_match(headers: &http::HeaderMap, expected: &BTreeMap<String, String>) {
+    for (name, value) in expected {
+        assert_eq!(
+            headers.get(name.as_str()).and_then(|v| v.to_str().ok()),
+            Some(value.as_str()),
+            "header mismatch for `{}`",
+            name
+        );
+    }
+}
+
+fn assert_body_matches(body: &[u8], expected: &Option<JsonValue>, expected_raw: &Option<String>) {
+    match (expected, expected_raw) {
+        (Some(expected), _) => {
+            let body: JsonValue = from_json_slice(body).expect("body is not valid JSON");
+            assert_eq!(&body, expected, "body mismatch");
+        }
+        (None, Some(expected_raw)) => {
+            assert_eq!(body, expected_raw.as_bytes(), "body mismatch");
+        }
+        // Endpoints without body fields send an empty JSON object or no body at all.
+        (None, None) => assert!(body.is_empty() || body == b"{}", "unexpected body"),
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    convert::TryFrom,
+    error::Error as StdError,
+    fmt::{self, Display},
+};
+
+/// A version of the Matrix specification.
+///
+/// The `r0.x.x` releases of the specification all map to `V1_0`, since the individual APIs
+/// only started to be versioned as a whole with `v1.1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MatrixVersion {
+    /// Version 1.0 of the Matrix specification, i.e. any of the `r0.x.x` releases.
+    V1_0,
+
+    /// Version 1.1 of the Matrix specification.
+    V1_1,
+
+    /// Version 1.2 of the Matrix specification.
+    V1_2,
+}
+
+impl MatrixVersion {
+    /// Returns the major and minor version numbers of this version.
+    pub fn into_parts(self) -> (u8, u8) {
+        match self {
+            Self::V1_0 => (1, 0),
+            Self::V1_1 => (1, 1),
+            Self::V1_2 => (1, 2),
+        }
+    }
+
+    /// Tries to convert the given major and minor version numbers into a `MatrixVersion`.
+    pub fn from_parts(major: u8, minor: u8) -> Result<Self, UnknownVersionError> {
+        match (major, minor) {
+            (1, 0) => Ok(Self::V1_0),
+            (1, 1) => Ok(Self::V1_1),
+            (1, 2) => Ok(Self::V1_2),
+            _ => Err(UnknownVersionError),
+        }
+    }
+}
+
+impl TryFrom<&str> for MatrixVersion {
+    type Error = UnknownVersionError;
+
+    /// Converts a version string as advertised by `GET /_matrix/client/versions`, such as
+    /// `r0.6.1` or `v1.1`.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            v if v.starts_with("r0.") => Ok(Self::V1_0),
+            "v1.1" => Ok(Self::V1_1),
+            "v1.2" => Ok(Self::V1_2),
+            _ => Err(UnknownVersionError),
+        }
+    }
+}
+
+impl Display for MatrixVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor) = self.into_parts();
+        write!(f, "v{}.{}", major, minor)
+    }
+}
+
+/// An error that happens when trying to convert an unknown version string into a
+/// `MatrixVersion`.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct UnknownVersionError;
+
+impl Display for UnknownVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "version string was unknown")
+    }
+}
+
+impl StdError for UnknownVersionError {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::MatrixVersion;
+
+    #[test]
+    fn parse_version_strings() {
+        assert_eq!(MatrixVersion::try_from("r0.6.1").unwrap(), MatrixVersion::V1_0);
+        assert_eq!(MatrixVersion::try_from("r0.0.1").unwrap(), MatrixVersion::V1_0);
+        assert_eq!(MatrixVersion::try_from("v1.1").unwrap(), MatrixVersion::V1_1);
+        assert_eq!(MatrixVersion::try_from("v1.2").unwrap(), MatrixVersion::V1_2);
+        assert!(MatrixVersion::try_from("v2.0").is_err());
+        assert!(MatrixVersion::try_from("1.1").is_err());
+    }
+
+    #[test]
+    fn version_ordering() {
+        assert!(MatrixVersion::V1_0 < MatrixVersion::V1_1);
+        assert!(MatrixVersion::V1_1 < MatrixVersion::V1_2);
+    }
+}
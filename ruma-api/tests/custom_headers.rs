@@ -0,0 +1,138 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use http::{header::CACHE_CONTROL, StatusCode};
+use ruma_api::{
+    error::{FromHttpResponseError, IntoHttpError},
+    ruma_api, IncomingRequest as _, OutgoingRequest as _,
+};
+
+/// A header value that is not a string.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Fast,
+    Slow,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Mode::Fast => "fast",
+            Mode::Slow => "slow",
+        })
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fast" => Ok(Mode::Fast),
+            "slow" => Ok(Mode::Slow),
+            _ => Err(format!("unknown mode `{}`", s)),
+        }
+    }
+}
+
+ruma_api! {
+    metadata: {
+        description: "Does something.",
+        method: POST,
+        name: "custom_headers",
+        path: "/_matrix/my/endpoint",
+        rate_limited: false,
+        authentication: None,
+    }
+
+    request: {
+        #[ruma_api(header = "x-mode")]
+        pub mode: Mode,
+
+        #[ruma_api(header = "x-limit")]
+        pub limit: Option<u64>,
+    }
+
+    #[ruma_api(status = ACCEPTED)]
+    response: {
+        #[ruma_api(header = CACHE_CONTROL)]
+        pub cache_control: Option<String>,
+
+        #[ruma_api(header = "x-retry-count")]
+        pub retry_count: u64,
+    }
+}
+
+#[test]
+fn request_headers_round_trip() {
+    let req = Request { mode: Mode::Slow, limit: Some(10) };
+    let http_req = req.try_into_http_request("https://homeserver.tld", None).unwrap();
+
+    assert_eq!(http_req.headers().get("x-mode").unwrap(), "slow");
+    assert_eq!(http_req.headers().get("x-limit").unwrap(), "10");
+
+    let req = Request::try_from_http_request(http_req).unwrap();
+    assert_eq!(req.mode, Mode::Slow);
+    assert_eq!(req.limit, Some(10));
+}
+
+#[test]
+fn request_optional_header_missing() {
+    let req = Request { mode: Mode::Fast, limit: None };
+    let http_req = req.try_into_http_request("https://homeserver.tld", None).unwrap();
+    assert!(http_req.headers().get("x-limit").is_none());
+
+    let req = Request::try_from_http_request(http_req).unwrap();
+    assert_eq!(req.limit, None);
+}
+
+#[test]
+fn request_invalid_header() {
+    let http_req = http::Request::post("https://homeserver.tld/_matrix/my/endpoint")
+        .header("x-mode", "medium")
+        .body(b"{}".to_vec())
+        .unwrap();
+
+    let err = Request::try_from_http_request(http_req).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "deserialization failed: Invalid value for header `x-mode`: unknown mode `medium`"
+    );
+}
+
+#[test]
+fn response_status_and_headers() {
+    let res = Response { cache_control: Some("no-cache".into()), retry_count: 3 };
+    let http_res: http::Response<Vec<u8>> = res.try_into().unwrap();
+
+    assert_eq!(http_res.status(), StatusCode::ACCEPTED);
+    assert_eq!(http_res.headers().get(CACHE_CONTROL).unwrap(), "no-cache");
+    assert_eq!(http_res.headers().get("x-retry-count").unwrap(), "3");
+
+    let res = Response::try_from(http_res).unwrap();
+    assert_eq!(res.cache_control.as_deref(), Some("no-cache"));
+    assert_eq!(res.retry_count, 3);
+}
+
+#[test]
+fn response_missing_header() {
+    let http_res = http::Response::builder().status(202).body(b"{}".to_vec()).unwrap();
+
+    match Response::try_from(http_res) {
+        Err(FromHttpResponseError::Deserialization(err)) => {
+            assert_eq!(err.to_string(), "Missing header `x-retry-count`");
+        }
+        other => panic!("expected a deserialization error, got {:?}", other),
+    }
+}
+
+#[test]
+fn response_invalid_header_value() {
+    let res = Response { cache_control: Some("no-cache\n".into()), retry_count: 0 };
+    let result: Result<http::Response<Vec<u8>>, _> = res.try_into();
+
+    assert!(matches!(result, Err(IntoHttpError::Header(_))));
+}
@@ -30,6 +30,10 @@ const METADATA: Metadata = Metadata {
     method: Method::PUT,
     name: "create_alias",
     path: "/_matrix/client/r0/directory/room/:room_alias",
+    unstable_paths: &[],
+    stable_paths: &[],
+    deprecated: None,
+    removed: None,
     rate_limited: false,
     authentication: AuthScheme::None,
 };
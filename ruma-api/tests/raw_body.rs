@@ -0,0 +1,86 @@
+use ruma_api::{
+    error::IntoHttpError, ruma_api, IncomingRawBodyRequest as _, IncomingRawBodyResponse as _,
+    OutgoingRawBodyRequest as _, OutgoingRawBodyResponse as _,
+};
+
+ruma_api! {
+    metadata: {
+        description: "Does something.",
+        method: POST,
+        name: "upload",
+        path: "/_matrix/foo/upload/:name",
+        rate_limited: false,
+        authentication: None,
+    }
+
+    request: {
+        #[ruma_api(path)]
+        pub name: String,
+
+        #[ruma_api(header = CONTENT_TYPE)]
+        pub content_type: String,
+
+        #[ruma_api(raw_body)]
+        pub file: Vec<u8>,
+    }
+
+    response: {
+        #[ruma_api(header = CONTENT_TYPE)]
+        pub content_type: String,
+
+        #[ruma_api(raw_body)]
+        pub file: Vec<u8>,
+    }
+}
+
+#[test]
+fn request_with_body() {
+    let req = Request {
+        name: "cat.png".to_owned(),
+        content_type: "image/png".to_owned(),
+        file: Vec::new(),
+    };
+
+    let http_req =
+        req.try_into_http_request_with_body("https://homeserver.tld", None, &[], "body").unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/foo/upload/cat%2Epng");
+    assert_eq!(http_req.headers()[http::header::CONTENT_TYPE], "image/png");
+    assert_eq!(*http_req.body(), "body");
+
+    let req = Request::try_from_http_request_head(http_req.map(|_| ())).unwrap();
+    assert_eq!(req.name, "cat.png");
+    assert_eq!(req.content_type, "image/png");
+    assert!(req.file.is_empty());
+}
+
+#[test]
+fn response_with_body() {
+    let res = Response { content_type: "image/png".to_owned(), file: Vec::new() };
+
+    let http_res = res.try_into_http_response_with_body(vec![1, 2, 3]).unwrap();
+    assert_eq!(http_res.headers()[http::header::CONTENT_TYPE], "image/png");
+    assert_eq!(*http_res.body(), vec![1, 2, 3]);
+
+    let res = Response::try_from_http_response_head(http_res.map(|_| ())).unwrap();
+    assert_eq!(res.content_type, "image/png");
+    assert!(res.file.is_empty());
+}
+
+#[test]
+fn raw_body_not_empty() {
+    let req = Request {
+        name: "cat.png".to_owned(),
+        content_type: "image/png".to_owned(),
+        file: vec![1, 2, 3],
+    };
+    assert!(matches!(
+        req.try_into_http_request_with_body("https://homeserver.tld", None, &[], "body"),
+        Err(IntoHttpError::RawBodyNotEmpty)
+    ));
+
+    let res = Response { content_type: "image/png".to_owned(), file: vec![1, 2, 3] };
+    assert!(matches!(
+        res.try_into_http_response_with_body(vec![4, 5, 6]),
+        Err(IntoHttpError::RawBodyNotEmpty)
+    ));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "registry")]
+
+use ruma_api::registry::{endpoints_of, schemas_of, ENDPOINTS};
+
+mod some_endpoint {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: GET,
+            name: "registered_endpoint",
+            path: "/_matrix/some/endpoint",
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {}
+        response: {}
+    }
+}
+
+#[test]
+fn endpoint_is_registered() {
+    let endpoint = ENDPOINTS
+        .iter()
+        .find(|endpoint| endpoint.metadata.name == "registered_endpoint")
+        .expect("endpoint is registered");
+
+    assert_eq!(endpoint.module_path, "registry::some_endpoint");
+    assert_eq!(endpoint.crate_name(), "registry");
+    assert!(endpoints_of("registry").any(|metadata| metadata.name == "registered_endpoint"));
+    assert_eq!(endpoints_of("ruma_api").count(), 0);
+
+    let schema = endpoint.schema.as_ref().expect("schema is registered");
+    assert_eq!(schema.metadata.name, "registered_endpoint");
+    assert!(schemas_of("registry").any(|schema| schema.metadata.name == "registered_endpoint"));
+}
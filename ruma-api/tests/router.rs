@@ -0,0 +1,152 @@
+use http::{Method, StatusCode};
+use ruma_api::{router::Router, OutgoingRequest as _};
+
+mod get_thing {
+    ruma_api::ruma_api! {
+        metadata: {
+            description: "Gets a thing.",
+            method: GET,
+            name: "get_thing",
+            stable_paths: [
+                1.0 => "/_matrix/client/r0/things/:thing",
+                1.1 => "/_matrix/client/v3/things/:thing",
+            ],
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub thing: String,
+        }
+
+        response: {
+            pub thing: String,
+        }
+    }
+}
+
+mod get_default_thing {
+    ruma_api::ruma_api! {
+        metadata: {
+            description: "Gets the default thing.",
+            method: GET,
+            name: "get_default_thing",
+            path: "/_matrix/client/r0/things/default",
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {}
+
+        response: {
+            pub thing: String,
+        }
+    }
+}
+
+mod get_owner {
+    ruma_api::ruma_api! {
+        metadata: {
+            description: "Gets the owner of a thing.",
+            method: GET,
+            name: "get_owner",
+            stable_paths: [
+                1.0 => "/_matrix/client/r0/things/:thing/owner",
+                1.1 => "/_matrix/client/v3/owner/of/:thing",
+            ],
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub thing: String,
+        }
+
+        response: {
+            pub thing: String,
+        }
+    }
+}
+
+fn router() -> Router {
+    Router::new()
+        .route(
+            |req: get_thing::Request| async move { Ok(get_thing::Response { thing: req.thing }) },
+        )
+        .route(|_: get_default_thing::Request| async move {
+            Ok(get_default_thing::Response { thing: "default".to_owned() })
+        })
+        .route(
+            |req: get_owner::Request| async move { Ok(get_owner::Response { thing: req.thing }) },
+        )
+}
+
+fn response_json(response: &http::Response<Vec<u8>>) -> serde_json::Value {
+    serde_json::from_slice(response.body()).unwrap()
+}
+
+#[tokio::test]
+async fn dispatch_on_all_paths() {
+    let router = router();
+
+    let request = get_thing::Request { thing: "a b".to_owned() }
+        .try_into_http_request("https://homeserver.tld", None)
+        .unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_json(&response), serde_json::json!({ "thing": "a b" }));
+
+    let request = http::Request::get("/_matrix/client/v3/things/c%2Fd").body(Vec::new()).unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_json(&response), serde_json::json!({ "thing": "c/d" }));
+}
+
+#[tokio::test]
+async fn prefer_literal_segments() {
+    let router = router();
+
+    let request = http::Request::get("/_matrix/client/r0/things/default").body(Vec::new()).unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response_json(&response), serde_json::json!({ "thing": "default" }));
+
+    // Percent-encoded literal segments are decoded before matching.
+    let metadata = router.find(&Method::GET, "/_matrix/client/r0/things/%64efault").unwrap();
+    assert_eq!(metadata.name, "get_default_thing");
+}
+
+#[tokio::test]
+async fn extract_from_matched_path() {
+    let router = router();
+
+    // The path parameters are extracted from the path that the router matched, after decoding.
+    let request = http::Request::get("/_matrix/client/v%33/owner/of/abc").body(Vec::new()).unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_json(&response), serde_json::json!({ "thing": "abc" }));
+
+    let request = http::Request::get("/_matrix/client/r0/things/%FF").body(Vec::new()).unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn unrecognized_requests() {
+    let router = router();
+
+    let request = http::Request::get("/_matrix/client/r0/nothing").body(Vec::new()).unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response_json(&response)["errcode"], "M_UNRECOGNIZED");
+
+    let request = http::Request::builder()
+        .method(Method::PUT)
+        .uri("/_matrix/client/r0/things/foo")
+        .body(Vec::new())
+        .unwrap();
+    let response = router.handle(request).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response_json(&response)["errcode"], "M_UNRECOGNIZED");
+}
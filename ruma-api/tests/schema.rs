@@ -0,0 +1,107 @@
+use ruma_api::schema::{openapi_document, FieldLocation};
+use serde_json::json;
+
+mod set_thing {
+    use std::collections::BTreeMap;
+
+    use ruma_identifiers::RoomId;
+
+    ruma_api::ruma_api! {
+        metadata: {
+            description: "Sets a thing.",
+            method: PUT,
+            name: "set_thing",
+            stable_paths: [
+                1.0 => "/_matrix/client/r0/rooms/:room_id/things",
+                1.1 => "/_matrix/client/v3/rooms/:room_id/things",
+            ],
+            rate_limited: true,
+            authentication: AccessToken,
+        }
+
+        request: {
+            /// The room to set the thing in.
+            #[ruma_api(path)]
+            pub room_id: RoomId,
+
+            /// Whether to overwrite an existing thing.
+            #[ruma_api(query)]
+            #[serde(default, rename = "overwrite_existing")]
+            pub overwrite: bool,
+
+            /// The thing.
+            pub thing: BTreeMap<String, u64>,
+
+            /// A comment about the thing.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub comment: Option<String>,
+        }
+
+        #[ruma_api(status = CREATED)]
+        response: {
+            /// The content type of the response.
+            #[ruma_api(header = CONTENT_TYPE)]
+            pub content_type: String,
+        }
+    }
+}
+
+#[test]
+fn endpoint_schema() {
+    let schema = set_thing::SCHEMA;
+    assert_eq!(schema.metadata.name, "set_thing");
+    assert_eq!(schema.status, http::StatusCode::CREATED);
+
+    let fields: Vec<_> =
+        schema.request.iter().map(|f| (f.name, f.location, f.ty, f.required)).collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("room_id", FieldLocation::Path, "RoomId", true),
+            ("overwrite_existing", FieldLocation::Query, "bool", false),
+            ("thing", FieldLocation::Body, "BTreeMap<String, u64>", true),
+            ("comment", FieldLocation::Body, "Option<String>", false),
+        ]
+    );
+    assert_eq!(schema.request[0].description, "The room to set the thing in.");
+
+    assert_eq!(schema.response[0].name, "content-type");
+    assert_eq!(schema.response[0].location, FieldLocation::Header);
+}
+
+#[test]
+fn openapi() {
+    let document = openapi_document("Test API", "1.0", &[set_thing::SCHEMA]);
+
+    let operation = &document["paths"]["/_matrix/client/v3/rooms/{room_id}/things"]["put"];
+    assert_eq!(document["paths"]["/_matrix/client/r0/rooms/{room_id}/things"]["put"], *operation);
+
+    assert_eq!(operation["operationId"], "set_thing");
+    assert_eq!(
+        operation["parameters"],
+        json!([
+            {
+                "name": "room_id",
+                "in": "path",
+                "required": true,
+                "description": "The room to set the thing in.",
+                "schema": { "type": "string", "x-rust-type": "RoomId" },
+            },
+            {
+                "name": "overwrite_existing",
+                "in": "query",
+                "required": false,
+                "description": "Whether to overwrite an existing thing.",
+                "schema": { "type": "boolean", "x-rust-type": "bool" },
+            },
+        ])
+    );
+    assert_eq!(
+        operation["requestBody"]["content"]["application/json"]["schema"]["required"],
+        json!(["thing"])
+    );
+    assert_eq!(operation["security"], json!([{ "accessToken": [] }, { "accessTokenQuery": [] }]));
+    assert!(operation["responses"]["429"].is_object());
+    assert!(operation["responses"]["201"]["headers"]["content-type"].is_object());
+    assert!(operation["responses"]["200"].is_null());
+}
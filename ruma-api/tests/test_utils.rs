@@ -0,0 +1,120 @@
+#![cfg(feature = "test-utils")]
+
+use ruma_api::test_utils::{check_request, round_trip_request, round_trip_response, Fixture};
+use serde_json::json;
+
+mod owned {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: PUT,
+            name: "owned",
+            path: "/_matrix/foo/:bar",
+            rate_limited: false,
+            authentication: AccessToken,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub bar: String,
+            #[ruma_api(query)]
+            pub limit: u32,
+            pub hello: String,
+        }
+
+        response: {
+            #[ruma_api(header = LOCATION)]
+            pub location: String,
+            pub world: String,
+        }
+    }
+}
+
+mod borrowed {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something with borrowed data.",
+            method: GET,
+            name: "borrowed",
+            path: "/_matrix/foo/:bar",
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub bar: &'a str,
+        }
+
+        response: {}
+    }
+}
+
+fn owned_fixture() -> Fixture {
+    Fixture::from_json(
+        &json!({
+            "request": {
+                "method": "PUT",
+                "uri": "/_matrix/foo/b%20ar?limit=10",
+                "body": { "hello": "hi" },
+            },
+            "response": {
+                "headers": { "location": "/somewhere" },
+                "body": { "world": "earth" },
+            },
+        })
+        .to_string(),
+    )
+}
+
+#[test]
+fn owned_round_trip() {
+    let fixture = owned_fixture();
+
+    let request: owned::Request = round_trip_request(&fixture.request);
+    assert_eq!(request.bar, "b ar");
+    assert_eq!(request.limit, 10);
+
+    let response: owned::Response = round_trip_response(&fixture.response);
+    assert_eq!(response.location, "/somewhere");
+    assert_eq!(response.world, "earth");
+}
+
+#[test]
+fn borrowed_request() {
+    let fixture = Fixture::from_json(
+        &json!({
+            "request": { "method": "GET", "uri": "/_matrix/foo/baz" },
+            "response": {},
+        })
+        .to_string(),
+    );
+
+    let request: borrowed::IncomingRequest =
+        check_request(borrowed::Request { bar: "baz" }, &fixture.request);
+    assert_eq!(request.bar, "baz");
+
+    let _: borrowed::Response = round_trip_response(&fixture.response);
+}
+
+#[test]
+#[should_panic(expected = "query string mismatch")]
+fn query_mismatch() {
+    let fixture = owned_fixture();
+    let request = owned::Request { bar: "b ar".to_owned(), limit: 11, hello: "hi".to_owned() };
+
+    let _: owned::Request = check_request(request, &fixture.request);
+}
+
+#[test]
+#[should_panic(expected = "body mismatch")]
+fn body_mismatch() {
+    let fixture = owned_fixture();
+    let request = owned::Request { bar: "b ar".to_owned(), limit: 10, hello: "hey".to_owned() };
+
+    let _: owned::Request = check_request(request, &fixture.request);
+}
@@ -0,0 +1,142 @@
+use ruma_api::{
+    error::IntoHttpError, ruma_api, IncomingRequest as _, MatrixVersion, OutgoingRequest as _,
+};
+
+ruma_api! {
+    metadata: {
+        description: "Does something.",
+        method: GET,
+        name: "versioned_endpoint",
+        unstable_paths: [
+            "/_matrix/client/unstable/org.matrix.msc1234/:room/things/:thing",
+            "/_matrix/client/unstable/org.matrix.msc1234.v2/:room/things/:thing",
+        ],
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/:room/things/:thing",
+            1.1 => "/_matrix/client/v3/:room/things/:thing",
+        ],
+        removed: 1.2,
+        rate_limited: false,
+        authentication: None,
+    }
+
+    request: {
+        #[ruma_api(path)]
+        pub room: String,
+        #[ruma_api(path)]
+        pub thing: String,
+    }
+
+    response: {}
+}
+
+fn request() -> Request {
+    Request { room: "some room".to_owned(), thing: "thing".to_owned() }
+}
+
+fn request_path(versions: &[MatrixVersion]) -> Result<String, IntoHttpError> {
+    let http_req =
+        request().try_into_http_request_with_versions("https://homeserver.tld", None, versions)?;
+    Ok(http_req.uri().path().to_owned())
+}
+
+#[test]
+fn default_path_is_r0_path() {
+    assert_eq!(METADATA.path, "/_matrix/client/r0/:room/things/:thing");
+
+    let http_req = request().try_into_http_request("https://homeserver.tld", None).unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/r0/some%20room/things/thing");
+}
+
+#[test]
+fn select_path_by_versions() {
+    assert_eq!(request_path(&[]).unwrap(), "/_matrix/client/r0/some%20room/things/thing");
+    assert_eq!(
+        request_path(&[MatrixVersion::V1_0]).unwrap(),
+        "/_matrix/client/r0/some%20room/things/thing"
+    );
+    assert_eq!(
+        request_path(&[MatrixVersion::V1_0, MatrixVersion::V1_1]).unwrap(),
+        "/_matrix/client/v3/some%20room/things/thing"
+    );
+    assert_eq!(
+        request_path(&[MatrixVersion::V1_1, MatrixVersion::V1_2]).unwrap(),
+        "/_matrix/client/v3/some%20room/things/thing"
+    );
+}
+
+#[test]
+fn removed_endpoint() {
+    match request_path(&[MatrixVersion::V1_2]) {
+        Err(IntoHttpError::EndpointRemoved(MatrixVersion::V1_2)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn incoming_request_on_any_path() {
+    for path in &[
+        "/_matrix/client/unstable/org.matrix.msc1234/some%20room/things/thing",
+        "/_matrix/client/unstable/org.matrix.msc1234.v2/some%20room/things/thing",
+        "/_matrix/client/r0/some%20room/things/thing",
+        "/_matrix/client/v3/some%20room/things/thing",
+    ] {
+        let http_req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri(format!("https://homeserver.tld{}", path))
+            .body(Vec::new())
+            .unwrap();
+        let req = Request::try_from_http_request(http_req).unwrap();
+
+        assert_eq!(req.room, "some room");
+        assert_eq!(req.thing, "thing");
+    }
+}
+
+#[test]
+fn metadata_paths() {
+    assert_eq!(
+        METADATA.paths().collect::<Vec<_>>(),
+        vec![
+            "/_matrix/client/r0/:room/things/:thing",
+            "/_matrix/client/unstable/org.matrix.msc1234/:room/things/:thing",
+            "/_matrix/client/unstable/org.matrix.msc1234.v2/:room/things/:thing",
+            "/_matrix/client/v3/:room/things/:thing",
+        ]
+    );
+}
+
+mod unstable_endpoint {
+    ruma_api::ruma_api! {
+        metadata: {
+            description: "Does something that is not in the specification yet.",
+            method: GET,
+            name: "unstable_endpoint",
+            unstable_paths: [
+                "/_matrix/client/unstable/org.matrix.msc1234/things",
+                "/_matrix/client/unstable/org.matrix.msc1234.v2/things",
+            ],
+            rate_limited: false,
+            authentication: None,
+        }
+
+        request: {}
+
+        response: {}
+    }
+}
+
+#[test]
+fn select_unstable_path() {
+    use ruma_api::OutgoingRequest as _;
+
+    let request = || unstable_endpoint::Request {};
+
+    let http_req = request().try_into_http_request("https://homeserver.tld", None).unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/unstable/org.matrix.msc1234/things");
+
+    let http_req = request()
+        .try_into_http_request_with_versions("https://homeserver.tld", None, &[MatrixVersion::V1_1])
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/unstable/org.matrix.msc1234.v2/things");
+}
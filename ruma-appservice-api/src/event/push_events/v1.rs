@@ -27,6 +27,8 @@ ruma_api! {
 
     #[derive(Default)]
     response: {}
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -13,6 +13,27 @@ pub mod event;
 pub mod query;
 pub mod thirdparty;
 
+/// A Matrix error returned by the application service.
+pub use ruma_api::error::MatrixError as Error;
+
+/// The metadata of all endpoints of the application service API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry`
+/// feature.
+#[cfg(feature = "registry")]
+pub fn endpoints() -> impl Iterator<Item = &'static ruma_api::Metadata> {
+    ruma_api::registry::endpoints_of(module_path!())
+}
+
+/// The schemas of all endpoints of the application service API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry` and
+/// `schema` features.
+#[cfg(all(feature = "registry", feature = "schema"))]
+pub fn endpoint_schemas() -> impl Iterator<Item = &'static ruma_api::schema::EndpointSchema> {
+    ruma_api::registry::schemas_of(module_path!())
+}
+
 /// A namespace defined by an application service.
 ///
 /// Used for [appservice registration](https://matrix.org/docs/spec/application_service/r0.1.2#registration).
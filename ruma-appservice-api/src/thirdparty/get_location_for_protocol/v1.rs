@@ -31,6 +31,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub locations: Vec<Location>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -24,6 +24,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub protocol: Protocol,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -25,6 +25,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub users: Vec<User>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
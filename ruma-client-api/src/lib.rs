@@ -12,3 +12,21 @@ pub mod r0;
 pub mod unversioned;
 
 pub use error::Error;
+
+/// The metadata of all endpoints of the client-server API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry`
+/// feature.
+#[cfg(feature = "registry")]
+pub fn endpoints() -> impl Iterator<Item = &'static ruma_api::Metadata> {
+    ruma_api::registry::endpoints_of(module_path!())
+}
+
+/// The schemas of all endpoints of the client-server API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry` and
+/// `schema` features.
+#[cfg(all(feature = "registry", feature = "schema"))]
+pub fn endpoint_schemas() -> impl Iterator<Item = &'static ruma_api::schema::EndpointSchema> {
+    ruma_api::registry::schemas_of(module_path!())
+}
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Add contact information to a user's account",
         method: POST,
         name: "add_3pid",
-        path: "/_matrix/client/r0/account/3pid/add",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/add",
+            1.1 => "/_matrix/client/v3/account/3pid/add",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Bind a 3PID to a user's account on an identity server",
         method: POST,
         name: "bind_3pid",
-        path: "/_matrix/client/r0/account/3pid/bind",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/bind",
+            1.1 => "/_matrix/client/v3/account/3pid/bind",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
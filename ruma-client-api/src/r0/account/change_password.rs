@@ -9,7 +9,10 @@ ruma_api! {
         description: "Change the password of the current user's account.",
         method: POST,
         name: "change_password",
-        path: "/_matrix/client/r0/account/password",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/password",
+            1.1 => "/_matrix/client/v3/account/password",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Deactivate the current user's account.",
         method: POST,
         name: "deactivate",
-        path: "/_matrix/client/r0/account/deactivate",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/deactivate",
+            1.1 => "/_matrix/client/v3/account/deactivate",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
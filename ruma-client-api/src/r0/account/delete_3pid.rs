@@ -10,7 +10,10 @@ ruma_api! {
         description: "Delete a 3PID from a user's account on an identity server.",
         method: POST,
         name: "delete_3pid",
-        path: "/_matrix/client/r0/account/3pid/delete",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/delete",
+            1.1 => "/_matrix/client/v3/account/3pid/delete",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -7,7 +7,10 @@ ruma_api! {
         description: "Checks to see if a username is available, and valid, for the server.",
         method: GET,
         name: "get_username_availability",
-        path: "/_matrix/client/r0/register/available",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/register/available",
+            1.1 => "/_matrix/client/v3/register/available",
+        ],
         rate_limited: true,
         authentication: None,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Register an account on this homeserver.",
         method: POST,
         name: "register",
-        path: "/_matrix/client/r0/register",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/register",
+            1.1 => "/_matrix/client/v3/register",
+        ],
         rate_limited: true,
         authentication: None,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Request a 3PID management token with a 3rd party email.",
         method: POST,
         name: "request_3pid_association_token_via_email",
-        path: "/_matrix/client/r0/account/3pid/email/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/email/requestToken",
+            1.1 => "/_matrix/client/v3/account/3pid/email/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
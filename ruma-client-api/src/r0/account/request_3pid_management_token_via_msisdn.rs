@@ -10,7 +10,10 @@ ruma_api! {
         description: "Request a 3PID management token with a phone number.",
         method: POST,
         name: "request_3pid_association_token_via_msisdn",
-        path: "/_matrix/client/r0/account/3pid/msisdn/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/msisdn/requestToken",
+            1.1 => "/_matrix/client/v3/account/3pid/msisdn/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Request an OpenID 1.0 token to verify identity with a third party.",
         name: "request_openid_token",
         method: POST,
-        path: "/_matrix/client/r0/user/:user_id/openid/request_token",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/openid/request_token",
+            1.1 => "/_matrix/client/v3/user/:user_id/openid/request_token",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
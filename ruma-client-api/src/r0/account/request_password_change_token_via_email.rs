@@ -10,7 +10,10 @@ ruma_api! {
         description: "Request that a password change token is sent to the given email address.",
         method: POST,
         name: "request_password_change_token_via_email",
-        path: "/_matrix/client/r0/account/password/email/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/password/email/requestToken",
+            1.1 => "/_matrix/client/v3/account/password/email/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Request that a password change token is sent to the given phone number.",
         method: POST,
         name: "request_password_change_token_via_msisdn",
-        path: "/_matrix/client/r0/account/password/msisdn/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/password/msisdn/requestToken",
+            1.1 => "/_matrix/client/v3/account/password/msisdn/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Request a registration token with a phone number.",
         method: POST,
         name: "request_registration_token_via_msisdn",
-        path: "/_matrix/client/r0/register/msisdn/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/register/msisdn/requestToken",
+            1.1 => "/_matrix/client/v3/register/msisdn/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Unbind a 3PID from a user's account on an identity server.",
         method: POST,
         name: "unbind_3pid",
-        path: "/_matrix/client/r0/account/3pid/unbind",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/unbind",
+            1.1 => "/_matrix/client/v3/account/3pid/unbind",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get information about the owner of a given access token.",
         method: GET,
         name: "whoami",
-        path: "/_matrix/client/r0/account/whoami",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/whoami",
+            1.1 => "/_matrix/client/v3/account/whoami",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Add an alias to a room.",
         method: PUT,
         name: "create_alias",
-        path: "/_matrix/client/r0/directory/room/:room_alias",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/directory/room/:room_alias",
+            1.1 => "/_matrix/client/v3/directory/room/:room_alias",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -9,8 +9,11 @@ ruma_api! {
     metadata: {
         description: "Updates the visibility of a given room on the application service's room directory.",
         method: PUT,
-        name: "set_room_visibility",
-        path: "/_matrix/client/r0/directory/list/appservice/:network_id/:room_id",
+        name: "appservice_set_room_visibility",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/directory/list/appservice/:network_id/:room_id",
+            1.1 => "/_matrix/client/v3/directory/list/appservice/:network_id/:room_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -19,16 +19,28 @@ pub mod update_backup;
 use std::collections::BTreeMap;
 
 use js_int::UInt;
+#[cfg(feature = "signatures")]
+use ruma_identifiers::DeviceKeyAlgorithm;
 use ruma_identifiers::{DeviceKeyId, UserId};
+#[cfg(feature = "signatures")]
+use ruma_serde::Raw;
 use serde::{Deserialize, Serialize};
 
 /// A wrapper around a mapping of session IDs to key data.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct RoomKeyBackup {
     /// A map of session IDs to key data.
     pub sessions: BTreeMap<String, KeyBackupData>,
 }
 
+impl RoomKeyBackup {
+    /// Creates a new `RoomKeyBackup` with the given sessions.
+    pub fn new(sessions: BTreeMap<String, KeyBackupData>) -> Self {
+        Self { sessions }
+    }
+}
+
 /// The algorithm used for storing backups.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "algorithm", content = "auth_data")]
@@ -44,8 +56,84 @@ pub enum BackupAlgorithm {
     },
 }
 
+#[cfg(feature = "signatures")]
+impl BackupAlgorithm {
+    /// Verifies that the `auth_data` of the given backup algorithm carries a valid signature of
+    /// `user_id` made with one of the given keys.
+    ///
+    /// The signature is checked against the `auth_data` as it was received, including the fields
+    /// that are not part of `BackupAlgorithm`, which is why this takes the raw JSON of the
+    /// algorithm, like the `algorithm` of `get_backup::Response`.
+    ///
+    /// `keys` are the public keys the caller trusts, usually the `keys` of one of the user's
+    /// verified devices or of the user's master cross-signing key. Only `ed25519` keys are
+    /// considered, other keys in the map are ignored.
+    ///
+    /// Returns an error if none of the given keys signed the `auth_data` or if the signature is
+    /// invalid, in which case the backup must not be trusted.
+    pub fn verify_auth_data(
+        algorithm: &Raw<BackupAlgorithm>,
+        user_id: &UserId,
+        keys: &BTreeMap<DeviceKeyId, String>,
+    ) -> Result<(), ruma_signatures::Error> {
+        #[derive(Deserialize)]
+        struct AuthData {
+            auth_data: ruma_signatures::CanonicalJsonObject,
+        }
+
+        let AuthData { auth_data } = serde_json::from_str(algorithm.json().get())?;
+
+        let public_key_set = keys
+            .iter()
+            .filter(|(key_id, _)| key_id.algorithm() == DeviceKeyAlgorithm::Ed25519)
+            .map(|(key_id, key)| (key_id.to_string(), key.clone()))
+            .collect();
+
+        let mut public_key_map = BTreeMap::new();
+        public_key_map.insert(user_id.to_string(), public_key_set);
+
+        ruma_signatures::verify_json(&public_key_map, &auth_data)
+    }
+}
+
+/// (De)serialization of a `Raw<BackupAlgorithm>` flattened in a response.
+///
+/// `Raw` can't be flattened directly, so the `algorithm` and `auth_data` fields are collected as
+/// JSON values, keeping the fields of `auth_data` that are not part of `BackupAlgorithm`.
+mod raw_algorithm_serde {
+    use ruma_serde::Raw;
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value as JsonValue;
+
+    use super::BackupAlgorithm;
+
+    #[derive(Deserialize, Serialize)]
+    struct AlgorithmWithData {
+        algorithm: JsonValue,
+        auth_data: JsonValue,
+    }
+
+    pub fn serialize<S>(algorithm: &Raw<BackupAlgorithm>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let algorithm: AlgorithmWithData =
+            serde_json::from_str(algorithm.json().get()).map_err(ser::Error::custom)?;
+        algorithm.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Raw<BackupAlgorithm>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let algorithm = AlgorithmWithData::deserialize(deserializer)?;
+        serde_json::value::to_raw_value(&algorithm).map(Raw::from_json).map_err(de::Error::custom)
+    }
+}
+
 /// Information about the backup key.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct KeyBackupData {
     /// The index of the first message in the session that the key can decrypt.
     pub first_message_index: UInt,
@@ -60,8 +148,23 @@ pub struct KeyBackupData {
     pub session_data: SessionData,
 }
 
-/// The algorithm used for storing backups.
+impl KeyBackupData {
+    /// Creates a new `KeyBackupData` with the given first message index, forwarded count,
+    /// verification status and session data.
+    pub fn new(
+        first_message_index: UInt,
+        forwarded_count: UInt,
+        is_verified: bool,
+        session_data: SessionData,
+    ) -> Self {
+        Self { first_message_index, forwarded_count, is_verified, session_data }
+    }
+}
+
+/// The encrypted session data of a backed up key, for the
+/// `m.megolm_backup.v1.curve25519-aes-sha2` algorithm.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct SessionData {
     /// Unpadded base64-encoded public half of the ephemeral key.
     pub ephemeral: String,
@@ -72,3 +175,138 @@ pub struct SessionData {
     /// First 8 bytes of MAC key, encoded in base64.
     pub mac: String,
 }
+
+impl SessionData {
+    /// Creates a new `SessionData` with the given ephemeral key, ciphertext and MAC.
+    pub fn new(ephemeral: String, ciphertext: String, mac: String) -> Self {
+        Self { ephemeral, ciphertext, mac }
+    }
+}
+
+#[cfg(all(test, feature = "signatures"))]
+mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    use ruma_identifiers::{user_id, DeviceKeyId};
+    use ruma_serde::Raw;
+    use ruma_signatures::{sign_json, CanonicalJsonValue, Ed25519KeyPair};
+    use serde_json::{json, value::to_raw_value as to_raw_json_value, Value as JsonValue};
+
+    use super::BackupAlgorithm;
+
+    fn key_pair(version: &str) -> Ed25519KeyPair {
+        Ed25519KeyPair::new(&Ed25519KeyPair::generate().unwrap(), version.into()).unwrap()
+    }
+
+    fn signed_auth_data(key_pair: &Ed25519KeyPair, auth_data: JsonValue) -> CanonicalJsonValue {
+        let mut auth_data = match ruma_serde::to_canonical_value(auth_data).unwrap() {
+            CanonicalJsonValue::Object(object) => object,
+            _ => unreachable!(),
+        };
+        sign_json("@alice:example.org", key_pair, &mut auth_data).unwrap();
+
+        CanonicalJsonValue::Object(auth_data)
+    }
+
+    fn signed_backup_algorithm(key_pair: &Ed25519KeyPair) -> Raw<BackupAlgorithm> {
+        let auth_data = signed_auth_data(
+            key_pair,
+            json!({ "public_key": "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo" }),
+        );
+
+        Raw::from_json(
+            to_raw_json_value(&json!({
+                "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+                "auth_data": auth_data,
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn public_keys(key_pair: &Ed25519KeyPair) -> BTreeMap<DeviceKeyId, String> {
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            DeviceKeyId::try_from(format!("ed25519:{}", key_pair.version())).unwrap(),
+            base64::encode_config(key_pair.public_key(), base64::STANDARD_NO_PAD),
+        );
+        keys.insert(
+            DeviceKeyId::try_from(format!("curve25519:{}", key_pair.version())).unwrap(),
+            "wjLpTLRqbqBzLs63aYaEv2Boi6cFEbbM/sSRQ2oAKk4".into(),
+        );
+        keys
+    }
+
+    #[test]
+    fn verify_auth_data_signed_by_trusted_key() {
+        let key_pair = key_pair("JLAFKJWSCS");
+        let algorithm = signed_backup_algorithm(&key_pair);
+
+        assert!(BackupAlgorithm::verify_auth_data(
+            &algorithm,
+            &user_id!("@alice:example.org"),
+            &public_keys(&key_pair)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn verify_auth_data_signed_by_other_key() {
+        let algorithm = signed_backup_algorithm(&key_pair("JLAFKJWSCS"));
+        let other_key_pair = key_pair("JLAFKJWSCS");
+
+        assert!(BackupAlgorithm::verify_auth_data(
+            &algorithm,
+            &user_id!("@alice:example.org"),
+            &public_keys(&other_key_pair)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_auth_data_signed_by_other_user() {
+        let key_pair = key_pair("JLAFKJWSCS");
+        let algorithm = signed_backup_algorithm(&key_pair);
+
+        assert!(BackupAlgorithm::verify_auth_data(
+            &algorithm,
+            &user_id!("@bob:example.org"),
+            &public_keys(&key_pair)
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn verify_auth_data_with_unknown_fields() {
+        use super::get_backup::Response;
+
+        let key_pair = key_pair("JLAFKJWSCS");
+        let auth_data = signed_auth_data(
+            &key_pair,
+            json!({
+                "public_key": "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo",
+                "org.example.passphrase": { "iterations": 500_000, "salt": "MmMsAlty" },
+            }),
+        );
+        let body = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": auth_data,
+            "count": 4,
+            "etag": "abcdefg",
+            "version": "1",
+        });
+
+        let response =
+            Response::try_from(http::Response::new(serde_json::to_vec(&body).unwrap())).unwrap();
+        assert!(BackupAlgorithm::verify_auth_data(
+            &response.algorithm,
+            &user_id!("@alice:example.org"),
+            &public_keys(&key_pair)
+        )
+        .is_ok());
+
+        let http_response = http::Response::<Vec<u8>>::try_from(response).unwrap();
+        let serialized: JsonValue = serde_json::from_slice(http_response.body()).unwrap();
+        assert_eq!(serialized, body);
+    }
+}
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Store several keys in the backup.",
         method: PUT,
         name: "add_backup_key_session",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id/:session_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
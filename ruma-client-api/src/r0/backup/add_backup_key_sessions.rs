@@ -13,7 +13,10 @@ ruma_api! {
         description: "Store several sessions in the backup.",
         method: PUT,
         name: "add_backup_key_sessions",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Creates a new backup.",
         method: POST,
         name: "create_backup",
-        path: "/_matrix/client/r0/room_keys/version",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/version",
+            1.1 => "/_matrix/client/v3/room_keys/version",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -7,7 +7,10 @@ ruma_api! {
         description: "Delete an existing backup.",
         method: DELETE,
         name: "delete_backup",
-        path: "/_matrix/client/r0/room_keys/version/:version",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/version/:version",
+            1.1 => "/_matrix/client/v3/room_keys/version/:version",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
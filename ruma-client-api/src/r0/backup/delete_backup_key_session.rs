@@ -7,9 +7,12 @@ use ruma_identifiers::RoomId;
 ruma_api! {
     metadata: {
         description: "Delete a key from the backup",
-        method: GET,
+        method: DELETE,
         name: "delete_backup_key_session",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id/:session_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -7,9 +7,12 @@ use ruma_identifiers::RoomId;
 ruma_api! {
     metadata: {
         description: "Delete keys from the backup for a given room.",
-        method: GET,
+        method: DELETE,
         name: "delete_backup_key_sessions",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
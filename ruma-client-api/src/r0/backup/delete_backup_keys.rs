@@ -6,9 +6,12 @@ use ruma_api::ruma_api;
 ruma_api! {
     metadata: {
         description: "Delete all keys in a backup.",
-        method: PUT,
+        method: DELETE,
         name: "delete_backup_keys",
-        path: "/_matrix/client/r0/room_keys/keys",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys",
+            1.1 => "/_matrix/client/v3/room_keys/keys",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -2,6 +2,7 @@
 
 use js_int::UInt;
 use ruma_api::ruma_api;
+use ruma_serde::Raw;
 
 use super::BackupAlgorithm;
 
@@ -10,7 +11,10 @@ ruma_api! {
         description: "Get information about an existing backup.",
         method: GET,
         name: "get_backup",
-        path: "/_matrix/client/r0/room_keys/version/:version",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/version/:version",
+            1.1 => "/_matrix/client/v3/room_keys/version/:version",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -23,8 +27,11 @@ ruma_api! {
 
     response: {
         /// The algorithm used for storing backups.
-        #[serde(flatten)]
-        pub algorithm: BackupAlgorithm,
+        ///
+        /// Use `BackupAlgorithm::verify_auth_data` to check its signatures, or deserialize it to
+        /// access its fields.
+        #[serde(flatten, with = "super::raw_algorithm_serde")]
+        pub algorithm: Raw<BackupAlgorithm>,
 
         /// The number of keys stored in the backup.
         pub count: UInt,
@@ -49,7 +56,12 @@ impl<'a> Request<'a> {
 
 impl Response {
     /// Creates a new `Response` with the gien algorithm, key count, etag and version.
-    pub fn new(algorithm: BackupAlgorithm, count: UInt, etag: String, version: String) -> Self {
+    pub fn new(
+        algorithm: Raw<BackupAlgorithm>,
+        count: UInt,
+        etag: String,
+        version: String,
+    ) -> Self {
         Self { algorithm, count, etag, version }
     }
 }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Retrieve a key from the backup",
         method: GET,
         name: "get_backup_key_session",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id/:session_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -12,7 +12,10 @@ ruma_api! {
         description: "Retrieve sessions from the backup for a given room.",
         method: GET,
         name: "get_backup_key_sessions",
-        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys/:room_id",
+            1.1 => "/_matrix/client/v3/room_keys/keys/:room_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -12,7 +12,10 @@ ruma_api! {
         description: "Retrieve all keys from a backup.",
         method: GET,
         name: "get_backup_keys",
-        path: "/_matrix/client/r0/room_keys/keys",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/keys",
+            1.1 => "/_matrix/client/v3/room_keys/keys",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
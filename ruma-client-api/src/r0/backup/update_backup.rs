@@ -7,9 +7,12 @@ use super::BackupAlgorithm;
 ruma_api! {
     metadata: {
         description: "Update information about an existing backup.",
-        method: POST,
+        method: PUT,
         name: "update_backup",
-        path: "/_matrix/client/r0/room_keys/version/:version",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/room_keys/version/:version",
+            1.1 => "/_matrix/client/v3/room_keys/version/:version",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Gets information about the server's supported feature set and other relevant capabilities.",
         method: GET,
         name: "get_capabilities",
-        path: "/_matrix/client/r0/capabilities",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/capabilities",
+            1.1 => "/_matrix/client/v3/capabilities",
+        ],
         rate_limited: true,
         authentication: AccessToken
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Gets global account data for a user.",
         name: "get_global_account_data",
         method: GET,
-        path: "/_matrix/client/r0/user/:user_id/account_data/:event_type",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/account_data/:event_type",
+            1.1 => "/_matrix/client/v3/user/:user_id/account_data/:event_type",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Associate account data with a room.",
         method: PUT,
         name: "set_room_account_data",
-        path: "/_matrix/client/r0/user/:user_id/rooms/:room_id/account_data/:event_type",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/rooms/:room_id/account_data/:event_type",
+            1.1 => "/_matrix/client/v3/user/:user_id/rooms/:room_id/account_data/:event_type",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
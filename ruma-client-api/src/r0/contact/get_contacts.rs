@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get a list of 3rd party contacts associated with the user's account.",
         method: GET,
         name: "get_contacts",
-        path: "/_matrix/client/r0/account/3pid",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid",
+            1.1 => "/_matrix/client/v3/account/3pid",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Ask for a verification token for a given 3rd party ID.",
         method: POST,
         name: "request_contact_verification_token",
-        path: "/_matrix/client/r0/account/3pid/email/requestToken",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/account/3pid/email/requestToken",
+            1.1 => "/_matrix/client/v3/account/3pid/email/requestToken",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -41,8 +44,21 @@ ruma_api! {
         pub id_access_token: Option<&'a str>,
     }
 
-    #[derive(Default)]
-    response: {}
+    response: {
+        /// The session identifier given by the identity server.
+        pub sid: String,
+
+        /// URL to submit validation token to. If omitted, verification happens without client.
+        ///
+        /// If you activate the `compat` feature, this field being an empty string in JSON will give
+        /// you `None` here.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "compat",
+            serde(default, deserialize_with = "ruma_serde::empty_string_as_none")
+        )]
+        pub submit_url: Option<String>
+    }
 
     error: crate::Error
 }
@@ -62,8 +78,8 @@ impl<'a> Request<'a> {
 }
 
 impl Response {
-    /// Creates an empty `Response`.
-    pub fn new() -> Self {
-        Self
+    /// Creates a new `Response` with the given session identifier.
+    pub fn new(sid: String) -> Self {
+        Self { sid, submit_url: None }
     }
 }
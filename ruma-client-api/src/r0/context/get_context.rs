@@ -12,7 +12,10 @@ ruma_api! {
     metadata: {
         description: "Get the events immediately preceding and following a given event.",
         method: GET,
-        path: "/_matrix/client/r0/rooms/:room_id/context/:event_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/context/:event_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/context/:event_id",
+        ],
         name: "get_context",
         rate_limited: false,
         authentication: AccessToken,
@@ -5,10 +5,16 @@ use std::time::SystemTime;
 use ruma_identifiers::DeviceIdBox;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "unstable-pre-spec")]
+pub mod claim_dehydrated_device;
 pub mod delete_device;
 pub mod delete_devices;
+#[cfg(feature = "unstable-pre-spec")]
+pub mod get_dehydrated_device;
 pub mod get_device;
 pub mod get_devices;
+#[cfg(feature = "unstable-pre-spec")]
+pub mod put_dehydrated_device;
 pub mod update_device;
 
 /// Information about a registered device.
@@ -39,3 +45,42 @@ impl Device {
         Self { device_id, display_name: None, last_seen_ip: None, last_seen_ts: None }
     }
 }
+
+/// The data of a dehydrated device, encrypted by the client.
+///
+/// Defined in [MSC2697](https://github.com/matrix-org/matrix-doc/pull/2697).
+#[cfg(feature = "unstable-pre-spec")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(tag = "algorithm")]
+pub enum DehydratedDeviceData {
+    /// The `org.matrix.msc2697.v1.olm.libolm_pickle` algorithm.
+    #[serde(rename = "org.matrix.msc2697.v1.olm.libolm_pickle")]
+    V1(DehydratedDeviceV1),
+}
+
+/// The data of a dehydrated device using the `org.matrix.msc2697.v1.olm.libolm_pickle`
+/// algorithm.
+#[cfg(feature = "unstable-pre-spec")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DehydratedDeviceV1 {
+    /// The Olm account of the device, pickled with libolm and encrypted with a key only known to
+    /// the user's clients.
+    pub account: String,
+}
+
+#[cfg(feature = "unstable-pre-spec")]
+impl DehydratedDeviceV1 {
+    /// Creates a new `DehydratedDeviceV1` with the given pickled account.
+    pub fn new(account: String) -> Self {
+        Self { account }
+    }
+}
+
+#[cfg(feature = "unstable-pre-spec")]
+impl From<DehydratedDeviceV1> for DehydratedDeviceData {
+    fn from(data: DehydratedDeviceV1) -> Self {
+        Self::V1(data)
+    }
+}
@@ -0,0 +1,45 @@
+//! POST /_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device/claim
+//!
+//! Defined in [MSC2697](https://github.com/matrix-org/matrix-doc/pull/2697)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::DeviceId;
+
+ruma_api! {
+    metadata: {
+        description: "Claims the dehydrated device of the user for the current session.",
+        method: POST,
+        name: "claim_dehydrated_device",
+        unstable_paths: ["/_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device/claim"],
+        rate_limited: false,
+        authentication: AccessToken,
+    }
+
+    request: {
+        /// The ID of the dehydrated device to claim.
+        pub device_id: &'a DeviceId,
+    }
+
+    response: {
+        /// Whether the device was successfully claimed.
+        ///
+        /// This is `false` if another client claimed the device first.
+        pub success: bool,
+    }
+
+    error: crate::Error
+}
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given device ID.
+    pub fn new(device_id: &'a DeviceId) -> Self {
+        Self { device_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given claim result.
+    pub fn new(success: bool) -> Self {
+        Self { success }
+    }
+}
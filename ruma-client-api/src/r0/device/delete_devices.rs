@@ -9,7 +9,10 @@ ruma_api! {
     metadata: {
         description: "Delete specified devices.",
         method: POST,
-        path: "/_matrix/client/r0/delete_devices",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/delete_devices",
+            1.1 => "/_matrix/client/v3/delete_devices",
+        ],
         name: "delete_devices",
         rate_limited: false,
         authentication: AccessToken,
@@ -0,0 +1,46 @@
+//! GET /_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device
+//!
+//! Defined in [MSC2697](https://github.com/matrix-org/matrix-doc/pull/2697)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::DeviceIdBox;
+
+use super::DehydratedDeviceData;
+
+ruma_api! {
+    metadata: {
+        description: "Gets the dehydrated device of the user, if there is one.",
+        method: GET,
+        name: "get_dehydrated_device",
+        unstable_paths: ["/_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device"],
+        rate_limited: false,
+        authentication: AccessToken,
+    }
+
+    #[derive(Default)]
+    request: {}
+
+    response: {
+        /// The ID of the dehydrated device.
+        pub device_id: DeviceIdBox,
+
+        /// The encrypted data of the device.
+        pub device_data: DehydratedDeviceData,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given device ID and device data.
+    pub fn new(device_id: DeviceIdBox, device_data: DehydratedDeviceData) -> Self {
+        Self { device_id, device_data }
+    }
+}
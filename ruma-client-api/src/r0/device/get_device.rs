@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get a device for authenticated user.",
         method: GET,
         name: "get_device",
-        path: "/_matrix/client/r0/devices/:device_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/devices/:device_id",
+            1.1 => "/_matrix/client/v3/devices/:device_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Get registered devices for authenticated user.",
         method: GET,
         name: "get_devices",
-        path: "/_matrix/client/r0/devices",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/devices",
+            1.1 => "/_matrix/client/v3/devices",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -0,0 +1,49 @@
+//! PUT /_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device
+//!
+//! Defined in [MSC2697](https://github.com/matrix-org/matrix-doc/pull/2697)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::DeviceIdBox;
+
+use super::DehydratedDeviceData;
+
+ruma_api! {
+    metadata: {
+        description: "Uploads a dehydrated device to the homeserver, replacing any previous one.",
+        method: PUT,
+        name: "put_dehydrated_device",
+        unstable_paths: ["/_matrix/client/unstable/org.matrix.msc2697.v2/dehydrated_device"],
+        rate_limited: false,
+        authentication: AccessToken,
+    }
+
+    request: {
+        /// The encrypted data of the device.
+        pub device_data: DehydratedDeviceData,
+
+        /// The display name of the device to create when it is claimed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub initial_device_display_name: Option<&'a str>,
+    }
+
+    response: {
+        /// The ID of the dehydrated device.
+        pub device_id: DeviceIdBox,
+    }
+
+    error: crate::Error
+}
+
+impl Request<'_> {
+    /// Creates a new `Request` with the given device data.
+    pub fn new(device_data: DehydratedDeviceData) -> Self {
+        Self { device_data, initial_device_display_name: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given device ID.
+    pub fn new(device_id: DeviceIdBox) -> Self {
+        Self { device_id }
+    }
+}
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Update metadata for a device.",
         method: PUT,
         name: "update_device",
-        path: "/_matrix/client/r0/devices/:device_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/devices/:device_id",
+            1.1 => "/_matrix/client/v3/devices/:device_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get the list of rooms in this homeserver's public directory.",
         method: GET,
         name: "get_public_rooms",
-        path: "/_matrix/client/r0/publicRooms",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/publicRooms",
+            1.1 => "/_matrix/client/v3/publicRooms",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -12,7 +12,10 @@ ruma_api! {
         description: "Get the list of rooms in this homeserver's public directory.",
         method: POST,
         name: "get_public_rooms_filtered",
-        path: "/_matrix/client/r0/publicRooms",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/publicRooms",
+            1.1 => "/_matrix/client/v3/publicRooms",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
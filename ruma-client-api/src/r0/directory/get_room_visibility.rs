@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get the visibility of a public room on a directory.",
         name: "get_room_visibility",
         method: GET,
-        path: "/_matrix/client/r0/directory/list/room/:room_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/directory/list/room/:room_id",
+            1.1 => "/_matrix/client/v3/directory/list/room/:room_id",
+        ],
         rate_limited: false,
         authentication: None,
     }
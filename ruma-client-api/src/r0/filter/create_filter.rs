@@ -10,7 +10,10 @@ ruma_api! {
         description: "Create a new filter for event retrieval.",
         method: POST,
         name: "create_filter",
-        path: "/_matrix/client/r0/user/:user_id/filter",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/filter",
+            1.1 => "/_matrix/client/v3/user/:user_id/filter",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Retrieve a previously created filter.",
         method: GET,
         name: "get_filter",
-        path: "/_matrix/client/r0/user/:user_id/filter/:filter_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/filter/:filter_id",
+            1.1 => "/_matrix/client/v3/user/:user_id/filter/:filter_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -3,17 +3,20 @@
 use std::{collections::BTreeMap, time::Duration};
 
 use ruma_api::ruma_api;
+use ruma_common::encryption::OneTimeKey;
 use ruma_identifiers::{DeviceIdBox, DeviceKeyAlgorithm, DeviceKeyId, UserId};
+use ruma_serde::Raw;
 use serde_json::Value as JsonValue;
 
-use super::OneTimeKey;
-
 ruma_api! {
     metadata: {
         description: "Claims one-time keys for use in pre-key messages.",
         method: POST,
         name: "claim_keys",
-        path: "/_matrix/client/r0/keys/claim",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/keys/claim",
+            1.1 => "/_matrix/client/v3/keys/claim",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -38,6 +41,10 @@ ruma_api! {
         pub failures: BTreeMap<String, JsonValue>,
 
         /// One-time keys for the queried devices.
+        ///
+        /// If a device has run out of one-time keys, this contains its fallback key instead, if
+        /// it has uploaded one. The keys are kept as they were received, so their signatures can
+        /// be verified.
         pub one_time_keys: BTreeMap<UserId, OneTimeKeys>,
     }
 
@@ -59,4 +66,4 @@ impl Response {
 }
 
 /// The one-time keys for a given device.
-pub type OneTimeKeys = BTreeMap<DeviceIdBox, BTreeMap<DeviceKeyId, OneTimeKey>>;
+pub type OneTimeKeys = BTreeMap<DeviceIdBox, BTreeMap<DeviceKeyId, Raw<OneTimeKey>>>;
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Gets a list of users who have updated their device identity keys since a previous sync token.",
         method: GET,
         name: "get_key_changes",
-        path: "/_matrix/client/r0/keys/changes",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/keys/changes",
+            1.1 => "/_matrix/client/v3/keys/changes",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
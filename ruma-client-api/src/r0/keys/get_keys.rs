@@ -3,19 +3,22 @@
 use std::{collections::BTreeMap, time::Duration};
 
 use ruma_api::ruma_api;
+#[cfg(feature = "unstable-pre-spec")]
+use ruma_common::encryption::CrossSigningKey;
 use ruma_common::encryption::DeviceKeys;
 use ruma_identifiers::{DeviceIdBox, UserId};
+use ruma_serde::Raw;
 use serde_json::Value as JsonValue;
 
-#[cfg(feature = "unstable-pre-spec")]
-use super::CrossSigningKey;
-
 ruma_api! {
     metadata: {
         description: "Returns the current devices and identity keys for the given users.",
         method: POST,
         name: "get_keys",
-        path: "/_matrix/client/r0/keys/query",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/keys/query",
+            1.1 => "/_matrix/client/v3/keys/query",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -53,23 +56,25 @@ ruma_api! {
         pub failures: BTreeMap<String, JsonValue>,
 
         /// Information on the queried devices.
+        ///
+        /// The device keys are kept as they were received, so their signatures can be verified.
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-        pub device_keys: BTreeMap<UserId, BTreeMap<DeviceIdBox, DeviceKeys>>,
+        pub device_keys: BTreeMap<UserId, BTreeMap<DeviceIdBox, Raw<DeviceKeys>>>,
 
         /// Information on the master cross-signing keys of the queried users.
         #[cfg(feature = "unstable-pre-spec")]
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-        pub master_keys: BTreeMap<UserId, CrossSigningKey>,
+        pub master_keys: BTreeMap<UserId, Raw<CrossSigningKey>>,
 
         /// Information on the self-signing keys of the queried users.
         #[cfg(feature = "unstable-pre-spec")]
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-        pub self_signing_keys: BTreeMap<UserId, CrossSigningKey>,
+        pub self_signing_keys: BTreeMap<UserId, Raw<CrossSigningKey>>,
 
         /// Information on the user-signing keys of the queried users.
         #[cfg(feature = "unstable-pre-spec")]
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
-        pub user_signing_keys: BTreeMap<UserId, CrossSigningKey>,
+        pub user_signing_keys: BTreeMap<UserId, Raw<CrossSigningKey>>,
     }
 
     error: crate::Error
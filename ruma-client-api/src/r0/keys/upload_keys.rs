@@ -4,17 +4,18 @@ use std::collections::BTreeMap;
 
 use js_int::UInt;
 use ruma_api::ruma_api;
-use ruma_common::encryption::DeviceKeys;
+use ruma_common::encryption::{DeviceKeys, OneTimeKey};
 use ruma_identifiers::{DeviceKeyAlgorithm, DeviceKeyId};
 
-use super::OneTimeKey;
-
 ruma_api! {
     metadata: {
         description: "Publishes end-to-end encryption keys for the device.",
         method: POST,
         name: "upload_keys",
-        path: "/_matrix/client/r0/keys/upload",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/keys/upload",
+            1.1 => "/_matrix/client/v3/keys/upload",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -28,6 +29,21 @@ ruma_api! {
         /// One-time public keys for "pre-key" messages.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub one_time_keys: Option<BTreeMap<DeviceKeyId, OneTimeKey>>,
+
+        /// Fallback public keys, used for "pre-key" messages once all one-time keys have been
+        /// claimed.
+        ///
+        /// Uploading a new fallback key for an algorithm replaces the previous one. The keys must
+        /// be signed and have `fallback` set to `true`.
+        ///
+        /// Defined in [MSC2732](https://github.com/matrix-org/matrix-doc/pull/2732).
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(
+            rename = "org.matrix.msc2732.fallback_keys",
+            alias = "fallback_keys",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub fallback_keys: Option<BTreeMap<DeviceKeyId, OneTimeKey>>,
     }
 
     response: {
@@ -52,3 +68,64 @@ impl Response {
         Self { one_time_key_counts }
     }
 }
+
+#[cfg(all(test, feature = "unstable-pre-spec"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use maplit::btreemap;
+    use ruma_common::encryption::{OneTimeKey, SignedKey};
+    use ruma_identifiers::{DeviceKeyAlgorithm, DeviceKeyId};
+    use serde_json::{json, Value as JsonValue};
+
+    fn fallback_keys() -> BTreeMap<DeviceKeyId, OneTimeKey> {
+        let key_id = DeviceKeyId::from_parts(DeviceKeyAlgorithm::SignedCurve25519, "AAAAHg".into());
+        let key = SignedKey::new_fallback(
+            "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs".into(),
+            BTreeMap::new(),
+        );
+
+        btreemap! { key_id => OneTimeKey::SignedKey(key) }
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn serialize_fallback_keys() {
+        use ruma_api::OutgoingRequest as _;
+
+        use super::Request;
+
+        let request = Request { fallback_keys: Some(fallback_keys()), ..Request::new() };
+        let http_request: http::Request<Vec<u8>> =
+            request.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        let body: JsonValue = serde_json::from_slice(http_request.body()).unwrap();
+        assert!(body.get("fallback_keys").is_none());
+        assert_eq!(
+            body["org.matrix.msc2732.fallback_keys"]["signed_curve25519:AAAAHg"]["fallback"],
+            json!(true)
+        );
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn deserialize_fallback_keys() {
+        use ruma_api::IncomingRequest as _;
+
+        use super::Request;
+
+        let fallback_keys = serde_json::to_value(fallback_keys()).unwrap();
+        for field in &["org.matrix.msc2732.fallback_keys", "fallback_keys"] {
+            let body = json!({ *field: fallback_keys });
+            let http_request = http::Request::builder()
+                .method("POST")
+                .uri("https://homeserver.tld/_matrix/client/r0/keys/upload")
+                .header("Authorization", "Bearer auth_tok")
+                .body(serde_json::to_vec(&body).unwrap())
+                .unwrap();
+
+            let request = Request::try_from_http_request(http_request).unwrap();
+            assert_eq!(request.fallback_keys.unwrap().len(), 1);
+        }
+    }
+}
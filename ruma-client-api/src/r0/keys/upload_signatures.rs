@@ -13,7 +13,10 @@ ruma_api! {
         description: "Publishes cross-signing signatures for the user.",
         method: POST,
         name: "upload_signatures",
-        path: "/_matrix/client/r0/keys/signatures/upload",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/keys/signatures/upload",
+            1.1 => "/_matrix/client/v3/keys/signatures/upload",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
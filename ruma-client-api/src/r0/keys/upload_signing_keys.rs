@@ -3,8 +3,8 @@
 //! Defined in [MSC 1756](https://github.com/matrix-org/matrix-doc/blob/master/proposals/1756-cross-signing.md#uploading-signing-keys)
 
 use ruma_api::ruma_api;
+use ruma_common::encryption::CrossSigningKey;
 
-use super::CrossSigningKey;
 use crate::r0::uiaa::{AuthData, IncomingAuthData, UiaaResponse};
 
 ruma_api! {
@@ -13,6 +13,7 @@ ruma_api! {
         method: POST,
         name: "upload_signing_keys",
         path: "/_matrix/client/unstable/keys/device_signing/upload",
+        stable_paths: [1.1 => "/_matrix/client/v3/keys/device_signing/upload"],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Upload content to the media store.",
         method: POST,
         name: "create_media_content",
-        path: "/_matrix/media/r0/upload",
+        stable_paths: [
+            1.0 => "/_matrix/media/r0/upload",
+            1.1 => "/_matrix/media/v3/upload",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Retrieve content from the media store.",
         method: GET,
         name: "get_media_content",
-        path: "/_matrix/media/r0/download/:server_name/:media_id",
+        stable_paths: [
+            1.0 => "/_matrix/media/r0/download/:server_name/:media_id",
+            1.1 => "/_matrix/media/v3/download/:server_name/:media_id",
+        ],
         rate_limited: false,
         authentication: None,
     }
@@ -47,6 +50,11 @@ ruma_api! {
         /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Disposition#Syntax
         #[ruma_api(header = CONTENT_DISPOSITION)]
         pub content_disposition: Option<String>,
+
+        /// The value of the `Cache-Control` HTTP header, controlling how long the content may be
+        /// cached.
+        #[ruma_api(header = CACHE_CONTROL)]
+        pub cache_control: Option<String>,
     }
 
     error: crate::Error
@@ -71,6 +79,6 @@ impl<'a> Request<'a> {
 impl Response {
     /// Creates a new `Response` with the given file contents.
     pub fn new(file: Vec<u8>) -> Self {
-        Self { file, content_type: None, content_disposition: None }
+        Self { file, content_type: None, content_disposition: None, cache_control: None }
     }
 }
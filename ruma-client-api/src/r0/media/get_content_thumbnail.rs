@@ -24,7 +24,10 @@ ruma_api! {
         description: "Get a thumbnail of content from the media store.",
         method: GET,
         name: "get_content_thumbnail",
-        path: "/_matrix/media/r0/thumbnail/:server_name/:media_id",
+        stable_paths: [
+            1.0 => "/_matrix/media/r0/thumbnail/:server_name/:media_id",
+            1.1 => "/_matrix/media/v3/thumbnail/:server_name/:media_id",
+        ],
         rate_limited: true,
         authentication: None,
     }
@@ -69,6 +72,11 @@ ruma_api! {
         /// The content type of the thumbnail.
         #[ruma_api(header = CONTENT_TYPE)]
         pub content_type: Option<String>,
+
+        /// The value of the `Cache-Control` HTTP header, controlling how long the content may be
+        /// cached.
+        #[ruma_api(header = CACHE_CONTROL)]
+        pub cache_control: Option<String>,
     }
 
     error: crate::Error
@@ -95,6 +103,6 @@ impl<'a> Request<'a> {
 impl Response {
     /// Creates a new `Response` with the given thumbnail.
     pub fn new(file: Vec<u8>) -> Self {
-        Self { file, content_type: None }
+        Self { file, content_type: None, cache_control: None }
     }
 }
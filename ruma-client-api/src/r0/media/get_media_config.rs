@@ -7,7 +7,10 @@ ruma_api! {
     metadata: {
         description: "Gets the config for the media repository.",
         method: GET,
-        path: "/_matrix/media/r0/config",
+        stable_paths: [
+            1.0 => "/_matrix/media/r0/config",
+            1.1 => "/_matrix/media/v3/config",
+        ],
         name: "get_media_config",
         rate_limited: true,
         authentication: AccessToken,
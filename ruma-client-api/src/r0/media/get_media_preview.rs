@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get a preview for a URL.",
         name: "get_media_preview",
         method: GET,
-        path: "/_matrix/media/r0/preview_url",
+        stable_paths: [
+            1.0 => "/_matrix/media/r0/preview_url",
+            1.1 => "/_matrix/media/v3/preview_url",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
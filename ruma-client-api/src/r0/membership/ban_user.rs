@@ -8,7 +8,10 @@ ruma_api! {
         description: "Ban a user from a room.",
         method: POST,
         name: "ban_user",
-        path: "/_matrix/client/r0/rooms/:room_id/ban",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/ban",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/ban",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Forget a room.",
         method: POST,
         name: "forget_room",
-        path: "/_matrix/client/r0/rooms/:room_id/forget",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/forget",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/forget",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
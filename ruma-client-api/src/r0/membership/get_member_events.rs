@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get membership events for a room.",
         method: GET,
         name: "get_member_events",
-        path: "/_matrix/client/r0/rooms/:room_id/members",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/members",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/members",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
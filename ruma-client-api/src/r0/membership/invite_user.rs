@@ -19,7 +19,10 @@ ruma_api! {
         description: "Invite a user to a room.",
         method: POST,
         name: "invite_user",
-        path: "/_matrix/client/r0/rooms/:room_id/invite",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/invite",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/invite",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
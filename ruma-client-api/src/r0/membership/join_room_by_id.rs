@@ -10,7 +10,10 @@ ruma_api! {
         description: "Join a room using its ID.",
         method: POST,
         name: "join_room_by_id",
-        path: "/_matrix/client/r0/rooms/:room_id/join",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/join",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/join",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
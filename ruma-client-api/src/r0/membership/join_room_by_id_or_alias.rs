@@ -10,7 +10,10 @@ ruma_api! {
         description: "Join a room using its ID or one of its aliases.",
         method: POST,
         name: "join_room_by_id_or_alias",
-        path: "/_matrix/client/r0/join/:room_id_or_alias",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/join/:room_id_or_alias",
+            1.1 => "/_matrix/client/v3/join/:room_id_or_alias",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get a map of user ids to member info objects for members of the room. Primarily for use in Application Services.",
         method: GET,
         name: "joined_members",
-        path: "/_matrix/client/r0/rooms/:room_id/joined_members",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/joined_members",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/joined_members",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get a list of the user's current rooms.",
         method: GET,
         name: "joined_rooms",
-        path: "/_matrix/client/r0/joined_rooms",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/joined_rooms",
+            1.1 => "/_matrix/client/v3/joined_rooms",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Kick a user from a room.",
         method: POST,
         name: "kick_user",
-        path: "/_matrix/client/r0/rooms/:room_id/kick",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/kick",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/kick",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
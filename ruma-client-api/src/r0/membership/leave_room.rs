@@ -8,7 +8,10 @@ ruma_api! {
         description: "Leave a room.",
         method: POST,
         name: "leave_room",
-        path: "/_matrix/client/r0/rooms/:room_id/leave",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/leave",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/leave",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
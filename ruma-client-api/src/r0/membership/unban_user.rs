@@ -8,7 +8,10 @@ ruma_api! {
         description: "Unban a user from a room.",
         method: POST,
         name: "unban_user",
-        path: "/_matrix/client/r0/rooms/:room_id/unban",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/unban",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/unban",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
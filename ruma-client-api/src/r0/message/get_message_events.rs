@@ -14,7 +14,10 @@ ruma_api! {
         description: "Get message events for a room.",
         method: GET,
         name: "get_message_events",
-        path: "/_matrix/client/r0/rooms/:room_id/messages",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/messages",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/messages",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Send a message event to a room.",
         method: PUT,
         name: "create_message_event",
-        path: "/_matrix/client/r0/rooms/:room_id/send/:event_type/:txn_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/send/:event_type/:txn_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/send/:event_type/:txn_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get presence status for this user.",
         method: GET,
         name: "get_presence",
-        path: "/_matrix/client/r0/presence/:user_id/status",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/presence/:user_id/status",
+            1.1 => "/_matrix/client/v3/presence/:user_id/status",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get the avatar URL of a user.",
         method: GET,
         name: "get_avatar_url",
-        path: "/_matrix/client/r0/profile/:user_id/avatar_url",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/profile/:user_id/avatar_url",
+            1.1 => "/_matrix/client/v3/profile/:user_id/avatar_url",
+        ],
         rate_limited: false,
         #[cfg(not(feature = "unstable-synapse-quirks"))]
         authentication: None,
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get the display name of a user.",
         method: GET,
         name: "get_display_name",
-        path: "/_matrix/client/r0/profile/:user_id/displayname",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/profile/:user_id/displayname",
+            1.1 => "/_matrix/client/v3/profile/:user_id/displayname",
+        ],
         rate_limited: false,
         #[cfg(not(feature = "unstable-synapse-quirks"))]
         authentication: None,
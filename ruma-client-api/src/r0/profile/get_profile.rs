@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get all profile information of an user.",
         method: GET,
         name: "get_profile",
-        path: "/_matrix/client/r0/profile/:user_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/profile/:user_id",
+            1.1 => "/_matrix/client/v3/profile/:user_id",
+        ],
         rate_limited: false,
         #[cfg(not(feature = "unstable-synapse-quirks"))]
         authentication: None,
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Set the avatar URL of the user.",
         method: PUT,
         name: "set_avatar_url",
-        path: "/_matrix/client/r0/profile/:user_id/avatar_url",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/profile/:user_id/avatar_url",
+            1.1 => "/_matrix/client/v3/profile/:user_id/avatar_url",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Set the display name of the user.",
         method: PUT,
         name: "set_display_name",
-        path: "/_matrix/client/r0/profile/:user_id/displayname",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/profile/:user_id/displayname",
+            1.1 => "/_matrix/client/v3/profile/:user_id/displayname",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
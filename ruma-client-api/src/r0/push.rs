@@ -21,6 +21,12 @@ pub mod set_pushrule;
 pub mod set_pushrule_actions;
 pub mod set_pushrule_enabled;
 
+mod room_notification_mode;
+
+pub use ruma_common::push::RuleKind;
+
+pub use self::room_notification_mode::{PushRuleChange, PushRuleRequest, RoomNotificationMode};
+
 /// Like `SimplePushRule`, but may represent any kind of push rule
 /// thanks to `pattern` and `conditions` being optional.
 ///
@@ -158,29 +164,6 @@ impl TryFrom<PushRule> for ConditionalPushRule {
     }
 }
 
-/// The kinds of push rules that are available.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
-#[ruma_enum(rename_all = "snake_case")]
-pub enum RuleKind {
-    /// User-configured rules that override all other kinds.
-    Override,
-
-    /// Lowest priority user-defined rules.
-    Underride,
-
-    /// Sender-specific rules.
-    Sender,
-
-    /// Room-specific rules.
-    Room,
-
-    /// Content-specific rules.
-    Content,
-
-    #[doc(hidden)]
-    _Custom(String),
-}
-
 /// Defines a pusher.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Pusher {
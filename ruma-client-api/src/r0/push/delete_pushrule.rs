@@ -9,7 +9,10 @@ ruma_api! {
         description: "This endpoint removes the push rule defined in the path.",
         method: DELETE,
         name: "delete_pushrule",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
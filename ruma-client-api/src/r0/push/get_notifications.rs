@@ -15,7 +15,10 @@ ruma_api! {
         description: "Paginate through the list of events that the user has been, or would have been notified about.",
         method: GET,
         name: "get_notifications",
-        path: "/_matrix/client/r0/notifications",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/notifications",
+            1.1 => "/_matrix/client/v3/notifications",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
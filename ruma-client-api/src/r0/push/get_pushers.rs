@@ -9,7 +9,10 @@ ruma_api! {
         description: "Gets all currently active pushers for the authenticated user.",
         method: GET,
         name: "get_pushers",
-        path: "/_matrix/client/r0/pushers",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushers",
+            1.1 => "/_matrix/client/v3/pushers",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "This endpoint get the actions for the specified push rule.",
         method: GET,
         name: "get_pushrule_actions",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/actions",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/actions",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id/actions",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
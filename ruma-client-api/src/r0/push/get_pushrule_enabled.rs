@@ -9,7 +9,10 @@ ruma_api! {
         description: "This endpoint gets whether the specified push rule is enabled.",
         method: GET,
         name: "get_pushrule_enabled",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/enabled",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/enabled",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id/enabled",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
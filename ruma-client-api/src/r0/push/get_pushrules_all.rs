@@ -8,7 +8,10 @@ ruma_api! {
         description: "Retrieve all push rulesets for this user.",
         method: GET,
         name: "get_pushrules_all",
-        path: "/_matrix/client/r0/pushrules/",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/",
+            1.1 => "/_matrix/client/v3/pushrules/",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Retrieve all push rulesets in the global scope for this user.",
         method: GET,
         name: "get_pushrules_global_scope",
-        path: "/_matrix/client/r0/pushrules/global/",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/global/",
+            1.1 => "/_matrix/client/v3/pushrules/global/",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
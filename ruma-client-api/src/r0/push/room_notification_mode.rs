@@ -0,0 +1,391 @@
+//! Notification settings of a room, on top of push rules.
+
+use ruma_common::push::{Action, AnyPushRuleRef, PushCondition, Ruleset};
+use ruma_identifiers::RoomId;
+
+use super::{delete_pushrule, set_pushrule, RuleKind};
+
+/// The notification mode of a room, as presented to users by clients.
+///
+/// The modes are represented with push rules of the `global` scope whose ID is the ID of the room:
+///
+/// * `AllMessages` is the absence of such rules with no `notify` action, so the messages of the
+///   room notify according to the other rules of the user.
+/// * `MentionsAndKeywords` is a `room` rule with no `notify` action, so only the `override` and
+///   `content` rules can make messages of the room notify.
+/// * `Mute` is an `override` rule with no `notify` action that applies to all the events of the
+///   room.
+///
+/// Enabled rules for the room with a `notify` action, like a rule that sets a custom sound, are
+/// left untouched when switching to `AllMessages` or `Mute`. Switching to `MentionsAndKeywords`
+/// replaces such a `room` rule, which is not restored when switching back to `AllMessages`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RoomNotificationMode {
+    /// All messages notify.
+    AllMessages,
+
+    /// Only messages that mention the user or contain one of their keywords notify.
+    MentionsAndKeywords,
+
+    /// No messages notify.
+    Mute,
+}
+
+impl RoomNotificationMode {
+    /// Reads the notification mode of the room with the given ID in the given push rules.
+    pub fn from_ruleset(ruleset: &Ruleset, room_id: &RoomId) -> Self {
+        if is_silent(ruleset.get(RuleKind::Override, room_id.as_str())) {
+            Self::Mute
+        } else if is_silent(ruleset.get(RuleKind::Room, room_id.as_str())) {
+            Self::MentionsAndKeywords
+        } else {
+            Self::AllMessages
+        }
+    }
+
+    /// The changes to make to the given push rules to switch the room with the given ID to this
+    /// mode.
+    ///
+    /// Returns an empty list if the room is already in this mode.
+    pub fn changes(self, ruleset: &Ruleset, room_id: &RoomId) -> Vec<PushRuleChange> {
+        let mut changes = Vec::new();
+        let rule_id = room_id.as_str();
+
+        let override_rule = ruleset.get(RuleKind::Override, rule_id);
+        let room_rule = ruleset.get(RuleKind::Room, rule_id);
+
+        match self {
+            Self::AllMessages => {
+                delete_silent_rule(&mut changes, override_rule);
+                delete_silent_rule(&mut changes, room_rule);
+            }
+            Self::MentionsAndKeywords => {
+                delete_silent_rule(&mut changes, override_rule);
+                set_silent_rule(&mut changes, room_rule, RuleKind::Room, room_id, Vec::new());
+            }
+            Self::Mute => {
+                let conditions = vec![PushCondition::EventMatch {
+                    key: "room_id".into(),
+                    pattern: rule_id.to_owned(),
+                }];
+
+                // The room rule doesn't matter since the override rule has a higher priority.
+                set_silent_rule(
+                    &mut changes,
+                    override_rule,
+                    RuleKind::Override,
+                    room_id,
+                    conditions,
+                );
+            }
+        }
+
+        changes
+    }
+}
+
+/// A change to a push rule of the `global` scope.
+///
+/// Use `PushRuleChange::request` to get the request to send to the homeserver.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum PushRuleChange {
+    /// Create or replace a push rule.
+    Set {
+        /// The kind of the rule.
+        kind: RuleKind,
+
+        /// The ID of the rule.
+        rule_id: String,
+
+        /// The actions of the rule.
+        actions: Vec<Action>,
+
+        /// The conditions of the rule.
+        conditions: Vec<PushCondition>,
+    },
+
+    /// Delete a push rule.
+    Delete {
+        /// The kind of the rule.
+        kind: RuleKind,
+
+        /// The ID of the rule.
+        rule_id: String,
+    },
+}
+
+impl PushRuleChange {
+    /// The request to send to the homeserver to make this change.
+    pub fn request(&self) -> PushRuleRequest<'_> {
+        match self {
+            Self::Set { kind, rule_id, actions, conditions } => {
+                let mut request =
+                    set_pushrule::Request::new("global", kind.clone(), rule_id, actions);
+                request.conditions = conditions;
+                PushRuleRequest::Set(request)
+            }
+            Self::Delete { kind, rule_id } => PushRuleRequest::Delete(
+                delete_pushrule::Request::new("global", kind.clone(), rule_id),
+            ),
+        }
+    }
+}
+
+/// A request to make a `PushRuleChange`.
+#[derive(Debug)]
+pub enum PushRuleRequest<'a> {
+    /// A request to the `set_pushrule` endpoint.
+    Set(set_pushrule::Request<'a>),
+
+    /// A request to the `delete_pushrule` endpoint.
+    Delete(delete_pushrule::Request<'a>),
+}
+
+/// Whether the given actions make an event notify.
+fn notifies(actions: &[Action]) -> bool {
+    actions.iter().any(|action| matches!(action, Action::Notify | Action::Coalesce))
+}
+
+/// Whether the given rule exists, is enabled and doesn't make events notify.
+fn is_silent(rule: Option<AnyPushRuleRef<'_>>) -> bool {
+    matches!(rule, Some(rule) if rule.is_enabled() && !notifies(rule.actions()))
+}
+
+/// Adds the change to delete the given rule, if it exists.
+fn delete_rule(changes: &mut Vec<PushRuleChange>, rule: Option<AnyPushRuleRef<'_>>) {
+    if let Some(rule) = rule {
+        changes
+            .push(PushRuleChange::Delete { kind: rule.kind(), rule_id: rule.rule_id().to_owned() });
+    }
+}
+
+/// Adds the change to delete the given rule, if it exists, is enabled and doesn't make events
+/// notify.
+fn delete_silent_rule(changes: &mut Vec<PushRuleChange>, rule: Option<AnyPushRuleRef<'_>>) {
+    if is_silent(rule) {
+        delete_rule(changes, rule);
+    }
+}
+
+/// Adds the changes to have an enabled rule for the room that doesn't notify, if the current
+/// `rule` isn't one.
+///
+/// A disabled rule is deleted first, since replacing a rule keeps its `enabled` state.
+fn set_silent_rule(
+    changes: &mut Vec<PushRuleChange>,
+    rule: Option<AnyPushRuleRef<'_>>,
+    kind: RuleKind,
+    room_id: &RoomId,
+    conditions: Vec<PushCondition>,
+) {
+    if is_silent(rule) {
+        return;
+    }
+    if let Some(rule) = rule.filter(|rule| !rule.is_enabled()) {
+        delete_rule(changes, Some(rule));
+    }
+
+    changes.push(PushRuleChange::Set {
+        kind,
+        rule_id: room_id.as_str().to_owned(),
+        actions: vec![Action::DontNotify],
+        conditions,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_common::push::{
+        Action, AnyPushRule, ConditionalPushRuleInit, Ruleset, SimplePushRuleInit, Tweak,
+    };
+    use ruma_identifiers::{room_id, user_id};
+
+    use super::{PushRuleChange, PushRuleRequest, RoomNotificationMode};
+    use crate::r0::push::RuleKind;
+
+    /// Applies the changes to the ruleset, like the homeserver would.
+    fn apply(ruleset: &mut Ruleset, changes: Vec<PushRuleChange>) {
+        for change in changes {
+            match change {
+                PushRuleChange::Set { kind, rule_id, actions, conditions } => {
+                    let rule = match kind {
+                        RuleKind::Override => AnyPushRule::Override(
+                            ConditionalPushRuleInit {
+                                actions,
+                                default: false,
+                                enabled: true,
+                                rule_id,
+                                conditions,
+                            }
+                            .into(),
+                        ),
+                        RuleKind::Room => AnyPushRule::Room(
+                            SimplePushRuleInit { actions, default: false, enabled: true, rule_id }
+                                .into(),
+                        ),
+                        _ => panic!("unexpected rule kind"),
+                    };
+                    ruleset.insert(rule, None, None).unwrap();
+                }
+                PushRuleChange::Delete { kind, rule_id } => {
+                    ruleset.remove(kind, &rule_id).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn switch_modes() {
+        let room_id = room_id!("!far_west:server.name");
+        let mut ruleset = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::AllMessages
+        );
+        assert!(RoomNotificationMode::AllMessages.changes(&ruleset, &room_id).is_empty());
+
+        for mode in [
+            RoomNotificationMode::Mute,
+            RoomNotificationMode::MentionsAndKeywords,
+            RoomNotificationMode::Mute,
+            RoomNotificationMode::AllMessages,
+            RoomNotificationMode::MentionsAndKeywords,
+            RoomNotificationMode::AllMessages,
+        ]
+        .iter()
+        {
+            let changes = mode.changes(&ruleset, &room_id);
+            assert!(!changes.is_empty());
+            apply(&mut ruleset, changes);
+
+            assert_eq!(RoomNotificationMode::from_ruleset(&ruleset, &room_id), *mode);
+            assert!(mode.changes(&ruleset, &room_id).is_empty());
+        }
+
+        assert!(ruleset.get(RuleKind::Override, room_id.as_str()).is_none());
+        assert!(ruleset.get(RuleKind::Room, room_id.as_str()).is_none());
+    }
+
+    #[test]
+    fn disabled_rules() {
+        let room_id = room_id!("!far_west:server.name");
+        let mut ruleset = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+
+        let changes = RoomNotificationMode::Mute.changes(&ruleset, &room_id);
+        apply(&mut ruleset, changes);
+        ruleset.set_enabled(RuleKind::Override, room_id.as_str(), false).unwrap();
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::AllMessages
+        );
+
+        let changes = RoomNotificationMode::Mute.changes(&ruleset, &room_id);
+        assert_matches!(
+            changes.as_slice(),
+            [PushRuleChange::Delete { kind: RuleKind::Override, .. }, PushRuleChange::Set { .. }]
+        );
+        apply(&mut ruleset, changes);
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::Mute
+        );
+    }
+
+    #[test]
+    fn notifying_rules() {
+        let room_id = room_id!("!far_west:server.name");
+        let mut ruleset = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        let actions = vec![Action::Notify, Action::SetTweak(Tweak::Sound("yeehaw".into()))];
+        ruleset
+            .insert(
+                AnyPushRule::Room(
+                    SimplePushRuleInit {
+                        actions,
+                        default: false,
+                        enabled: true,
+                        rule_id: room_id.to_string(),
+                    }
+                    .into(),
+                ),
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::AllMessages
+        );
+        assert!(RoomNotificationMode::AllMessages.changes(&ruleset, &room_id).is_empty());
+
+        let changes = RoomNotificationMode::Mute.changes(&ruleset, &room_id);
+        assert_matches!(changes.as_slice(), [PushRuleChange::Set { kind: RuleKind::Override, .. }]);
+        apply(&mut ruleset, changes);
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::Mute
+        );
+
+        let changes = RoomNotificationMode::AllMessages.changes(&ruleset, &room_id);
+        assert_matches!(
+            changes.as_slice(),
+            [PushRuleChange::Delete { kind: RuleKind::Override, .. }]
+        );
+        apply(&mut ruleset, changes);
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::AllMessages
+        );
+        assert_matches!(
+            ruleset.get(RuleKind::Room, room_id.as_str()).unwrap().actions(),
+            [Action::Notify, Action::SetTweak(Tweak::Sound(sound))] if sound == "yeehaw"
+        );
+
+        let changes = RoomNotificationMode::MentionsAndKeywords.changes(&ruleset, &room_id);
+        assert_matches!(
+            changes.as_slice(),
+            [PushRuleChange::Set { kind: RuleKind::Room, actions, .. }]
+                if matches!(actions.as_slice(), [Action::DontNotify])
+        );
+        apply(&mut ruleset, changes);
+        assert_eq!(
+            RoomNotificationMode::from_ruleset(&ruleset, &room_id),
+            RoomNotificationMode::MentionsAndKeywords
+        );
+
+        let changes = RoomNotificationMode::AllMessages.changes(&ruleset, &room_id);
+        assert_matches!(changes.as_slice(), [PushRuleChange::Delete { kind: RuleKind::Room, .. }]);
+        apply(&mut ruleset, changes);
+        assert!(ruleset.get(RuleKind::Room, room_id.as_str()).is_none());
+    }
+
+    #[test]
+    fn change_requests() {
+        let room_id = room_id!("!far_west:server.name");
+        let ruleset = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+
+        let changes = RoomNotificationMode::Mute.changes(&ruleset, &room_id);
+        match changes[0].request() {
+            PushRuleRequest::Set(request) => {
+                assert_eq!(request.scope, "global");
+                assert_eq!(request.kind, RuleKind::Override);
+                assert_eq!(request.rule_id, "!far_west:server.name");
+                assert_matches!(request.actions, [Action::DontNotify]);
+                assert_eq!(request.conditions.len(), 1);
+            }
+            request => panic!("unexpected request: {:?}", request),
+        }
+
+        let changes = PushRuleChange::Delete { kind: RuleKind::Room, rule_id: room_id.to_string() };
+        match changes.request() {
+            PushRuleRequest::Delete(request) => {
+                assert_eq!(request.kind, RuleKind::Room);
+                assert_eq!(request.rule_id, "!far_west:server.name");
+            }
+            request => panic!("unexpected request: {:?}", request),
+        }
+    }
+}
@@ -9,7 +9,10 @@ ruma_api! {
         description: "This endpoint allows the creation, modification and deletion of pushers for this user ID.",
         method: POST,
         name: "set_pusher",
-        path: "/_matrix/client/r0/pushers/set",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushers/set",
+            1.1 => "/_matrix/client/v3/pushers/set",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "This endpoint allows the creation, modification and deletion of pushers for this user ID.",
         method: PUT,
         name: "set_pushrule",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
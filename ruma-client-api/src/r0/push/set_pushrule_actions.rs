@@ -10,7 +10,10 @@ ruma_api! {
         description: "This endpoint allows clients to change the actions of a push rule. This can be used to change the actions of builtin rules.",
         method: PUT,
         name: "set_pushrule_actions",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/actions",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/actions",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id/actions",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
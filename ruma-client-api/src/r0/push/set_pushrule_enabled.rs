@@ -9,7 +9,10 @@ ruma_api! {
         description: "This endpoint allows clients to enable or disable the specified push rule.",
         method: PUT,
         name: "set_pushrule_enabled",
-        path: "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/enabled",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/pushrules/:scope/:kind/:rule_id/enabled",
+            1.1 => "/_matrix/client/v3/pushrules/:scope/:kind/:rule_id/enabled",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Send a receipt event to a room.",
         method: POST,
         name: "create_receipt",
-        path: "/_matrix/client/r0/rooms/:room_id/receipt/:receipt_type/:event_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/receipt/:receipt_type/:event_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/receipt/:receipt_type/:event_id",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -1,5 +1,6 @@
 //! Endpoints for room management.
 
+pub mod aliases;
 pub mod create_room;
 pub mod get_room_event;
 pub mod report_content;
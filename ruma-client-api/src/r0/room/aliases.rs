@@ -8,7 +8,10 @@ ruma_api! {
         description: "Get a list of aliases maintained by the local server for the given room.",
         method: GET,
         name: "aliases",
-        path: "/_matrix/client/r0/rooms/:room_id/aliases",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/aliases",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/aliases",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
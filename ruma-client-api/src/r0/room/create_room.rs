@@ -21,7 +21,10 @@ ruma_api! {
         description: "Create a new room.",
         method: POST,
         name: "create_room",
-        path: "/_matrix/client/r0/createRoom",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/createRoom",
+            1.1 => "/_matrix/client/v3/createRoom",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
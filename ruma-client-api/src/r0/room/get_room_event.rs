@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get a single event based on roomId/eventId",
         method: GET,
         name: "get_room_event",
-        path: "/_matrix/client/r0/rooms/:room_id/event/:event_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/event/:event_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/event/:event_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
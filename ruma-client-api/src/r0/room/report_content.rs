@@ -9,7 +9,10 @@ ruma_api! {
         description: "Report content as inappropriate.",
         method: POST,
         name: "report_content",
-        path: "/_matrix/client/r0/rooms/:room_id/report/:event_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/report/:event_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/report/:event_id",
+        ],
         rate_limited:  false,
         authentication: AccessToken,
     }
@@ -8,7 +8,10 @@ ruma_api! {
         description: "Upgrades a room to a particular version.",
         method: POST,
         name: "upgrade_room",
-        path: "/_matrix/client/r0/rooms/:room_id/upgrade",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/upgrade",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/upgrade",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
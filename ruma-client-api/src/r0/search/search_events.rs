@@ -16,7 +16,10 @@ ruma_api! {
         description: "Search events.",
         method: POST,
         name: "search",
-        path: "/_matrix/client/r0/search",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/search",
+            1.1 => "/_matrix/client/v3/search",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -407,12 +410,12 @@ impl ResultGroup {
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct SearchResult {
     /// Context for result, if requested.
-    #[serde(skip_serializing_if = "EventContextResult::is_empty")]
+    #[serde(default, skip_serializing_if = "EventContextResult::is_empty")]
     pub context: EventContextResult,
 
     /// A number that describes how closely this result matches the search. Higher is closer.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rank: Option<UInt>,
+    pub rank: Option<f64>,
 
     /// The event that matched.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get information about a particular user.",
         method: GET,
         name: "get_user_info",
-        path: "/_matrix/client/r0/admin/whois/:user_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/admin/whois/:user_id",
+            1.1 => "/_matrix/client/v3/admin/whois/:user_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Login to the homeserver.",
         method: POST,
         name: "login",
-        path: "/_matrix/client/r0/login",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/login",
+            1.1 => "/_matrix/client/v3/login",
+        ],
         rate_limited: true,
         authentication: None,
     }
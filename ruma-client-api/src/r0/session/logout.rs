@@ -7,7 +7,10 @@ ruma_api! {
         description: "Log out of the homeserver.",
         method: POST,
         name: "logout",
-        path: "/_matrix/client/r0/logout",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/logout",
+            1.1 => "/_matrix/client/v3/logout",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
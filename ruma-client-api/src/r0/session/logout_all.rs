@@ -7,7 +7,10 @@ ruma_api! {
         description: "Invalidates all access tokens for a user, so that they can no longer be used for authorization.",
         method: POST,
         name: "logout_all",
-        path: "/_matrix/client/r0/logout/all",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/logout/all",
+            1.1 => "/_matrix/client/v3/logout/all",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -7,7 +7,10 @@ ruma_api! {
         description: "",
         method: GET,
         name: "sso_login",
-        path: "/_matrix/client/r0/login/sso/redirect",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/login/sso/redirect",
+            1.1 => "/_matrix/client/v3/login/sso/redirect",
+        ],
         rate_limited: false,
         authentication: None,
 
@@ -21,6 +24,7 @@ ruma_api! {
         pub redirect_url: &'a str,
     }
 
+    #[ruma_api(status = FOUND)]
     response: {
         /// Redirect URL to the SSO identity provider.
         #[ruma_api(header = LOCATION)]
@@ -61,4 +65,23 @@ mod tests {
             "https://homeserver.tld/_matrix/client/r0/login/sso/redirect?redirectUrl=https%3A%2F%2Fexample.com%2Fsso"
         );
     }
+
+    #[test]
+    #[cfg(feature = "server")]
+    fn serialize_sso_login_response_redirect() {
+        use std::convert::TryFrom;
+
+        use super::Response;
+
+        let res = http::Response::<Vec<u8>>::try_from(Response::new(
+            "https://sso.example.com/login".into(),
+        ))
+        .unwrap();
+
+        assert_eq!(res.status(), http::StatusCode::FOUND);
+        assert_eq!(
+            res.headers().get(http::header::LOCATION).unwrap(),
+            "https://sso.example.com/login"
+        );
+    }
 }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Get state events for a room.",
         method: GET,
         name: "get_state_events",
-        path: "/_matrix/client/r0/rooms/:room_id/state",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/state",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/state",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -11,7 +11,10 @@ ruma_api! {
         description: "Get state events associated with a given key.",
         method: GET,
         name: "get_state_events_for_key",
-        path: "/_matrix/client/r0/rooms/:room_id/state/:event_type/:state_key",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/state/:event_type/:state_key",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/state/:event_type/:state_key",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
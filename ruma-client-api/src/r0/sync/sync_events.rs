@@ -20,7 +20,10 @@ ruma_api! {
         description: "Get all new events from all rooms since the last sync or a given point of time.",
         method: GET,
         name: "sync",
-        path: "/_matrix/client/r0/sync",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/sync",
+            1.1 => "/_matrix/client/v3/sync",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -92,6 +95,19 @@ ruma_api! {
         /// currently held on the server for a device.
         #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
         pub device_one_time_keys_count: BTreeMap<DeviceKeyAlgorithm, UInt>,
+
+        /// The key algorithms for which the device has an unused fallback key.
+        ///
+        /// `None` if the server doesn't support fallback keys.
+        ///
+        /// Defined in [MSC2732](https://github.com/matrix-org/matrix-doc/pull/2732).
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(
+            rename = "org.matrix.msc2732.device_unused_fallback_key_types",
+            alias = "device_unused_fallback_key_types",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub device_unused_fallback_key_types: Option<Vec<DeviceKeyAlgorithm>>,
     }
 
     error: crate::Error
@@ -115,6 +131,8 @@ impl Response {
             to_device: Default::default(),
             device_lists: Default::default(),
             device_one_time_keys_count: BTreeMap::new(),
+            #[cfg(feature = "unstable-pre-spec")]
+            device_unused_fallback_key_types: None,
         }
     }
 }
@@ -589,6 +607,32 @@ mod client_tests {
         assert!(query.contains("set_presence=offline"));
         assert!(query.contains("timeout=30000"))
     }
+
+    #[test]
+    #[cfg(feature = "unstable-pre-spec")]
+    fn deserialize_unused_fallback_key_types() {
+        use std::convert::TryFrom;
+
+        use ruma_identifiers::DeviceKeyAlgorithm;
+        use serde_json::json;
+
+        use super::Response;
+
+        for field in &[
+            "org.matrix.msc2732.device_unused_fallback_key_types",
+            "device_unused_fallback_key_types",
+        ] {
+            let mut body = json!({ "next_batch": "s72595_4483_1934" });
+            body[*field] = json!(["signed_curve25519"]);
+            let http_response = http::Response::new(serde_json::to_vec(&body).unwrap());
+
+            let response = Response::try_from(http_response).unwrap();
+            assert_eq!(
+                response.device_unused_fallback_key_types,
+                Some(vec![DeviceKeyAlgorithm::SignedCurve25519])
+            );
+        }
+    }
 }
 
 #[cfg(all(test, feature = "server"))]
@@ -674,4 +718,29 @@ mod server_tests {
         assert_eq!(req.set_presence, PresenceState::Online);
         assert_eq!(req.timeout, Some(Duration::from_millis(0)));
     }
+
+    #[test]
+    #[cfg(feature = "unstable-pre-spec")]
+    fn serialize_unused_fallback_key_types() {
+        use std::convert::TryFrom;
+
+        use ruma_identifiers::DeviceKeyAlgorithm;
+        use serde_json::{json, Value as JsonValue};
+
+        use super::Response;
+
+        let mut response = Response::new("s72595_4483_1934".into());
+        response.device_unused_fallback_key_types =
+            Some(vec![DeviceKeyAlgorithm::SignedCurve25519]);
+
+        let http_response = http::Response::<Vec<u8>>::try_from(response).unwrap();
+        let body: JsonValue = serde_json::from_slice(http_response.body()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "next_batch": "s72595_4483_1934",
+                "org.matrix.msc2732.device_unused_fallback_key_types": ["signed_curve25519"],
+            })
+        );
+    }
 }
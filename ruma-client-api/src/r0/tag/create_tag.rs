@@ -9,7 +9,10 @@ ruma_api! {
         description: "Add a new tag to a room.",
         method: PUT,
         name: "create_tag",
-        path: "/_matrix/client/r0/user/:user_id/rooms/:room_id/tags/:tag",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/rooms/:room_id/tags/:tag",
+            1.1 => "/_matrix/client/v3/user/:user_id/rooms/:room_id/tags/:tag",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
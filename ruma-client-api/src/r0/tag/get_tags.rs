@@ -9,7 +9,10 @@ ruma_api! {
         description: "Get the tags associated with a room.",
         method: GET,
         name: "get_tags",
-        path: "/_matrix/client/r0/user/:user_id/rooms/:room_id/tags",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user/:user_id/rooms/:room_id/tags",
+            1.1 => "/_matrix/client/v3/user/:user_id/rooms/:room_id/tags",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
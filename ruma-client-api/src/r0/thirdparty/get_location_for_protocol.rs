@@ -10,7 +10,10 @@ ruma_api! {
         description: "Fetches third party locations for a protocol.",
         method: GET,
         name: "get_location_for_protocol",
-        path: "/_matrix/client/r0/thirdparty/location/:protocol",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/location/:protocol",
+            1.1 => "/_matrix/client/v3/thirdparty/location/:protocol",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
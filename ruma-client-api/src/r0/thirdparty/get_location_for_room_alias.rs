@@ -9,7 +9,10 @@ ruma_api! {
         description: "Retrieve an array of third party network locations from a Matrix room alias.",
         method: GET,
         name: "get_location_for_room_alias",
-        path: "/_matrix/client/r0/thirdparty/location",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/location",
+            1.1 => "/_matrix/client/v3/thirdparty/location",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
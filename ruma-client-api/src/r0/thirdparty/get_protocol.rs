@@ -8,7 +8,10 @@ ruma_api! {
         description: "Fetches the metadata from the homeserver about a particular third party protocol.",
         method: GET,
         name: "get_protocol",
-        path: "/_matrix/client/r0/thirdparty/protocol/:protocol",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/protocol/:protocol",
+            1.1 => "/_matrix/client/v3/thirdparty/protocol/:protocol",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
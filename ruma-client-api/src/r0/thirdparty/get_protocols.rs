@@ -10,7 +10,10 @@ ruma_api! {
         description: "Fetches the overall metadata about protocols supported by the homeserver.",
         method: GET,
         name: "get_protocols",
-        path: "/_matrix/client/r0/thirdparty/protocols",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/protocols",
+            1.1 => "/_matrix/client/v3/thirdparty/protocols",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Fetches third party users for a protocol.",
         method: GET,
         name: "get_user_for_protocol",
-        path: "/_matrix/client/r0/thirdparty/user/:protocol",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/user/:protocol",
+            1.1 => "/_matrix/client/v3/thirdparty/user/:protocol",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
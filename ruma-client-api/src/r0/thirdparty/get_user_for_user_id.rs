@@ -9,7 +9,10 @@ ruma_api! {
         description: "Retrieve an array of third party users from a Matrix User ID.",
         method: GET,
         name: "get_user_for_user_id",
-        path: "/_matrix/client/r0/thirdparty/user",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/thirdparty/user",
+            1.1 => "/_matrix/client/v3/thirdparty/user",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
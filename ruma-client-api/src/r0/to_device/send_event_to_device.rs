@@ -14,7 +14,10 @@ ruma_api! {
         description: "Send an event to a device or devices.",
         method: PUT,
         name: "send_event_to_device",
-        path: "/_matrix/client/r0/sendToDevice/:event_type/:txn_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/sendToDevice/:event_type/:txn_id",
+            1.1 => "/_matrix/client/v3/sendToDevice/:event_type/:txn_id",
+        ],
         rate_limited: false,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ use serde::{de::Error, Deserialize, Deserializer, Serialize};
 ruma_api! {
     metadata: {
         method: PUT,
-        path: "/_matrix/client/r0/rooms/:room_id/typing/:user_id",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/rooms/:room_id/typing/:user_id",
+            1.1 => "/_matrix/client/v3/rooms/:room_id/typing/:user_id",
+        ],
         name: "create_typing_event",
         description: "Send a typing event to a room.",
         authentication: AccessToken,
@@ -10,7 +10,10 @@ ruma_api! {
         description: "Performs a search for users.",
         method: POST,
         name: "search_users",
-        path: "/_matrix/client/r0/user_directory/search",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/user_directory/search",
+            1.1 => "/_matrix/client/v3/user_directory/search",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -9,7 +9,10 @@ ruma_api! {
         description: "Get credentials for the client to use when initiating VoIP calls.",
         method: GET,
         name: "turn_server_info",
-        path: "/_matrix/client/r0/voip/turnServer",
+        stable_paths: [
+            1.0 => "/_matrix/client/r0/voip/turnServer",
+            1.1 => "/_matrix/client/v3/voip/turnServer",
+        ],
         rate_limited: true,
         authentication: AccessToken,
     }
@@ -1,8 +1,11 @@
 //! [GET /_matrix/client/versions](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-versions)
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+};
 
-use ruma_api::ruma_api;
+use ruma_api::{ruma_api, MatrixVersion};
 
 ruma_api! {
     metadata: {
@@ -41,4 +44,36 @@ impl Response {
     pub fn new(versions: Vec<String>) -> Self {
         Self { versions, unstable_features: BTreeMap::new() }
     }
+
+    /// Extracts known Matrix versions from this response.
+    ///
+    /// Matrix versions that Ruma cannot parse, or does not know about, are discarded.
+    ///
+    /// The result can be passed to `OutgoingRequest::try_into_http_request_with_versions` to
+    /// select the appropriate path for an endpoint.
+    pub fn known_versions(&self) -> BTreeSet<MatrixVersion> {
+        self.versions.iter().flat_map(|s| MatrixVersion::try_from(s.as_str())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::MatrixVersion;
+
+    use super::Response;
+
+    #[test]
+    fn known_versions() {
+        let response = Response::new(vec![
+            "r0.5.0".to_owned(),
+            "r0.6.1".to_owned(),
+            "v1.1".to_owned(),
+            "v9.9".to_owned(),
+        ]);
+
+        assert_eq!(
+            response.known_versions().into_iter().collect::<Vec<_>>(),
+            vec![MatrixVersion::V1_0, MatrixVersion::V1_1]
+        );
+    }
 }
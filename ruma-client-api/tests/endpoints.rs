@@ -0,0 +1,19 @@
+#![cfg(feature = "registry")]
+
+use std::collections::BTreeSet;
+
+use ruma_client_api::endpoints;
+
+#[test]
+fn endpoints_are_registered() {
+    assert!(endpoints().any(|metadata| metadata.name == "sync"));
+    assert!(endpoints().any(|metadata| metadata.name == "create_message_event"));
+}
+
+#[test]
+fn endpoint_names_are_unique() {
+    let mut names = BTreeSet::new();
+    for metadata in endpoints() {
+        assert!(names.insert(metadata.name), "duplicate endpoint name {}", metadata.name);
+    }
+}
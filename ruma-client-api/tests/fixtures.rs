@@ -0,0 +1,669 @@
+//! Checks the HTTP (de)serialization of every endpoint against the examples in `fixtures`, which
+//! are mostly taken from the specification.
+//!
+//! The fixture of an endpoint is found at the path of its module, e.g.
+//! `fixtures/r0/account/whoami.json` for `r0::account::whoami`.
+
+#![cfg(all(feature = "client", feature = "server"))]
+
+use std::{
+    convert::TryFrom,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use js_int::{int, uint};
+use ruma_api::test_utils::{check_request, round_trip_response, Fixture};
+use ruma_client_api::r0::{
+    account::IdentityServerInfo,
+    filter::FilterDefinition,
+    membership::{
+        get_member_events::MembershipEventFilter, invite_user::InvitationRecipient,
+        ThirdPartySigned,
+    },
+    message::get_message_events::Direction,
+    push::RuleKind,
+    receipt::create_receipt::ReceiptType,
+    room::{create_room::RoomPreset, Visibility},
+    search::search_events::{Categories, Criteria, OrderBy, SearchKeys},
+    session::login::{LoginInfo, UserIdentifier},
+    sync::sync_events::Filter as SyncFilter,
+    typing::create_typing_event::Typing,
+    uiaa::AuthData,
+};
+use ruma_common::{
+    directory::RoomNetwork, presence::PresenceState, push::Action, thirdparty::Medium,
+};
+use ruma_events::{
+    room::{message::MessageEventContent, topic::TopicEventContent},
+    tag::TagInfo,
+    AnyMessageEventContent, AnyStateEventContent, EventType,
+};
+use ruma_identifiers::{
+    event_id, mxc_uri, room_alias_id, room_id, server_name, user_id, RoomIdOrAliasId, RoomVersionId,
+};
+use ruma_serde::Outgoing;
+use serde_json::{
+    from_value as from_json_value, json, value::to_raw_value as to_raw_json_value,
+    Value as JsonValue,
+};
+
+/// Generates a test for each given endpoint that checks that the given request is serialized to
+/// the request of the endpoint's fixture, and that the fixture's response round-trips.
+///
+/// The request expression can refer to the endpoint's `Request` type directly. The JSON body of the
+/// fixture's request can be made available to it, for building complex fields, by adding the name
+/// to bind it to in parentheses after the test name.
+macro_rules! fixture_tests {
+    (
+        $(
+            $( #[$attr:meta] )*
+            $test:ident $( ($body:ident) )?: $( $module:ident )::+ => $request:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            $( #[$attr] )*
+            fn $test() {
+                use ruma_client_api::$( $module )::+::{Request, Response};
+
+                let fixture = Fixture::from_json(include_str!(concat!(
+                    "fixtures" $( , "/", stringify!($module) )+, ".json"
+                )));
+                $( let $body = fixture.request.body.clone().unwrap(); )?
+
+                let _: <Request as Outgoing>::Incoming = check_request($request, &fixture.request);
+                let _: Response = round_trip_response(&fixture.response);
+            }
+        )*
+    };
+}
+
+fixture_tests! {
+    add_3pid: r0::account::add_3pid => Request::new("d0nt-T3ll", "abc123987"),
+    bind_3pid: r0::account::bind_3pid => Request::new(
+        "d0nt-T3ll",
+        IdentityServerInfo::new("example.org", "abc123_OpaqueString"),
+        "abc123987",
+    ),
+    change_password: r0::account::change_password => {
+        let mut request = Request::new("ihatebananas");
+        request.logout_devices = false;
+        request
+    },
+    deactivate: r0::account::deactivate => {
+        let mut request = Request::new();
+        request.id_server = Some("example.org");
+        request
+    },
+    delete_3pid: r0::account::delete_3pid => {
+        let mut request = Request::new(Medium::Email, "example@example.org");
+        request.id_server = Some("example.org");
+        request
+    },
+    get_username_availability: r0::account::get_username_availability
+        => Request::new("my_cool_localpart"),
+    register: r0::account::register => {
+        let mut request = Request::new();
+        request.username = Some("cheeky_monkey");
+        request.password = Some("ilovebananas");
+        request.device_id = Some("GHTYAJCE".into());
+        request.initial_device_display_name = Some("Jungle Phone");
+        request.kind = ruma_client_api::r0::account::register::RegistrationKind::Guest;
+        request.inhibit_login = true;
+        request
+    },
+    request_3pid_management_token_via_email: r0::account::request_3pid_management_token_via_email
+        => {
+        let mut request = Request::new("monkeys_are_GREAT", "alice@example.org", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request.identity_server_info = Some(IdentityServerInfo::new("id.example.com", "abc123_OpaqueString"));
+        request
+    },
+    request_3pid_management_token_via_msisdn:
+        r0::account::request_3pid_management_token_via_msisdn => {
+        let mut request = Request::new("monkeys_are_GREAT", "GB", "07700900001", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request.identity_server_info =
+            Some(IdentityServerInfo::new("id.example.com", "abc123_OpaqueString"));
+        request
+    },
+    request_openid_token: r0::account::request_openid_token
+        => Request::new(&user_id!("@alice:example.org")),
+    request_password_change_token_via_email:
+        r0::account::request_password_change_token_via_email => {
+        let mut request = Request::new("monkeys_are_GREAT", "alice@example.org", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request
+    },
+    request_password_change_token_via_msisdn:
+        r0::account::request_password_change_token_via_msisdn => {
+        let mut request = Request::new("monkeys_are_GREAT", "GB", "07700900001", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request
+    },
+    request_registration_token_via_email: r0::account::request_registration_token_via_email => {
+        let mut request = Request::new("monkeys_are_GREAT", "alice@example.org", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request.identity_server_info =
+            Some(IdentityServerInfo::new("id.example.com", "abc123_OpaqueString"));
+        request
+    },
+    request_registration_token_via_msisdn: r0::account::request_registration_token_via_msisdn => {
+        let mut request = Request::new("monkeys_are_GREAT", "GB", "07700900001", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request.identity_server_info =
+            Some(IdentityServerInfo::new("id.example.com", "abc123_OpaqueString"));
+        request
+    },
+    unbind_3pid: r0::account::unbind_3pid => {
+        let mut request = Request::new(Medium::Email, "example@example.org");
+        request.id_server = Some("example.org");
+        request
+    },
+    whoami: r0::account::whoami => Request::new(),
+    create_alias: r0::alias::create_alias => Request::new(
+        &room_alias_id!("#monkeys:matrix.org"),
+        &room_id!("!abnjk:matrix.org"),
+    ),
+    delete_alias: r0::alias::delete_alias => Request::new(&room_alias_id!("#monkeys:matrix.org")),
+    get_alias: r0::alias::get_alias => Request::new(&room_alias_id!("#monkeys:matrix.org")),
+    appservice_set_room_visibility: r0::appservice::set_room_visibility => Request::new(
+        "irc",
+        &room_id!("!room:example.org"),
+        Visibility::Public,
+    ),
+    #[cfg(feature = "unstable-pre-spec")]
+    add_backup_key_session(body): r0::backup::add_backup_key_session => Request::new(
+        "1",
+        &room_id!("!room:example.org"),
+        "sessionid",
+        from_json_value(body).unwrap(),
+    ),
+    #[cfg(feature = "unstable-pre-spec")]
+    add_backup_key_sessions(body): r0::backup::add_backup_key_sessions => Request::new(
+        "1",
+        &room_id!("!room:example.org"),
+        from_json_value(body["sessions"].clone()).unwrap(),
+    ),
+    #[cfg(feature = "unstable-pre-spec")]
+    add_backup_keys(body): r0::backup::add_backup_keys
+        => Request::new("1", from_json_value(body["rooms"].clone()).unwrap()),
+    #[cfg(feature = "unstable-pre-spec")]
+    create_backup(body): r0::backup::create_backup => Request::new(from_json_value(body).unwrap()),
+    #[cfg(feature = "unstable-pre-spec")]
+    delete_backup: r0::backup::delete_backup => Request::new("1"),
+    #[cfg(feature = "unstable-pre-spec")]
+    delete_backup_key_session: r0::backup::delete_backup_key_session
+        => Request::new("1", &room_id!("!room:example.org"), "sessionid"),
+    #[cfg(feature = "unstable-pre-spec")]
+    delete_backup_key_sessions: r0::backup::delete_backup_key_sessions
+        => Request::new("1", &room_id!("!room:example.org")),
+    #[cfg(feature = "unstable-pre-spec")]
+    delete_backup_keys: r0::backup::delete_backup_keys => Request::new("1"),
+    #[cfg(feature = "unstable-pre-spec")]
+    get_backup: r0::backup::get_backup => Request::new("1"),
+    #[cfg(feature = "unstable-pre-spec")]
+    get_backup_key_session: r0::backup::get_backup_key_session
+        => Request::new("1", &room_id!("!room:example.org"), "sessionid"),
+    #[cfg(feature = "unstable-pre-spec")]
+    get_backup_key_sessions: r0::backup::get_backup_key_sessions
+        => Request::new("1", &room_id!("!room:example.org")),
+    #[cfg(feature = "unstable-pre-spec")]
+    get_backup_keys: r0::backup::get_backup_keys => Request::new("1"),
+    #[cfg(feature = "unstable-pre-spec")]
+    get_latest_backup: r0::backup::get_latest_backup => Request::new(),
+    #[cfg(feature = "unstable-pre-spec")]
+    update_backup(body): r0::backup::update_backup
+        => Request::new("1", from_json_value(body).unwrap()),
+    get_capabilities: r0::capabilities::get_capabilities => Request::new(),
+    get_global_account_data: r0::config::get_global_account_data
+        => Request::new(&user_id!("@alice:example.org"), "org.example.custom.config"),
+    get_room_account_data: r0::config::get_room_account_data => Request::new(
+        &user_id!("@alice:example.org"),
+        &room_id!("!726s6s6q:example.com"),
+        "org.example.custom.room.config",
+    ),
+    set_global_account_data(body): r0::config::set_global_account_data => Request::new(
+        to_raw_json_value(&body).unwrap(),
+        "org.example.custom.config",
+        &user_id!("@alice:example.org"),
+    ),
+    set_room_account_data(body): r0::config::set_room_account_data => Request::new(
+        to_raw_json_value(&body).unwrap(),
+        "org.example.custom.room.config",
+        &room_id!("!726s6s6q:example.com"),
+        &user_id!("@alice:example.org"),
+    ),
+    get_contacts: r0::contact::get_contacts => Request::new(),
+    request_contact_verification_token: r0::contact::request_contact_verification_token => {
+        let mut request = Request::new("monkeys_are_GREAT", "alice@example.org", uint!(1));
+        request.next_link = Some("https://example.org/congratulations.html");
+        request.id_server = Some("id.example.com");
+        request.id_access_token = Some("abc123_OpaqueString");
+        request
+    },
+    get_context: r0::context::get_context => {
+        let mut request = Request::new(
+            leak(room_id!("!636q39766251:example.com")),
+            leak(event_id!("$143273582443PhrSn:example.org")),
+        );
+        request.limit = uint!(3);
+        request
+    },
+    #[cfg(feature = "unstable-pre-spec")]
+    claim_dehydrated_device: r0::device::claim_dehydrated_device
+        => Request::new("ABCDEFG".into()),
+    delete_device: r0::device::delete_device => {
+        let mut request = Request::new("QBUAZIFURK".into());
+        request.auth = Some(auth_data());
+        request
+    },
+    delete_devices: r0::device::delete_devices => {
+        let mut request = Request::new(leak(vec!["QBUAZIFURK".into(), "AUIECTSRND".into()]));
+        request.auth = Some(auth_data());
+        request
+    },
+    #[cfg(feature = "unstable-pre-spec")]
+    get_dehydrated_device: r0::device::get_dehydrated_device => Request::new(),
+    get_device: r0::device::get_device => Request::new("QBUAZIFURK".into()),
+    get_devices: r0::device::get_devices => Request::new(),
+    #[cfg(feature = "unstable-pre-spec")]
+    put_dehydrated_device(body): r0::device::put_dehydrated_device => {
+        let mut request = Request::new(from_json_value(body["device_data"].clone()).unwrap());
+        request.initial_device_display_name = Some("Dehydrated device");
+        request
+    },
+    update_device: r0::device::update_device => {
+        let mut request = Request::new("QBUAZIFURK".into());
+        request.display_name = Some("My other phone".to_owned());
+        request
+    },
+    get_public_rooms: r0::directory::get_public_rooms => {
+        let mut request = Request::new();
+        request.limit = Some(uint!(10));
+        request.since = Some("p1902");
+        request.server = Some(leak(server_name!("matrix.org")));
+        request
+    },
+    get_public_rooms_filtered: r0::directory::get_public_rooms_filtered => {
+        let mut request = Request::new();
+        request.server = Some(leak(server_name!("matrix.org")));
+        request.limit = Some(uint!(10));
+        request.filter.generic_search_term = Some("foo");
+        request.room_network = RoomNetwork::ThirdParty("irc");
+        request
+    },
+    get_room_visibility: r0::directory::get_room_visibility
+        => Request::new(&room_id!("!abnjk:matrix.org")),
+    set_room_visibility: r0::directory::set_room_visibility
+        => Request::new(&room_id!("!abnjk:matrix.org"), Visibility::Public),
+    create_filter: r0::filter::create_filter => {
+        let mut filter = FilterDefinition::empty();
+        filter.event_fields = Some(strings(&["type", "content", "sender"]));
+        filter.presence.not_senders = leak(vec![user_id!("@alice:example.com")]);
+        filter.presence.types = Some(strings(&["m.presence"]));
+        filter.room.ephemeral.not_rooms = strings(&["!726s6s6q:example.com"]);
+        filter.room.ephemeral.not_senders = leak(vec![user_id!("@spam:example.com")]);
+        filter.room.ephemeral.types = Some(strings(&["m.receipt", "m.typing"]));
+        filter.room.state.not_rooms = strings(&["!726s6s6q:example.com"]);
+        filter.room.state.types = Some(strings(&["m.room.*"]));
+        filter.room.timeline.limit = Some(uint!(10));
+        filter.room.timeline.not_rooms = strings(&["!726s6s6q:example.com"]);
+        filter.room.timeline.not_senders = leak(vec![user_id!("@spam:example.com")]);
+        filter.room.timeline.types = Some(strings(&["m.room.message"]));
+
+        Request::new(leak(user_id!("@alice:example.org")), filter)
+    },
+    get_filter: r0::filter::get_filter
+        => Request::new(&user_id!("@alice:example.org"), "66696p746572"),
+    claim_keys(body): r0::keys::claim_keys => {
+        let mut request = Request::new(from_json_value(body["one_time_keys"].clone()).unwrap());
+        request.timeout = Some(Duration::from_secs(10));
+        request
+    },
+    get_key_changes: r0::keys::get_key_changes
+        => Request::new("s72594_4483_1934", "s75689_5632_2435"),
+    get_keys: r0::keys::get_keys => {
+        let mut request = Request::new();
+        request.timeout = Some(Duration::from_secs(10));
+        request.device_keys.insert(user_id!("@alice:example.com"), Vec::new());
+        request.token = Some("string");
+        request
+    },
+    upload_keys(body): r0::keys::upload_keys => {
+        let mut request = Request::new();
+        request.device_keys = Some(from_json_value(body["device_keys"].clone()).unwrap());
+        request.one_time_keys = Some(from_json_value(body["one_time_keys"].clone()).unwrap());
+        request
+    },
+    #[cfg(feature = "unstable-pre-spec")]
+    upload_signatures(body): r0::keys::upload_signatures
+        => Request::new(from_json_value(body).unwrap()),
+    #[cfg(feature = "unstable-pre-spec")]
+    upload_signing_keys(body): r0::keys::upload_signing_keys => {
+        let mut request = Request::new();
+        request.auth = Some(auth_data());
+        request.master_key = Some(from_json_value(body["master_key"].clone()).unwrap());
+        request.self_signing_key = Some(from_json_value(body["self_signing_key"].clone()).unwrap());
+        request.user_signing_key = Some(from_json_value(body["user_signing_key"].clone()).unwrap());
+        request
+    },
+    create_content: r0::media::create_content => {
+        let mut request = Request::new(b"%PDF-1.5 War and Peace".to_vec());
+        request.filename = Some("War and Peace.pdf");
+        request.content_type = Some("application/pdf");
+        request
+    },
+    get_content: r0::media::get_content => {
+        let mut request = Request::new("ascERGshawAWawugaAcauga", leak(server_name!("matrix.org")));
+        request.allow_remote = false;
+        request
+    },
+    get_content_as_filename: r0::media::get_content_as_filename
+        => Request::new("ascERGshawAWawugaAcauga", &server_name!("matrix.org"), "hello.txt"),
+    get_content_thumbnail: r0::media::get_content_thumbnail => {
+        let mut request = Request::new(
+            "ascERGshawAWawugaAcauga",
+            leak(server_name!("matrix.org")),
+            uint!(64),
+            uint!(64),
+        );
+        request.method = Some(ruma_client_api::r0::media::get_content_thumbnail::Method::Scale);
+        request
+    },
+    get_media_config: r0::media::get_media_config => Request::new(),
+    get_media_preview: r0::media::get_media_preview => Request::new(
+        "https://matrix.org",
+        UNIX_EPOCH + Duration::from_millis(1_510_610_716_656),
+    ),
+    ban_user: r0::membership::ban_user => {
+        let mut request = Request::new(
+            leak(room_id!("!e42d8c:matrix.org")),
+            leak(user_id!("@cheeky_monkey:matrix.org")),
+        );
+        request.reason = Some("Telling unfunny jokes");
+        request
+    },
+    forget_room: r0::membership::forget_room => Request::new(&room_id!("!e42d8c:matrix.org")),
+    get_member_events: r0::membership::get_member_events => {
+        let mut request = Request::new(leak(room_id!("!636q39766251:example.com")));
+        request.at = Some("1234");
+        request.membership = Some(MembershipEventFilter::Join);
+        request.not_membership = Some(MembershipEventFilter::Leave);
+        request
+    },
+    invite_user: r0::membership::invite_user => Request::new(
+        &room_id!("!e42d8c:matrix.org"),
+        InvitationRecipient::UserId { user_id: &user_id!("@cheeky_monkey:matrix.org") },
+    ),
+    join_room_by_id(body): r0::membership::join_room_by_id => {
+        let mut request = Request::new(leak(room_id!("!d41d8cd:matrix.org")));
+        request.third_party_signed = Some(third_party_signed(&body));
+        request
+    },
+    join_room_by_id_or_alias(body): r0::membership::join_room_by_id_or_alias => {
+        let mut request =
+            Request::new(leak(RoomIdOrAliasId::try_from("#monkeys:matrix.org").unwrap()));
+        request.server_name =
+            leak(vec![server_name!("matrix.org"), server_name!("elsewhere.ca")]);
+        request.third_party_signed = Some(third_party_signed(&body));
+        request
+    },
+    joined_members: r0::membership::joined_members
+        => Request::new(&room_id!("!e42d8c:matrix.org")),
+    joined_rooms: r0::membership::joined_rooms => Request::new(),
+    kick_user: r0::membership::kick_user => {
+        let mut request = Request::new(
+            leak(room_id!("!e42d8c:matrix.org")),
+            leak(user_id!("@cheeky_monkey:matrix.org")),
+        );
+        request.reason = Some("Telling unfunny jokes");
+        request
+    },
+    leave_room: r0::membership::leave_room => Request::new(&room_id!("!e42d8c:matrix.org")),
+    unban_user: r0::membership::unban_user => Request::new(
+        &room_id!("!e42d8c:matrix.org"),
+        &user_id!("@cheeky_monkey:matrix.org"),
+    ),
+    get_message_events: r0::message::get_message_events => {
+        let mut request = Request::new(
+            leak(room_id!("!636q39766251:example.com")),
+            "s345_678_333",
+            Direction::Backward,
+        );
+        request.limit = uint!(3);
+        request
+    },
+    send_message_event: r0::message::send_message_event => Request::new(
+        leak(room_id!("!636q39766251:example.com")),
+        "35",
+        leak(AnyMessageEventContent::RoomMessage(MessageEventContent::text_plain("hello"))),
+    ),
+    get_presence: r0::presence::get_presence => Request::new(&user_id!("@alice:example.com")),
+    set_presence: r0::presence::set_presence => {
+        let mut request = Request::new(leak(user_id!("@alice:example.com")), PresenceState::Online);
+        request.status_msg = Some("I am here.");
+        request
+    },
+    get_avatar_url: r0::profile::get_avatar_url => Request::new(&user_id!("@alice:example.org")),
+    get_display_name: r0::profile::get_display_name
+        => Request::new(&user_id!("@alice:example.org")),
+    get_profile: r0::profile::get_profile => Request::new(&user_id!("@alice:example.org")),
+    set_avatar_url: r0::profile::set_avatar_url => Request::new(
+        &user_id!("@alice:example.org"),
+        Some(&mxc_uri!("mxc://matrix.org/wefh34uihSDRGhw34")),
+    ),
+    set_display_name: r0::profile::set_display_name
+        => Request::new(&user_id!("@alice:example.org"), Some("Alice Margatroid")),
+    delete_pushrule: r0::push::delete_pushrule
+        => Request::new("global", RuleKind::Content, "nocake"),
+    get_notifications: r0::push::get_notifications => {
+        let mut request = Request::new();
+        request.from = Some("xxxxx");
+        request.limit = Some(uint!(20));
+        request.only = Some("highlight");
+        request
+    },
+    get_pushers: r0::push::get_pushers => Request::new(),
+    get_pushrule: r0::push::get_pushrule => Request::new("global", RuleKind::Content, "nocake"),
+    get_pushrule_actions: r0::push::get_pushrule_actions
+        => Request::new("global", RuleKind::Content, "nocake"),
+    get_pushrule_enabled: r0::push::get_pushrule_enabled
+        => Request::new("global", RuleKind::Content, "nocake"),
+    get_pushrules_all: r0::push::get_pushrules_all => Request::new(),
+    get_pushrules_global_scope: r0::push::get_pushrules_global_scope => Request::new(),
+    set_pusher(body): r0::push::set_pusher => {
+        let mut request = Request::new(from_json_value(body).unwrap());
+        request.append = true;
+        request
+    },
+    set_pushrule: r0::push::set_pushrule => {
+        let mut request =
+            Request::new("global", RuleKind::Content, "nocake", leak(vec![Action::Notify]));
+        request.before = Some("someRuleId");
+        request.after = Some("anotherRuleId");
+        request.pattern = Some("cake*lie");
+        request
+    },
+    set_pushrule_actions: r0::push::set_pushrule_actions
+        => Request::new("global", RuleKind::Content, "nocake", vec![Action::Notify]),
+    set_pushrule_enabled: r0::push::set_pushrule_enabled
+        => Request::new("global", RuleKind::Content, "nocake", true),
+    set_read_marker: r0::read_marker::set_read_marker => {
+        let mut request = Request::new(
+            leak(room_id!("!636q39766251:example.com")),
+            leak(event_id!("$somewhere:example.org")),
+        );
+        request.read_receipt = Some(leak(event_id!("$elsewhere:example.org")));
+        request
+    },
+    create_receipt: r0::receipt::create_receipt => Request::new(
+        &room_id!("!636q39766251:example.com"),
+        ReceiptType::Read,
+        &event_id!("$1:example.com"),
+    ),
+    redact_event: r0::redact::redact_event => {
+        let mut request = Request::new(
+            leak(room_id!("!636q39766251:example.com")),
+            leak(event_id!("$bai2b1i9:matrix.org")),
+            "37",
+        );
+        request.reason = Some("Indecent material");
+        request
+    },
+    aliases: r0::room::aliases => Request::new(&room_id!("!636q39766251:example.com")),
+    create_room: r0::room::create_room => {
+        let mut request = Request::new();
+        request.creation_content.federate = false;
+        request.invite = leak(vec![user_id!("@bob:example.org")]);
+        request.name = Some("The Grand Duke Pub");
+        request.preset = Some(RoomPreset::PublicChat);
+        request.room_alias_name = Some("thepub");
+        request.room_version = Some(leak(RoomVersionId::Version6));
+        request.topic = Some("All about happy hour");
+        request.visibility = Visibility::Public;
+        request
+    },
+    get_room_event: r0::room::get_room_event => Request::new(
+        &room_id!("!636q39766251:example.com"),
+        &event_id!("$143273582443PhrSn:example.org"),
+    ),
+    report_content: r0::room::report_content => Request::new(
+        &room_id!("!636q39766251:example.com"),
+        &event_id!("$143273582443PhrSn:example.org"),
+        int!(-100),
+        "this makes me sad",
+    ),
+    upgrade_room: r0::room::upgrade_room
+        => Request::new(&room_id!("!726s6s6q:example.com"), &RoomVersionId::Version2),
+    search_events: r0::search::search_events => {
+        let mut criteria = Criteria::new("martians and men");
+        criteria.keys = Some(leak(vec![SearchKeys::ContentBody]));
+        criteria.order_by = Some(OrderBy::Recent);
+        let mut categories = Categories::new();
+        categories.room_events = Some(criteria);
+
+        let mut request = Request::new(categories);
+        request.next_batch = Some("YWxsCgpOb25lLDM1ODcwOA");
+        request
+    },
+    get_user_info: r0::server::get_user_info => Request::new(&user_id!("@peter:rabbit.rocks")),
+    get_login_types: r0::session::get_login_types => Request::new(),
+    login: r0::session::login => {
+        let mut request = Request::new(LoginInfo::Password {
+            identifier: UserIdentifier::MatrixId("cheeky_monkey"),
+            password: "ilovebananas",
+        });
+        request.initial_device_display_name = Some("Jungle Phone");
+        request
+    },
+    logout: r0::session::logout => Request::new(),
+    logout_all: r0::session::logout_all => Request::new(),
+    sso_login: r0::session::sso_login => Request::new("https://example.com/sso"),
+    get_state_events: r0::state::get_state_events
+        => Request::new(&room_id!("!726s6s6q:example.com")),
+    get_state_events_for_key: r0::state::get_state_events_for_key => Request::new(
+        &room_id!("!726s6s6q:example.com"),
+        EventType::RoomMember,
+        "@alice:example.org",
+    ),
+    send_state_event: r0::state::send_state_event => Request::new(
+        leak(room_id!("!726s6s6q:example.com")),
+        "",
+        leak(AnyStateEventContent::RoomTopic(TopicEventContent {
+            topic: "All about happy hour".to_owned(),
+        })),
+    ),
+    sync_events: r0::sync::sync_events => {
+        let mut request = Request::new();
+        request.filter = Some(leak(SyncFilter::FilterId("66696p746572")));
+        request.since = Some("s72594_4483_1934");
+        request.set_presence = &PresenceState::Offline;
+        request.timeout = Some(Duration::from_secs(30));
+        request
+    },
+    create_tag: r0::tag::create_tag => {
+        let mut tag_info = TagInfo::new();
+        tag_info.order = Some(0.25);
+        Request::new(
+            leak(user_id!("@alice:example.com")),
+            leak(room_id!("!726s6s6q:example.com")),
+            "u.work",
+            tag_info,
+        )
+    },
+    delete_tag: r0::tag::delete_tag => Request::new(
+        &user_id!("@alice:example.com"),
+        &room_id!("!726s6s6q:example.com"),
+        "u.work",
+    ),
+    get_tags: r0::tag::get_tags
+        => Request::new(&user_id!("@alice:example.com"), &room_id!("!726s6s6q:example.com")),
+    get_location_for_protocol: r0::thirdparty::get_location_for_protocol => {
+        let mut request = Request::new("irc");
+        request.fields.insert("network".to_owned(), "freenode".to_owned());
+        request.fields.insert("channel".to_owned(), "#matrix".to_owned());
+        request
+    },
+    get_location_for_room_alias: r0::thirdparty::get_location_for_room_alias
+        => Request::new(&room_alias_id!("#freenode_#matrix:matrix.org")),
+    get_protocol: r0::thirdparty::get_protocol => Request::new("irc"),
+    get_protocols: r0::thirdparty::get_protocols => Request::new(),
+    get_user_for_protocol: r0::thirdparty::get_user_for_protocol => {
+        let mut request = Request::new("gitter");
+        request.fields.insert("user".to_owned(), "jim".to_owned());
+        request
+    },
+    get_user_for_user_id: r0::thirdparty::get_user_for_user_id
+        => Request::new(&user_id!("@_gitter_jim:matrix.org")),
+    send_event_to_device(body): r0::to_device::send_event_to_device => Request::new(
+        EventType::from("m.new_device"),
+        "35",
+        from_json_value(body["messages"].clone()).unwrap(),
+    ),
+    create_typing_event: r0::typing::create_typing_event => Request::new(
+        &user_id!("@alice:example.com"),
+        &room_id!("!726s6s6q:example.com"),
+        Typing::Yes(Duration::from_secs(30)),
+    ),
+    search_users: r0::user_directory::search_users => {
+        let mut request = Request::new("foo");
+        request.limit = uint!(20);
+        request.language = Some("en-US".to_owned());
+        request
+    },
+    get_turn_server_info: r0::voip::get_turn_server_info => Request::new(),
+    discover_homeserver: unversioned::discover_homeserver => Request::new(),
+    get_supported_versions: unversioned::get_supported_versions => Request::new(),
+}
+
+/// Moves the given value to the heap and leaks it, for requests that are built in a block and
+/// borrow from it.
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
+
+/// Collects the given strings to a leaked slice of `String`s.
+fn strings(strings: &[&str]) -> &'static [String] {
+    leak(strings.iter().map(|&s| s.to_owned()).collect::<Vec<_>>())
+}
+
+/// The `third_party_signed` field of the given request body.
+fn third_party_signed(body: &JsonValue) -> ThirdPartySigned<'static> {
+    ThirdPartySigned {
+        sender: "@alice:example.org",
+        mxid: "@bob:example.org",
+        token: "random8nonce",
+        signatures: from_json_value(body["third_party_signed"]["signatures"].clone()).unwrap(),
+    }
+}
+
+/// The user-interactive authentication data of the specification's examples.
+fn auth_data() -> AuthData<'static> {
+    AuthData::DirectRequest {
+        kind: "example.type.foo",
+        session: Some("xxxxx"),
+        auth_parameters: from_json_value(json!({ "example_credential": "verypoorsharedsecret" }))
+            .unwrap(),
+    }
+}
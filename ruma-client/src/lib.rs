@@ -24,6 +24,20 @@
 //! };
 //! ```
 //!
+//! Servers that only support newer versions of the specification may not accept the `r0` paths
+//! that requests use by default. Call `Client::discover_versions` once to make the client use the
+//! paths of the versions the server supports instead:
+//!
+//! ```no_run
+//! # use ruma_client::Client;
+//! # let homeserver_url = "https://example.com".parse().unwrap();
+//! # let client = Client::new(homeserver_url, None);
+//! # async {
+//! let versions = client.discover_versions().await?;
+//! # Result::<(), ruma_client::Error<_>>::Ok(())
+//! # };
+//! ```
+//!
 //! You can also pass an existing session to the `Client` constructor to restore a previous session
 //! rather than calling `log_in`. This can also be used to create a session for an application
 //! service that does not need to log in, but uses the access_token directly:
@@ -103,7 +117,7 @@
 #![deny(missing_debug_implementations, missing_docs)]
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
     sync::{Arc, Mutex},
     time::Duration,
@@ -113,8 +127,16 @@ use assign::assign;
 use async_stream::try_stream;
 use futures_core::stream::Stream;
 use http::{uri::Uri, Response as HttpResponse};
-use hyper::client::{Client as HyperClient, HttpConnector};
-use ruma_api::{AuthScheme, OutgoingRequest};
+use hyper::{
+    body::Bytes,
+    client::{Client as HyperClient, HttpConnector},
+    Body,
+};
+use ruma_api::{
+    error::{IntoHttpError, ServerError},
+    AuthScheme, EndpointError, IncomingRawBodyResponse, MatrixVersion, OutgoingRawBodyRequest,
+    OutgoingRequest,
+};
 use ruma_client_api::r0::sync::sync_events::{
     Filter as SyncFilter, Request as SyncRequest, Response as SyncResponse,
 };
@@ -170,6 +192,10 @@ struct ClientData {
 
     /// User session data.
     session: Mutex<Option<Session>>,
+
+    /// The versions of the specification supported by the homeserver, used to choose the paths
+    /// of endpoints. Empty if they are unknown.
+    supported_versions: Mutex<Vec<MatrixVersion>>,
 }
 
 impl Client {
@@ -179,6 +205,7 @@ impl Client {
             homeserver_url,
             hyper: HyperClient::builder().build(create_connector()),
             session: Mutex::new(session),
+            supported_versions: Mutex::new(Vec::new()),
         }))
     }
 
@@ -194,6 +221,7 @@ impl Client {
             homeserver_url,
             hyper: client_builder.build(create_connector()),
             session: Mutex::new(session),
+            supported_versions: Mutex::new(Vec::new()),
         }))
     }
 
@@ -204,6 +232,23 @@ impl Client {
         self.0.session.lock().expect("session mutex was poisoned").clone()
     }
 
+    /// Asks the homeserver which versions of the specification it supports.
+    ///
+    /// The versions are stored in this client and used to choose the paths of the endpoints of
+    /// all following requests. Until then, requests use the default paths of the endpoints, which
+    /// are the `r0` paths for most endpoints.
+    pub async fn discover_versions(
+        &self,
+    ) -> Result<BTreeSet<MatrixVersion>, Error<ruma_client_api::Error>> {
+        use ruma_client_api::unversioned::get_supported_versions;
+
+        let response = self.request(get_supported_versions::Request::new()).await?;
+        let versions = response.known_versions();
+        *self.0.supported_versions.lock().unwrap() = versions.iter().copied().collect();
+
+        Ok(versions)
+    }
+
     /// Log in with a username and password.
     ///
     /// In contrast to `api::r0::session::login::call()`, this method stores the
@@ -344,7 +389,109 @@ impl Client {
         request: Request,
         extra_params: Option<BTreeMap<String, String>>,
     ) -> Result<Request::IncomingResponse, Error<Request::EndpointError>> {
-        let client = self.0.clone();
+        let http_request = self.http_request(request, extra_params)?;
+        let hyper_response = self.0.hyper.request(http_request.map(Body::from)).await?;
+
+        Ok(Request::IncomingResponse::try_from(collect_response(hyper_response).await?)?)
+    }
+
+    /// Makes a request to a Matrix API endpoint, sending the given stream as the body.
+    ///
+    /// This is only available for endpoints with a raw body, like `r0::media::create_content`, and
+    /// allows uploading large files without reading them into memory first. The `raw_body` field
+    /// of `request` must be left empty.
+    pub async fn request_with_body_stream<Request, S, O, E>(
+        &self,
+        request: Request,
+        body: S,
+    ) -> Result<Request::IncomingResponse, Error<Request::EndpointError>>
+    where
+        Request: OutgoingRawBodyRequest,
+        S: Stream<Item = Result<O, E>> + Send + 'static,
+        O: Into<Bytes> + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        let http_request =
+            self.http_request_with(request, None, |request, base_url, access_token, versions| {
+                request.try_into_http_request_with_body(
+                    base_url,
+                    access_token,
+                    versions,
+                    Body::wrap_stream(body),
+                )
+            })?;
+        let hyper_response = self.0.hyper.request(http_request).await?;
+
+        Ok(Request::IncomingResponse::try_from(collect_response(hyper_response).await?)?)
+    }
+
+    /// Makes a request to a Matrix API endpoint, returning the body of a successful response as a
+    /// stream.
+    ///
+    /// This is only available for endpoints whose response has a raw body, like
+    /// `r0::media::get_content`, and allows downloading large files without reading them into
+    /// memory first. The `raw_body` field of the returned response is left empty.
+    ///
+    /// Error responses are read completely to deserialize the error.
+    pub async fn request_with_body_stream_response<Request>(
+        &self,
+        request: Request,
+    ) -> Result<(Request::IncomingResponse, Body), Error<Request::EndpointError>>
+    where
+        Request: OutgoingRequest,
+        Request::IncomingResponse: IncomingRawBodyResponse<Request::EndpointError>,
+    {
+        let http_request = self.http_request(request, None)?;
+        let hyper_response = self.0.hyper.request(http_request.map(Body::from)).await?;
+
+        if hyper_response.status().as_u16() < 400 {
+            let (head, body) = hyper_response.into_parts();
+            let response = Request::IncomingResponse::try_from_http_response_head(
+                HttpResponse::from_parts(head, ()),
+            )?;
+
+            Ok((response, body))
+        } else {
+            let response = collect_response(hyper_response).await?;
+            let error = match Request::EndpointError::try_from_response(response) {
+                Ok(err) => ServerError::Known(err),
+                Err(response_err) => ServerError::Unknown(response_err),
+            };
+
+            Err(Error::FromHttpResponse(error.into()))
+        }
+    }
+
+    /// Converts the given request into an `http::Request`, adding the access token if required
+    /// and the given additional URL parameters.
+    fn http_request<Request: OutgoingRequest>(
+        &self,
+        request: Request,
+        extra_params: Option<BTreeMap<String, String>>,
+    ) -> Result<http::Request<Vec<u8>>, Error<Request::EndpointError>> {
+        self.http_request_with(request, extra_params, Request::try_into_http_request_with_versions)
+    }
+
+    /// Converts the given request into an `http::Request` with the given conversion function,
+    /// adding the access token if required and the given additional URL parameters.
+    ///
+    /// The conversion function is also given the versions of the specification supported by the
+    /// homeserver, if they are known.
+    fn http_request_with<Request, T>(
+        &self,
+        request: Request,
+        extra_params: Option<BTreeMap<String, String>>,
+        into_http_request: impl FnOnce(
+            Request,
+            &str,
+            Option<&str>,
+            &[MatrixVersion],
+        ) -> Result<http::Request<T>, IntoHttpError>,
+    ) -> Result<http::Request<T>, Error<Request::EndpointError>>
+    where
+        Request: OutgoingRequest,
+    {
+        let client = &self.0;
         let mut http_request = {
             let session;
             let access_token = if Request::METADATA.authentication == AuthScheme::AccessToken {
@@ -358,7 +505,13 @@ impl Client {
                 None
             };
 
-            request.try_into_http_request(&client.homeserver_url.to_string(), access_token)?
+            let supported_versions = client.supported_versions.lock().unwrap().clone();
+            into_http_request(
+                request,
+                &client.homeserver_url.to_string(),
+                access_token,
+                &supported_versions,
+            )?
         };
 
         let extra_params = urlencoded::to_string(extra_params).unwrap();
@@ -371,14 +524,17 @@ impl Client {
             path_and_query: Some(new_path_and_query.parse()?),
         }))?;
 
-        let hyper_response = client.hyper.request(http_request.map(hyper::Body::from)).await?;
-        let (head, body) = hyper_response.into_parts();
+        Ok(http_request)
+    }
+}
 
-        // FIXME: We read the response into a contiguous buffer here (not actually required for
-        // deserialization) and then copy the whole thing to convert from Bytes to Vec<u8>.
-        let full_body = hyper::body::to_bytes(body).await?;
-        let full_response = HttpResponse::from_parts(head, full_body.as_ref().to_owned());
+/// Reads the whole body of the given response.
+async fn collect_response<E>(
+    hyper_response: HttpResponse<Body>,
+) -> Result<HttpResponse<Vec<u8>>, Error<E>> {
+    let (head, body) = hyper_response.into_parts();
 
-        Ok(Request::IncomingResponse::try_from(full_response)?)
-    }
+    // Unlike copying the bytes, `Vec::from` reuses the buffer if nothing else refers to it.
+    let full_body = hyper::body::to_bytes(body).await?;
+    Ok(HttpResponse::from_parts(head, Vec::from(full_body)))
 }
@@ -0,0 +1,282 @@
+// `cargo bench` works, but if you use `cargo bench -- --save-baseline <name>`
+// or pass any other args to it, it fails with the error
+// `cargo bench unknown option --save-baseline`.
+// To pass args to criterion, use this form
+// `cargo bench --features criterion --bench <name of the bench> -- --save-baseline <name>`.
+
+#![allow(unused_imports, dead_code)]
+
+#[cfg(feature = "criterion")]
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ruma_common::push::{
+    Action, FlattenedJson, GlobMatcher, PatternedPushRuleInit, PushCondition, PushConditionRoomCtx,
+    Ruleset,
+};
+use ruma_identifiers::{room_id, user_id};
+use ruma_serde::Raw;
+use serde_json::{json, Value as JsonValue};
+
+/// Patterns of `event_match` conditions on `content.body`, like keywords set by users.
+const KEYWORDS: &[&str] =
+    &["ruma", "matrix", "conduit", "release", "rust", "Homeserver", "push rules", "bench"];
+
+/// Glob patterns of `event_match` conditions on other keys, matching the whole value.
+const GLOBS: &[&str] = &["m.room.*", "m.call.*", "*.encrypted", "m.room.?essage", "m.reaction"];
+
+fn message() -> Raw<JsonValue> {
+    serde_json::from_value(json!({
+        "type": "m.room.message",
+        "sender": "@rantanplan:server.name",
+        "room_id": "!far_west:server.name",
+        "content": {
+            "msgtype": "m.text",
+            "body": "Has anybody tried to run a homeserver on the latest nightly? It fails to \
+                     build since last week, and I can't find what changed in the compiler."
+        }
+    }))
+    .unwrap()
+}
+
+fn context() -> PushConditionRoomCtx {
+    PushConditionRoomCtx::new(
+        room_id!("!far_west:server.name"),
+        100u32.into(),
+        "Jolly Jumper".into(),
+    )
+}
+
+fn keyword_conditions() -> Vec<PushCondition> {
+    KEYWORDS
+        .iter()
+        .map(|pattern| PushCondition::EventMatch {
+            key: "content.body".into(),
+            pattern: (*pattern).into(),
+        })
+        .collect()
+}
+
+/// The implementation of pattern matching before `GlobMatcher`, to compare the performance.
+mod baseline {
+    use std::str::FromStr;
+
+    use wildmatch::WildMatch;
+
+    pub fn matches_pattern(value: &str, pattern: &str, match_words: bool) -> bool {
+        let value = &value.to_lowercase();
+        let pattern = &pattern.to_lowercase();
+
+        if match_words {
+            value.matches_word(pattern)
+        } else {
+            WildMatch::new(pattern).matches(value)
+        }
+    }
+
+    trait StrExt {
+        fn char_len(&self, index: usize) -> usize;
+        fn char_at(&self, index: usize) -> char;
+        fn find_prev_char(&self, index: usize) -> Option<char>;
+        fn matches_word(&self, pattern: &str) -> bool;
+    }
+
+    impl StrExt for str {
+        fn char_len(&self, index: usize) -> usize {
+            let mut len = 1;
+            while !self.is_char_boundary(index + len) {
+                len += 1;
+            }
+            len
+        }
+
+        fn char_at(&self, index: usize) -> char {
+            let end = index + self.char_len(index);
+            char::from_str(&self[index..end]).unwrap()
+        }
+
+        fn find_prev_char(&self, index: usize) -> Option<char> {
+            if index == 0 {
+                return None;
+            }
+
+            let mut pos = index - 1;
+            while !self.is_char_boundary(pos) {
+                pos -= 1;
+            }
+            Some(self.char_at(pos))
+        }
+
+        fn matches_word(&self, pattern: &str) -> bool {
+            if self == pattern {
+                return true;
+            }
+            if pattern.is_empty() {
+                return false;
+            }
+
+            let start = match self.find(pattern) {
+                Some(start) => start,
+                None => return false,
+            };
+            let end = start + pattern.len();
+
+            let word_boundary_start = !is_word_char(self.char_at(start))
+                || !matches!(self.find_prev_char(start), Some(c) if is_word_char(c));
+
+            if word_boundary_start {
+                let word_boundary_end = end == self.len()
+                    || !is_word_char(self.find_prev_char(end).unwrap())
+                    || !is_word_char(self.char_at(end));
+
+                if word_boundary_end {
+                    return true;
+                }
+            }
+
+            let non_word_str = &self[start..];
+            let non_word = match non_word_str.find(|c: char| !is_word_char(c)) {
+                Some(pos) => pos,
+                None => return false,
+            };
+
+            let word_str = &non_word_str[non_word..];
+            let word = match word_str.find(is_word_char) {
+                Some(pos) => pos,
+                None => return false,
+            };
+
+            word_str[word..].matches_word(pattern)
+        }
+    }
+
+    fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+}
+
+#[cfg(feature = "criterion")]
+fn match_keywords_baseline(c: &mut Criterion) {
+    let event = FlattenedJson::from_raw(&message());
+    let body = event.get_str("content.body").unwrap();
+
+    c.bench_function("match keywords with the baseline implementation", |b| {
+        b.iter(|| {
+            for pattern in KEYWORDS {
+                black_box(baseline::matches_pattern(body, pattern, true));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn match_keywords_compiling_patterns(c: &mut Criterion) {
+    let event = FlattenedJson::from_raw(&message());
+    let body = event.get_str("content.body").unwrap();
+
+    c.bench_function("match keywords, compiling the patterns for every event", |b| {
+        b.iter(|| {
+            for pattern in KEYWORDS {
+                black_box(GlobMatcher::new(pattern, true).matches(body));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn match_keywords_compiled_patterns(c: &mut Criterion) {
+    let event = FlattenedJson::from_raw(&message());
+    let body = event.get_str("content.body").unwrap();
+    let matchers: Vec<_> = KEYWORDS.iter().map(|pattern| GlobMatcher::new(pattern, true)).collect();
+
+    c.bench_function("match keywords with compiled patterns", |b| {
+        b.iter(|| {
+            for matcher in &matchers {
+                black_box(matcher.matches(body));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn match_keywords_cached_patterns(c: &mut Criterion) {
+    let event = FlattenedJson::from_raw(&message());
+    let context = context();
+    let conditions = keyword_conditions();
+
+    c.bench_function("match keywords with `PushCondition::applies`", |b| {
+        b.iter(|| {
+            for condition in &conditions {
+                black_box(condition.applies(&event, &context));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn match_globs_baseline(c: &mut Criterion) {
+    c.bench_function("match globs with the baseline implementation", |b| {
+        b.iter(|| {
+            for pattern in GLOBS {
+                black_box(baseline::matches_pattern("m.room.message", pattern, false));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn match_globs_compiled_patterns(c: &mut Criterion) {
+    let matchers: Vec<_> = GLOBS.iter().map(|pattern| GlobMatcher::new(pattern, false)).collect();
+
+    c.bench_function("match globs with compiled patterns", |b| {
+        b.iter(|| {
+            for matcher in &matchers {
+                black_box(matcher.matches("m.room.message"));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+fn evaluate_ruleset(c: &mut Criterion) {
+    let message = message();
+    let context = context();
+
+    let mut ruleset = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+    for (i, pattern) in KEYWORDS.iter().enumerate() {
+        ruleset.content.insert(
+            PatternedPushRuleInit {
+                actions: vec![Action::Notify],
+                default: false,
+                enabled: true,
+                rule_id: format!("keyword_{}", i),
+                pattern: (*pattern).into(),
+            }
+            .into(),
+        );
+    }
+
+    c.bench_function("evaluate the server-default ruleset with keywords", |b| {
+        b.iter(|| black_box(ruleset.get_actions(&message, &context).count()))
+    });
+}
+
+#[cfg(feature = "criterion")]
+criterion_group!(
+    benches,
+    match_keywords_baseline,
+    match_keywords_compiling_patterns,
+    match_keywords_compiled_patterns,
+    match_keywords_cached_patterns,
+    match_globs_baseline,
+    match_globs_compiled_patterns,
+    evaluate_ruleset
+);
+
+#[cfg(feature = "criterion")]
+criterion_main!(benches);
+
+#[cfg(not(feature = "criterion"))]
+fn main() {
+    // To run the benchmarks the "criterion" feature must be enabled use:
+    // `cargo bench --features criterion --bench push_rules`
+    panic!("Enable the criterion feature to run benchmarks");
+}
@@ -4,7 +4,9 @@
 
 use std::collections::BTreeMap;
 
-use ruma_identifiers::{DeviceIdBox, DeviceKeyId, EventEncryptionAlgorithm, UserId};
+use ruma_identifiers::{
+    DeviceIdBox, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm, UserId,
+};
 use serde::{Deserialize, Serialize};
 
 /// Identity keys for a device.
@@ -66,3 +68,115 @@ impl UnsignedDeviceInfo {
         self.device_display_name.is_none()
     }
 }
+
+/// Signatures for a `SignedKey` object.
+pub type SignedKeySignatures = BTreeMap<UserId, BTreeMap<DeviceKeyId, String>>;
+
+/// A key for the SignedCurve25519 algorithm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct SignedKey {
+    /// Base64-encoded 32-byte Curve25519 public key.
+    pub key: String,
+
+    /// Signatures for the key object.
+    pub signatures: SignedKeySignatures,
+
+    /// Whether this is a fallback key, which is used when the device has run out of one-time
+    /// keys.
+    ///
+    /// Defined in [MSC2732](https://github.com/matrix-org/matrix-doc/pull/2732).
+    #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+    pub fallback: bool,
+}
+
+impl SignedKey {
+    /// Creates a new `SignedKey` with the given key and signatures.
+    pub fn new(key: String, signatures: SignedKeySignatures) -> Self {
+        Self { key, signatures, fallback: false }
+    }
+
+    /// Creates a new fallback `SignedKey` with the given key and signatures.
+    pub fn new_fallback(key: String, signatures: SignedKeySignatures) -> Self {
+        Self { key, signatures, fallback: true }
+    }
+}
+
+/// A one-time public key for "pre-key" messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(untagged)]
+pub enum OneTimeKey {
+    /// A key containing signatures, for the SignedCurve25519 algorithm.
+    SignedKey(SignedKey),
+
+    /// A string-valued key, for the Ed25519 and Curve25519 algorithms.
+    Key(String),
+}
+
+/// Signatures for a `CrossSigningKey` object.
+pub type CrossSigningKeySignatures = BTreeMap<UserId, BTreeMap<DeviceKeyId, String>>;
+
+/// A cross signing key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct CrossSigningKey {
+    /// The ID of the user the key belongs to.
+    pub user_id: UserId,
+
+    /// What the key is used for.
+    pub usage: Vec<KeyUsage>,
+
+    /// The public key. The object must have exactly one property.
+    ///
+    /// The key ID is the algorithm followed by the unpadded base64 encoded public key, e.g.
+    /// `ed25519:nqOvzeuGWT/sRx3h7+MHoInYj3Uk2LD/unI9kDYcHwk`.
+    pub keys: BTreeMap<DeviceKeyId, String>,
+
+    /// Signatures of the key. Only optional for master key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub signatures: CrossSigningKeySignatures,
+}
+
+impl CrossSigningKey {
+    /// Creates a new `CrossSigningKey` with the given user ID, usage, keys and
+    /// signatures.
+    pub fn new(
+        user_id: UserId,
+        usage: Vec<KeyUsage>,
+        keys: BTreeMap<DeviceKeyId, String>,
+        signatures: CrossSigningKeySignatures,
+    ) -> Self {
+        Self { user_id, usage, keys, signatures }
+    }
+
+    /// Returns the ID and value of the ed25519 public key of this cross signing key.
+    ///
+    /// Returns `None` if `keys` doesn't contain exactly one key or if that key isn't an ed25519
+    /// key.
+    pub fn public_key(&self) -> Option<(&DeviceKeyId, &str)> {
+        let mut keys = self.keys.iter();
+
+        match (keys.next(), keys.next()) {
+            (Some((key_id, key)), None) if key_id.algorithm() == DeviceKeyAlgorithm::Ed25519 => {
+                Some((key_id, key))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The usage of a cross signing key.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyUsage {
+    /// Master key.
+    Master,
+
+    /// Self-signing key.
+    SelfSigning,
+
+    /// User-signing key.
+    UserSigning,
+}
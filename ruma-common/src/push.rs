@@ -14,7 +14,10 @@
 //! - sender rules
 //! - underride rules
 
-use std::hash::{Hash, Hasher};
+use std::{
+    hash::{Hash, Hasher},
+    mem,
+};
 
 use indexmap::{
     set::{IndexSet, IntoIter as IndexSetIter},
@@ -25,15 +28,32 @@ use serde::{Deserialize, Serialize};
 
 mod action;
 mod condition;
+mod error;
+mod outcome;
 mod predefined;
 
+#[cfg(feature = "unstable-pre-spec")]
+pub use self::condition::RelatedEvent;
 pub use self::{
     action::{Action, Tweak},
     condition::{
-        ComparisonOperator, FlattenedJson, PushCondition, PushConditionRoomCtx, RoomMemberCountIs,
+        ComparisonOperator, FlattenedJson, FlattenedJsonValue, GlobMatcher, PushCondition,
+        PushConditionPowerLevels, PushConditionRoomCtx, RoomMemberCountIs, ScalarJsonValue,
     },
+    error::{InsertPushRuleError, PusherUrlError, RemovePushRuleError, RuleNotFoundError},
+    outcome::{DecryptedEventOutcome, NotificationOutcome},
 };
 
+/// The IDs of the server-default rules that look for mentions in the body of a message.
+///
+/// They are ignored for events that have an `m.mentions` property, which is matched by the
+/// `.m.rule.is_user_mention` and `.m.rule.is_room_mention` rules instead.
+const LEGACY_MENTION_RULE_IDS: &[&str] =
+    &[".m.rule.contains_display_name", ".m.rule.contains_user_name", ".m.rule.roomnotif"];
+
+/// The path of the push gateway endpoint that HTTP pushers send notifications to.
+const PUSH_GATEWAY_NOTIFY_PATH: &str = "/_matrix/push/v1/notify";
+
 /// A push ruleset scopes a set of rules according to some criteria.
 ///
 /// For example, some rules may only be applied for messages from a particular sender, a particular
@@ -83,6 +103,167 @@ impl Ruleset {
         }
     }
 
+    /// Inserts a user-defined rule in the rule set, or replaces the rule of the same kind with the
+    /// same `rule_id`.
+    ///
+    /// This follows the semantics of the `PUT /pushrules/{scope}/{kind}/{ruleId}` endpoint of the
+    /// client-server API:
+    ///
+    /// * `before` and `after` are the rule IDs of user-defined rules of the same kind, the new rule
+    ///   becomes the next-most important rule before `before` and the next-less important rule
+    ///   after `after`.
+    /// * Without `before` and `after`, a new rule becomes the most important user-defined rule of
+    ///   its kind, and a replaced rule keeps its position.
+    /// * A replaced rule keeps its `enabled` state.
+    /// * The rule is marked as user-defined by setting its `default` field to `false`.
+    ///
+    /// Server-default rules have a lower priority than user-defined rules, except for the
+    /// `.m.rule.master` override rule, which always comes first.
+    pub fn insert(
+        &mut self,
+        rule: AnyPushRule,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> Result<(), InsertPushRuleError> {
+        let rule_id = rule.rule_id();
+        if rule_id.starts_with('.') {
+            return Err(InsertPushRuleError::ServerDefaultRuleId);
+        }
+        if rule_id.contains(&['/', '\\'][..]) {
+            return Err(InsertPushRuleError::InvalidRuleId);
+        }
+
+        match rule {
+            AnyPushRule::Override(r) => {
+                let position = leading_override_defaults(&self.override_);
+                insert_rule(&mut self.override_, r, position, before, after)
+            }
+            AnyPushRule::Content(r) => insert_rule(&mut self.content, r, 0, before, after),
+            AnyPushRule::Room(r) => insert_rule(&mut self.room, r, 0, before, after),
+            AnyPushRule::Sender(r) => insert_rule(&mut self.sender, r, 0, before, after),
+            AnyPushRule::Underride(r) => insert_rule(&mut self.underride, r, 0, before, after),
+        }
+    }
+
+    /// Get the rule of the given kind with the given `rule_id`.
+    pub fn get(&self, kind: RuleKind, rule_id: &str) -> Option<AnyPushRuleRef<'_>> {
+        match kind {
+            RuleKind::Override => self.override_.get(rule_id).map(AnyPushRuleRef::Override),
+            RuleKind::Content => self.content.get(rule_id).map(AnyPushRuleRef::Content),
+            RuleKind::Room => self.room.get(rule_id).map(AnyPushRuleRef::Room),
+            RuleKind::Sender => self.sender.get(rule_id).map(AnyPushRuleRef::Sender),
+            RuleKind::Underride => self.underride.get(rule_id).map(AnyPushRuleRef::Underride),
+            RuleKind::_Custom(_) => None,
+        }
+    }
+
+    /// Removes the user-defined rule of the given kind with the given `rule_id`.
+    ///
+    /// Server-default rules can't be removed, only disabled.
+    pub fn remove(&mut self, kind: RuleKind, rule_id: &str) -> Result<(), RemovePushRuleError> {
+        match kind {
+            RuleKind::Override => remove_rule(&mut self.override_, rule_id),
+            RuleKind::Content => remove_rule(&mut self.content, rule_id),
+            RuleKind::Room => remove_rule(&mut self.room, rule_id),
+            RuleKind::Sender => remove_rule(&mut self.sender, rule_id),
+            RuleKind::Underride => remove_rule(&mut self.underride, rule_id),
+            RuleKind::_Custom(_) => Err(RemovePushRuleError::NotFound),
+        }
+    }
+
+    /// Enables or disables the rule of the given kind with the given `rule_id`.
+    pub fn set_enabled(
+        &mut self,
+        kind: RuleKind,
+        rule_id: &str,
+        enabled: bool,
+    ) -> Result<(), RuleNotFoundError> {
+        self.update_rule(kind, rule_id, |rule| rule.set_enabled(enabled))
+    }
+
+    /// Sets the actions of the rule of the given kind with the given `rule_id`.
+    pub fn set_actions(
+        &mut self,
+        kind: RuleKind,
+        rule_id: &str,
+        actions: Vec<Action>,
+    ) -> Result<(), RuleNotFoundError> {
+        self.update_rule(kind, rule_id, |rule| rule.set_actions(actions))
+    }
+
+    /// Updates the server-default rules of this rule set with the given ones, typically
+    /// `Ruleset::server_default` after the server was upgraded.
+    ///
+    /// * Server-default rules that are new are added in the order of `new_defaults`.
+    /// * Server-default rules that already exist take the definition of the new ones, but keep
+    ///   their `enabled` state and their actions, which can be changed by the user.
+    /// * Server-default rules that are not in `new_defaults` are removed.
+    /// * User-defined rules are kept in the same order, with a higher priority than the
+    ///   server-default rules, except for the `.m.rule.master` override rule, which always comes
+    ///   first.
+    pub fn update_with_server_default(&mut self, new_defaults: Ruleset) {
+        let Ruleset { content, override_, room, sender, underride } = new_defaults;
+
+        let leading = leading_override_defaults(&override_);
+        update_with_server_default(&mut self.override_, override_, leading);
+        update_with_server_default(&mut self.content, content, 0);
+        update_with_server_default(&mut self.room, room, 0);
+        update_with_server_default(&mut self.sender, sender, 0);
+        update_with_server_default(&mut self.underride, underride, 0);
+    }
+
+    /// Iterates over all the rules, from the highest to the lowest priority.
+    fn rules_by_priority(&self) -> impl Iterator<Item = AnyPushRuleRef<'_>> {
+        self.override_
+            .iter()
+            .map(AnyPushRuleRef::Override)
+            .chain(self.content.iter().map(AnyPushRuleRef::Content))
+            .chain(self.room.iter().map(AnyPushRuleRef::Room))
+            .chain(self.sender.iter().map(AnyPushRuleRef::Sender))
+            .chain(self.underride.iter().map(AnyPushRuleRef::Underride))
+    }
+
+    fn update_rule(
+        &mut self,
+        kind: RuleKind,
+        rule_id: &str,
+        f: impl FnOnce(&mut dyn RuleFields),
+    ) -> Result<(), RuleNotFoundError> {
+        match kind {
+            RuleKind::Override => update_rule(&mut self.override_, rule_id, f),
+            RuleKind::Content => update_rule(&mut self.content, rule_id, f),
+            RuleKind::Room => update_rule(&mut self.room, rule_id, f),
+            RuleKind::Sender => update_rule(&mut self.sender, rule_id, f),
+            RuleKind::Underride => update_rule(&mut self.underride, rule_id, f),
+            RuleKind::_Custom(_) => Err(RuleNotFoundError),
+        }
+    }
+
+    /// Get the first push rule that applies to this event.
+    ///
+    /// Returns `None` if no push rule applies.
+    ///
+    /// If the event has an `m.mentions` property, the server-default rules that look for mentions
+    /// in the body of the message (`.m.rule.contains_display_name`, `.m.rule.contains_user_name`
+    /// and `.m.rule.roomnotif`) are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The raw JSON of a room message event.
+    /// * `context` - The context of the message and room at the time of the event.
+    pub fn get_match<T>(
+        &self,
+        event: &Raw<T>,
+        context: &PushConditionRoomCtx,
+    ) -> Option<AnyPushRuleRef<'_>>
+    where
+        T: Serialize,
+    {
+        let event_map = &FlattenedJson::from_raw(event);
+
+        self.rules_by_priority().find(|rule| rule.applies(event_map, context))
+    }
+
     /// Get the push actions that apply to this event.
     ///
     /// Returns an empty iterator if no push rule applies.
@@ -99,49 +280,275 @@ impl Ruleset {
     where
         T: Serialize,
     {
-        let event_map = &FlattenedJson::from_raw(event);
+        self.get_match(event, context).map_or(&[][..], AnyPushRuleRef::actions).iter()
+    }
 
-        for rule in self.override_.iter().filter(|r| r.enabled) {
-            if rule.applies(event_map, context) {
-                return rule.actions.iter();
+    /// Evaluate the push rules for this event, and interpret the actions of the first push rule
+    /// that applies.
+    ///
+    /// Returns `None` if no push rule applies, in which case the event doesn't generate a
+    /// notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The raw JSON of a room message event.
+    /// * `context` - The context of the message and room at the time of the event.
+    pub fn evaluate<T>(
+        &self,
+        event: &Raw<T>,
+        context: &PushConditionRoomCtx,
+    ) -> Option<NotificationOutcome>
+    where
+        T: Serialize,
+    {
+        self.get_match(event, context).map(|rule| {
+            NotificationOutcome::from_actions(
+                rule.actions(),
+                (rule.kind(), rule.rule_id().to_owned()),
+            )
+        })
+    }
+
+    /// Evaluate the push rules for an encrypted event, and again for the same event after it was
+    /// decrypted.
+    ///
+    /// Servers can only evaluate the push rules for the encrypted event, which usually matches the
+    /// `.m.rule.encrypted` or `.m.rule.encrypted_room_one_to_one` rules. Clients can use this
+    /// method after decrypting the event to know how it should actually notify the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_event` - The raw JSON of the `m.room.encrypted` event.
+    /// * `decrypted_event` - The raw JSON of the decrypted event. It should also contain the fields
+    ///   of the encrypted event that are not part of the encrypted payload, like `sender` and
+    ///   `room_id`.
+    /// * `context` - The context of the message and room at the time of the event.
+    pub fn evaluate_decrypted<T, U>(
+        &self,
+        encrypted_event: &Raw<T>,
+        decrypted_event: &Raw<U>,
+        context: &PushConditionRoomCtx,
+    ) -> DecryptedEventOutcome
+    where
+        T: Serialize,
+        U: Serialize,
+    {
+        let encrypted_map = &FlattenedJson::from_raw(encrypted_event);
+        let decrypted_map = &FlattenedJson::from_raw(decrypted_event);
+
+        let mut encrypted = None;
+        let mut decrypted = None;
+        let mut changed_rules = Vec::new();
+
+        for rule in self.rules_by_priority() {
+            let applies_to_encrypted = rule.applies(encrypted_map, context);
+            let applies_to_decrypted = rule.applies(decrypted_map, context);
+
+            if applies_to_encrypted && encrypted.is_none() {
+                encrypted = Some(rule);
+            }
+            if applies_to_decrypted && decrypted.is_none() {
+                decrypted = Some(rule);
+            }
+            if applies_to_encrypted != applies_to_decrypted {
+                changed_rules.push((rule.kind(), rule.rule_id().to_owned()));
             }
         }
-        for rule in self.content.iter().filter(|r| r.enabled) {
-            let condition = PushCondition::EventMatch {
-                key: "content.body".into(),
-                pattern: rule.pattern.clone(),
-            };
 
-            if condition.applies(event_map, context) {
-                return rule.actions.iter();
-            }
+        let outcome = |rule: AnyPushRuleRef<'_>| {
+            NotificationOutcome::from_actions(
+                rule.actions(),
+                (rule.kind(), rule.rule_id().to_owned()),
+            )
+        };
+
+        DecryptedEventOutcome {
+            encrypted: encrypted.map(outcome),
+            decrypted: decrypted.map(outcome),
+            changed_rules,
         }
-        for rule in self.room.iter().filter(|r| r.enabled) {
-            let condition =
-                PushCondition::EventMatch { key: "room_id".into(), pattern: rule.rule_id.clone() };
+    }
+}
+
+/// Fields shared by all kinds of push rules, used to edit a `Ruleset`.
+trait RuleFields {
+    fn rule_id(&self) -> &str;
+    fn is_default(&self) -> bool;
+    fn is_enabled(&self) -> bool;
+    fn actions(&self) -> &[Action];
+    fn set_default(&mut self, default: bool);
+    fn set_enabled(&mut self, enabled: bool);
+    fn set_actions(&mut self, actions: Vec<Action>);
+}
+
+macro_rules! impl_rule_fields {
+    ($($ty:ty),*) => {
+        $(
+            impl RuleFields for $ty {
+                fn rule_id(&self) -> &str {
+                    &self.rule_id
+                }
+
+                fn is_default(&self) -> bool {
+                    self.default
+                }
+
+                fn is_enabled(&self) -> bool {
+                    self.enabled
+                }
+
+                fn actions(&self) -> &[Action] {
+                    &self.actions
+                }
+
+                fn set_default(&mut self, default: bool) {
+                    self.default = default;
+                }
 
-            if condition.applies(event_map, context) {
-                return rule.actions.iter();
+                fn set_enabled(&mut self, enabled: bool) {
+                    self.enabled = enabled;
+                }
+
+                fn set_actions(&mut self, actions: Vec<Action>) {
+                    self.actions = actions;
+                }
             }
+        )*
+    };
+}
+
+impl_rule_fields!(SimplePushRule, ConditionalPushRule, PatternedPushRule);
+
+/// Inserts or replaces a user-defined rule in the given set, see `Ruleset::insert`.
+///
+/// `default_position` is the index of a new rule that is not positioned with `before` or `after`.
+fn insert_rule<T>(
+    set: &mut IndexSet<T>,
+    mut rule: T,
+    default_position: usize,
+    before: Option<&str>,
+    after: Option<&str>,
+) -> Result<(), InsertPushRuleError>
+where
+    T: RuleFields + Hash + Eq,
+    str: Equivalent<T>,
+{
+    let index_of = |rule_id: &str| match set.get_full(rule_id) {
+        Some((_, r)) if r.is_default() => Err(InsertPushRuleError::RelativeToServerDefaultRule),
+        Some((index, _)) => Ok(index),
+        None => Err(InsertPushRuleError::UnknownRuleId),
+    };
+    let before = before.map(index_of).transpose()?;
+    let after = after.map(index_of).transpose()?;
+
+    rule.set_default(false);
+    let existing = set.get_index_of(rule.rule_id());
+    if let Some(index) = existing {
+        rule.set_enabled(set[index].is_enabled());
+    }
+
+    let mut position = match (before, after) {
+        (Some(before), Some(after)) if before <= after => {
+            return Err(InsertPushRuleError::BeforeHigherThanAfter);
+        }
+        (Some(before), _) => before,
+        (None, Some(after)) => after + 1,
+        (None, None) if existing.is_some() => {
+            set.replace(rule);
+            return Ok(());
         }
-        for rule in self.sender.iter().filter(|r| r.enabled) {
-            let condition =
-                PushCondition::EventMatch { key: "sender".into(), pattern: rule.rule_id.clone() };
+        (None, None) => default_position.min(set.len()),
+    };
 
-            if condition.applies(event_map, context) {
-                return rule.actions.iter();
-            }
+    // `IndexSet` can't move its items, so the set is rebuilt. Push rule sets are small.
+    let mut rules: Vec<_> = mem::take(set).into_iter().collect();
+    if let Some(index) = existing {
+        rules.remove(index);
+        if index < position {
+            position -= 1;
         }
-        for rule in self.underride.iter().filter(|r| r.enabled) {
-            if rule.applies(event_map, context) {
-                return rule.actions.iter();
+    }
+    rules.insert(position, rule);
+    *set = rules.into_iter().collect();
+
+    Ok(())
+}
+
+/// The number of server-default rules at the start of the given override rules, that have a
+/// higher priority than user-defined rules.
+fn leading_override_defaults(set: &IndexSet<ConditionalPushRule>) -> usize {
+    match set.get_index(0) {
+        Some(first) if first.rule_id == ".m.rule.master" => 1,
+        _ => 0,
+    }
+}
+
+/// Replaces the server-default rules in the given set, see `Ruleset::update_with_server_default`.
+///
+/// `leading_defaults` is the number of rules of `new_defaults` that come before the user-defined
+/// rules.
+fn update_with_server_default<T>(
+    set: &mut IndexSet<T>,
+    new_defaults: IndexSet<T>,
+    leading_defaults: usize,
+) where
+    T: RuleFields + Hash + Eq,
+    str: Equivalent<T>,
+{
+    let old_rules = mem::take(set);
+
+    let mut new_defaults: Vec<_> = new_defaults
+        .into_iter()
+        .map(|mut rule| {
+            if let Some(old_rule) = old_rules.get(rule.rule_id()) {
+                rule.set_enabled(old_rule.is_enabled());
+                rule.set_actions(old_rule.actions().to_vec());
             }
-        }
+            rule
+        })
+        .collect();
+    let user_rules: Vec<_> = old_rules
+        .into_iter()
+        .filter(|rule| {
+            !rule.is_default() && !new_defaults.iter().any(|d| d.rule_id() == rule.rule_id())
+        })
+        .collect();
+    let trailing_defaults = new_defaults.split_off(leading_defaults.min(new_defaults.len()));
+
+    *set = new_defaults.into_iter().chain(user_rules).chain(trailing_defaults).collect();
+}
 
-        [].iter()
+fn remove_rule<T>(set: &mut IndexSet<T>, rule_id: &str) -> Result<(), RemovePushRuleError>
+where
+    T: RuleFields + Hash + Eq,
+    str: Equivalent<T>,
+{
+    match set.get(rule_id) {
+        Some(rule) if rule.is_default() => Err(RemovePushRuleError::ServerDefault),
+        Some(_) => {
+            set.shift_remove(rule_id);
+            Ok(())
+        }
+        None => Err(RemovePushRuleError::NotFound),
     }
 }
 
+fn update_rule<T>(
+    set: &mut IndexSet<T>,
+    rule_id: &str,
+    f: impl FnOnce(&mut dyn RuleFields),
+) -> Result<(), RuleNotFoundError>
+where
+    T: RuleFields + Clone + Hash + Eq,
+    str: Equivalent<T>,
+{
+    let mut rule = set.get(rule_id).ok_or(RuleNotFoundError)?.clone();
+    f(&mut rule);
+    set.replace(rule);
+
+    Ok(())
+}
+
 /// Iterator type for `Ruleset`
 #[derive(Debug)]
 pub struct RulesetIter {
@@ -211,6 +618,139 @@ impl AnyPushRule {
             Self::Sender(rule) => &rule.rule_id,
         }
     }
+
+    /// Get the kind of the push rule.
+    pub fn kind(&self) -> RuleKind {
+        match self {
+            Self::Override(_) => RuleKind::Override,
+            Self::Underride(_) => RuleKind::Underride,
+            Self::Content(_) => RuleKind::Content,
+            Self::Room(_) => RuleKind::Room,
+            Self::Sender(_) => RuleKind::Sender,
+        }
+    }
+}
+
+/// A reference to a push rule of any kind.
+#[derive(Clone, Copy, Debug)]
+pub enum AnyPushRuleRef<'a> {
+    /// Rules that override all other kinds.
+    Override(&'a ConditionalPushRule),
+
+    /// Content-specific rules.
+    Content(&'a PatternedPushRule),
+
+    /// Room-specific rules.
+    Room(&'a SimplePushRule),
+
+    /// Sender-specific rules.
+    Sender(&'a SimplePushRule),
+
+    /// Lowest priority rules.
+    Underride(&'a ConditionalPushRule),
+}
+
+impl<'a> AnyPushRuleRef<'a> {
+    /// Convert to an owned `AnyPushRule` by cloning the push rule.
+    pub fn to_owned(self) -> AnyPushRule {
+        match self {
+            Self::Override(rule) => AnyPushRule::Override(rule.clone()),
+            Self::Content(rule) => AnyPushRule::Content(rule.clone()),
+            Self::Room(rule) => AnyPushRule::Room(rule.clone()),
+            Self::Sender(rule) => AnyPushRule::Sender(rule.clone()),
+            Self::Underride(rule) => AnyPushRule::Underride(rule.clone()),
+        }
+    }
+
+    fn fields(self) -> &'a dyn RuleFields {
+        match self {
+            Self::Override(rule) | Self::Underride(rule) => rule,
+            Self::Content(rule) => rule,
+            Self::Room(rule) | Self::Sender(rule) => rule,
+        }
+    }
+
+    /// Get the `rule_id` of the push rule.
+    pub fn rule_id(self) -> &'a str {
+        self.fields().rule_id()
+    }
+
+    /// Whether the push rule is enabled and applies to the event.
+    ///
+    /// The server-default rules that look for mentions in the body of the message never apply
+    /// to events that have an `m.mentions` property.
+    fn applies(self, event: &FlattenedJson, context: &PushConditionRoomCtx) -> bool {
+        if !self.is_enabled()
+            || (event.contains_mentions() && LEGACY_MENTION_RULE_IDS.contains(&self.rule_id()))
+        {
+            return false;
+        }
+
+        let condition = match self {
+            Self::Override(rule) | Self::Underride(rule) => return rule.applies(event, context),
+            Self::Content(rule) => PushCondition::EventMatch {
+                key: "content.body".into(),
+                pattern: rule.pattern.clone(),
+            },
+            Self::Room(rule) => {
+                PushCondition::EventMatch { key: "room_id".into(), pattern: rule.rule_id.clone() }
+            }
+            Self::Sender(rule) => {
+                PushCondition::EventMatch { key: "sender".into(), pattern: rule.rule_id.clone() }
+            }
+        };
+
+        condition.applies(event, context)
+    }
+
+    /// Get the kind of the push rule.
+    pub fn kind(self) -> RuleKind {
+        match self {
+            Self::Override(_) => RuleKind::Override,
+            Self::Underride(_) => RuleKind::Underride,
+            Self::Content(_) => RuleKind::Content,
+            Self::Room(_) => RuleKind::Room,
+            Self::Sender(_) => RuleKind::Sender,
+        }
+    }
+
+    /// Whether the push rule is a server-default rule.
+    pub fn is_default(self) -> bool {
+        self.fields().is_default()
+    }
+
+    /// Whether the push rule is enabled.
+    pub fn is_enabled(self) -> bool {
+        self.fields().is_enabled()
+    }
+
+    /// Get the actions of the push rule.
+    pub fn actions(self) -> &'a [Action] {
+        self.fields().actions()
+    }
+}
+
+/// The kinds of push rules that are available.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
+#[ruma_enum(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// User-configured rules that override all other kinds.
+    Override,
+
+    /// Lowest priority user-defined rules.
+    Underride,
+
+    /// Sender-specific rules.
+    Sender,
+
+    /// Room-specific rules.
+    Room,
+
+    /// Content-specific rules.
+    Content,
+
+    #[doc(hidden)]
+    _Custom(String),
 }
 
 impl Extend<AnyPushRule> for Ruleset {
@@ -487,6 +1027,31 @@ impl PusherData {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Checks the `url` of an HTTP pusher.
+    ///
+    /// As required by the specification, it must be an `https` URL whose path ends in
+    /// `/_matrix/push/v1/notify`, without a query or a fragment.
+    ///
+    /// Returns the URL without this path, which is the base URL of the push gateway to send
+    /// `ruma_push_gateway_api::send_event_notification::v1` requests to.
+    pub fn validate_url(&self) -> Result<&str, PusherUrlError> {
+        let url = self.url.as_deref().ok_or(PusherUrlError::Missing)?;
+        let rest = url.strip_prefix("https://").ok_or(PusherUrlError::UnsupportedScheme)?;
+
+        // The host ends at the start of the path, the query or the fragment.
+        let path_start = rest.find(&['/', '?', '#'][..]).unwrap_or(rest.len());
+        if path_start == 0 {
+            return Err(PusherUrlError::MissingHost);
+        }
+
+        let path = &rest[path_start..];
+        if path.contains(&['?', '#'][..]) || !path.ends_with(PUSH_GATEWAY_NOTIFY_PATH) {
+            return Err(PusherUrlError::InvalidPath);
+        }
+
+        Ok(&url[..url.len() - PUSH_GATEWAY_NOTIFY_PATH.len()])
+    }
 }
 
 /// A special format that the homeserver should use when sending notifications to a Push Gateway.
@@ -521,7 +1086,8 @@ mod tests {
     use super::{
         action::{Action, Tweak},
         condition::{PushCondition, PushConditionRoomCtx, RoomMemberCountIs},
-        AnyPushRule, ConditionalPushRule, PatternedPushRule, Ruleset, SimplePushRule,
+        AnyPushRule, ConditionalPushRule, InsertPushRuleError, PatternedPushRule, PusherData,
+        PusherUrlError, RemovePushRuleError, RuleKind, RuleNotFoundError, Ruleset, SimplePushRule,
     };
 
     fn example_ruleset() -> Ruleset {
@@ -583,6 +1149,246 @@ mod tests {
         assert!(rule.is_none());
     }
 
+    fn user_override_rule(rule_id: &str) -> AnyPushRule {
+        AnyPushRule::Override(ConditionalPushRule {
+            conditions: vec![],
+            actions: vec![Action::DontNotify],
+            rule_id: rule_id.into(),
+            enabled: true,
+            default: false,
+        })
+    }
+
+    fn override_rule_ids(set: &Ruleset) -> Vec<&str> {
+        set.override_.iter().map(|rule| rule.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn insert_new_rule() {
+        let mut set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        let default_len = set.override_.len();
+
+        set.insert(user_override_rule("first"), None, None).unwrap();
+        set.insert(user_override_rule("second"), None, None).unwrap();
+
+        assert_eq!(set.override_.len(), default_len + 2);
+        assert_eq!(override_rule_ids(&set)[..3], [".m.rule.master", "second", "first"]);
+
+        set.insert(
+            AnyPushRule::Room(SimplePushRule {
+                actions: vec![Action::Notify],
+                default: true,
+                enabled: false,
+                rule_id: "!dummy:server.name".into(),
+            }),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let rule = set.get(RuleKind::Room, "!dummy:server.name").unwrap();
+        assert_eq!(rule.kind(), RuleKind::Room);
+        assert!(!rule.is_default());
+        assert!(!rule.is_enabled());
+        assert_eq!(set.room.get_index(0).unwrap().rule_id, "!dummy:server.name");
+    }
+
+    #[test]
+    fn insert_new_rule_in_empty_set() {
+        let mut set = Ruleset::new();
+
+        set.insert(user_override_rule("rule"), None, None).unwrap();
+
+        assert_eq!(override_rule_ids(&set), ["rule"]);
+    }
+
+    #[test]
+    fn insert_rule_before_and_after() {
+        let mut set = Ruleset::new();
+        set.insert(user_override_rule("c"), None, None).unwrap();
+        set.insert(user_override_rule("a"), None, None).unwrap();
+
+        set.insert(user_override_rule("b"), None, Some("a")).unwrap();
+        assert_eq!(override_rule_ids(&set), ["a", "b", "c"]);
+
+        set.insert(user_override_rule("d"), None, Some("c")).unwrap();
+        assert_eq!(override_rule_ids(&set), ["a", "b", "c", "d"]);
+
+        set.insert(user_override_rule("e"), Some("b"), Some("a")).unwrap();
+        assert_eq!(override_rule_ids(&set), ["a", "e", "b", "c", "d"]);
+
+        // Move existing rules.
+        set.insert(user_override_rule("a"), Some("d"), None).unwrap();
+        assert_eq!(override_rule_ids(&set), ["e", "b", "c", "a", "d"]);
+
+        set.insert(user_override_rule("d"), None, Some("e")).unwrap();
+        assert_eq!(override_rule_ids(&set), ["e", "d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn insert_rule_errors() {
+        let mut set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        set.insert(user_override_rule("a"), None, None).unwrap();
+        set.insert(user_override_rule("b"), None, Some("a")).unwrap();
+
+        assert_eq!(
+            set.insert(user_override_rule(".m.rule.mine"), None, None),
+            Err(InsertPushRuleError::ServerDefaultRuleId)
+        );
+        assert_eq!(
+            set.insert(user_override_rule("my/rule"), None, None),
+            Err(InsertPushRuleError::InvalidRuleId)
+        );
+        assert_eq!(
+            set.insert(user_override_rule("c"), Some("unknown"), None),
+            Err(InsertPushRuleError::UnknownRuleId)
+        );
+        assert_eq!(
+            set.insert(user_override_rule("c"), None, Some(".m.rule.master")),
+            Err(InsertPushRuleError::RelativeToServerDefaultRule)
+        );
+        assert_eq!(
+            set.insert(user_override_rule("c"), Some("a"), Some("b")),
+            Err(InsertPushRuleError::BeforeHigherThanAfter)
+        );
+        assert!(set.get(RuleKind::Override, "c").is_none());
+    }
+
+    #[test]
+    fn replace_rule() {
+        let mut set = Ruleset::new();
+        set.insert(user_override_rule("a"), None, None).unwrap();
+        set.insert(user_override_rule("b"), None, Some("a")).unwrap();
+        set.set_enabled(RuleKind::Override, "a", false).unwrap();
+
+        let mut rule = user_override_rule("a");
+        if let AnyPushRule::Override(rule) = &mut rule {
+            rule.actions = vec![Action::Notify];
+        }
+        set.insert(rule, None, None).unwrap();
+
+        assert_eq!(override_rule_ids(&set), ["a", "b"]);
+        let rule = set.get(RuleKind::Override, "a").unwrap();
+        assert!(!rule.is_enabled());
+        assert_matches!(rule.actions(), [Action::Notify]);
+    }
+
+    #[test]
+    fn remove_rule() {
+        let mut set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        set.insert(user_override_rule("mine"), None, None).unwrap();
+
+        assert_eq!(
+            set.remove(RuleKind::Override, ".m.rule.master"),
+            Err(RemovePushRuleError::ServerDefault)
+        );
+        assert_eq!(set.remove(RuleKind::Content, "mine"), Err(RemovePushRuleError::NotFound));
+
+        set.remove(RuleKind::Override, "mine").unwrap();
+        assert!(set.get(RuleKind::Override, "mine").is_none());
+        assert_eq!(override_rule_ids(&set)[0], ".m.rule.master");
+    }
+
+    #[test]
+    fn update_server_default_rule() {
+        let mut set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+
+        set.set_enabled(RuleKind::Override, ".m.rule.master", true).unwrap();
+        set.set_actions(RuleKind::Underride, ".m.rule.message", vec![Action::DontNotify]).unwrap();
+
+        assert!(set.get(RuleKind::Override, ".m.rule.master").unwrap().is_enabled());
+        let rule = set.get(RuleKind::Underride, ".m.rule.message").unwrap();
+        assert_matches!(rule.actions(), [Action::DontNotify]);
+        assert!(rule.is_default());
+        assert_eq!(set.underride.get_index_of(".m.rule.message"), Some(3));
+
+        assert_eq!(
+            set.set_enabled(RuleKind::Sender, "@nobody:server.name", false),
+            Err(RuleNotFoundError)
+        );
+    }
+
+    #[test]
+    fn update_with_server_default() {
+        let user_id = user_id!("@jolly_jumper:server.name");
+
+        // A rule set stored with older server-default rules.
+        let mut set = Ruleset::server_default(&user_id);
+        set.override_.shift_remove(".m.rule.is_user_mention");
+        set.override_.shift_remove(".m.rule.is_room_mention");
+        set.override_.replace(ConditionalPushRule {
+            conditions: vec![],
+            ..set.override_.get(".m.rule.tombstone").unwrap().clone()
+        });
+        set.add(AnyPushRule::Underride(ConditionalPushRule {
+            conditions: vec![],
+            actions: vec![Action::Notify],
+            rule_id: ".m.rule.obsolete".into(),
+            enabled: true,
+            default: true,
+        }));
+
+        set.insert(user_override_rule("mine"), None, None).unwrap();
+        set.set_enabled(RuleKind::Override, ".m.rule.contains_display_name", false).unwrap();
+        set.set_actions(RuleKind::Override, ".m.rule.roomnotif", vec![Action::DontNotify]).unwrap();
+
+        set.update_with_server_default(Ruleset::server_default(&user_id));
+
+        let mut expected_ids = override_rule_ids(&Ruleset::server_default(&user_id))
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        expected_ids.insert(1, "mine".to_owned());
+        assert_eq!(override_rule_ids(&set), expected_ids);
+
+        let tombstone = set.override_.get(".m.rule.tombstone").unwrap();
+        assert_eq!(tombstone.conditions.len(), 2);
+        assert!(!set
+            .get(RuleKind::Override, ".m.rule.contains_display_name")
+            .unwrap()
+            .is_enabled());
+        assert_matches!(
+            set.get(RuleKind::Override, ".m.rule.roomnotif").unwrap().actions(),
+            [Action::DontNotify]
+        );
+        assert!(set.get(RuleKind::Override, "mine").unwrap().is_enabled());
+        assert!(set.get(RuleKind::Underride, ".m.rule.obsolete").is_none());
+        assert_eq!(set.underride.len(), Ruleset::server_default(&user_id).underride.len());
+    }
+
+    #[test]
+    fn update_with_server_default_keeps_actions() {
+        let user_id = user_id!("@jolly_jumper:server.name");
+
+        // A rule set stored with an older definition of `.m.rule.encrypted`, whose actions were
+        // changed by the user.
+        let mut set = Ruleset::server_default(&user_id);
+        set.underride.replace(ConditionalPushRule {
+            conditions: vec![],
+            ..set.underride.get(".m.rule.encrypted").unwrap().clone()
+        });
+        set.set_actions(
+            RuleKind::Underride,
+            ".m.rule.encrypted",
+            vec![Action::Notify, Action::SetTweak(Tweak::Sound("default".into()))],
+        )
+        .unwrap();
+        set.set_actions(RuleKind::Underride, ".m.rule.message", vec![Action::DontNotify]).unwrap();
+
+        set.update_with_server_default(Ruleset::server_default(&user_id));
+
+        let encrypted = set.underride.get(".m.rule.encrypted").unwrap();
+        assert_eq!(encrypted.conditions.len(), 1);
+        assert_matches!(
+            encrypted.actions.as_slice(),
+            [Action::Notify, Action::SetTweak(Tweak::Sound(sound))] if sound == "default"
+        );
+        assert_matches!(
+            set.get(RuleKind::Underride, ".m.rule.message").unwrap().actions(),
+            [Action::DontNotify]
+        );
+    }
+
     #[test]
     fn iter() {
         let mut set = example_ruleset();
@@ -1015,6 +1821,8 @@ mod tests {
             users_power_levels: BTreeMap::new(),
             default_power_level: 50.into(),
             notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
         };
 
         let context_public_room = &PushConditionRoomCtx {
@@ -1024,6 +1832,8 @@ mod tests {
             users_power_levels: BTreeMap::new(),
             default_power_level: 50.into(),
             notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
         };
 
         let message = serde_json::from_str::<Raw<JsonValue>>(
@@ -1095,6 +1905,221 @@ mod tests {
         assert_matches!(actions.next(), None);
     }
 
+    #[test]
+    fn default_ruleset_evaluate() {
+        let set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+
+        let context = &PushConditionRoomCtx {
+            room_id: room_id!("!dm:server.name"),
+            member_count: 2u32.into(),
+            user_display_name: "Jolly Jumper".into(),
+            users_power_levels: BTreeMap::new(),
+            default_power_level: 50.into(),
+            notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
+        };
+
+        let message = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "content": {
+                    "body": "Hello!"
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&message, context).unwrap();
+        assert!(outcome.notify);
+        assert!(!outcome.highlight);
+        assert_eq!(outcome.sound.as_deref(), Some("default"));
+        assert_eq!(outcome.matched_rule, (RuleKind::Underride, ".m.rule.room_one_to_one".into()));
+
+        let user_name = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "content": {
+                    "body": "Hi jolly_jumper!"
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&user_name, context).unwrap();
+        assert!(outcome.notify);
+        assert!(outcome.highlight);
+        assert_eq!(outcome.matched_rule, (RuleKind::Content, ".m.rule.contains_user_name".into()));
+
+        let notice = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "content": {
+                    "msgtype": "m.notice"
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&notice, context).unwrap();
+        assert!(!outcome.notify);
+        assert_eq!(outcome.matched_rule, (RuleKind::Override, ".m.rule.suppress_notices".into()));
+
+        let empty = serde_json::from_str::<Raw<JsonValue>>(r#"{}"#).unwrap();
+        assert_eq!(set.evaluate(&empty, context), None);
+    }
+
+    #[test]
+    fn default_ruleset_evaluate_decrypted() {
+        let set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+        let context = &PushConditionRoomCtx::new(
+            room_id!("!far_west:server.name"),
+            100u32.into(),
+            "Jolly Jumper".into(),
+        );
+
+        let encrypted = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.encrypted",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "algorithm": "m.megolm.v1.aes-sha2",
+                    "ciphertext": "AwgAEnACgAkLmt6qF84IK++J7UDH2Za1YVchHyprqTqsg..."
+                }
+            }"#,
+        )
+        .unwrap();
+        let decrypted = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "msgtype": "m.text",
+                    "body": "Hi Jolly Jumper!"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let outcome = set.evaluate_decrypted(&encrypted, &decrypted, context);
+        assert_eq!(
+            outcome.encrypted.as_ref().unwrap().matched_rule,
+            (RuleKind::Underride, ".m.rule.encrypted".into())
+        );
+        assert_eq!(
+            outcome.decrypted.as_ref().unwrap().matched_rule,
+            (RuleKind::Override, ".m.rule.contains_display_name".into())
+        );
+        assert!(outcome.notify());
+        assert!(outcome.highlight());
+        assert!(outcome.outcome_changed());
+        assert_eq!(
+            outcome.changed_rules,
+            [
+                (RuleKind::Override, ".m.rule.contains_display_name".to_owned()),
+                (RuleKind::Underride, ".m.rule.message".to_owned()),
+                (RuleKind::Underride, ".m.rule.encrypted".to_owned()),
+            ]
+        );
+
+        let notice = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "msgtype": "m.notice",
+                    "body": "Hi Jolly Jumper!"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let outcome = set.evaluate_decrypted(&encrypted, &notice, context);
+        assert!(!outcome.notify());
+        assert!(!outcome.highlight());
+        assert!(outcome.outcome_changed());
+    }
+
+    #[test]
+    fn default_ruleset_intentional_mentions() {
+        let set = Ruleset::server_default(&user_id!("@jolly_jumper:server.name"));
+
+        let context = &PushConditionRoomCtx {
+            room_id: room_id!("!far_west:server.name"),
+            member_count: 100u32.into(),
+            user_display_name: "Jolly Jumper".into(),
+            users_power_levels: BTreeMap::new(),
+            default_power_level: 50.into(),
+            notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
+        };
+
+        let user_mention = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "body": "Hello!",
+                    "m.mentions": {
+                        "user_ids": ["@jolly_jumper:server.name"]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&user_mention, context).unwrap();
+        assert!(outcome.highlight);
+        assert_eq!(outcome.matched_rule, (RuleKind::Override, ".m.rule.is_user_mention".into()));
+
+        let room_mention = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "body": "Attention please!",
+                    "m.mentions": {
+                        "room": true
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&room_mention, context).unwrap();
+        assert!(outcome.highlight);
+        assert_eq!(outcome.matched_rule, (RuleKind::Override, ".m.rule.is_room_mention".into()));
+
+        // The legacy rules are ignored when the event has `m.mentions`, even if it is empty.
+        let no_mention = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "body": "@room Jolly Jumper, jolly_jumper!",
+                    "m.mentions": {}
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&no_mention, context).unwrap();
+        assert!(!outcome.highlight);
+        assert_eq!(outcome.matched_rule, (RuleKind::Underride, ".m.rule.message".into()));
+
+        let legacy_mention = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "body": "Hi Jolly Jumper!"
+                }
+            }"#,
+        )
+        .unwrap();
+        let outcome = set.evaluate(&legacy_mention, context).unwrap();
+        assert!(outcome.highlight);
+        assert_eq!(
+            outcome.matched_rule,
+            (RuleKind::Override, ".m.rule.contains_display_name".into())
+        );
+    }
+
     #[test]
     fn custom_ruleset_applies() {
         let context_one_to_one = &PushConditionRoomCtx {
@@ -1104,6 +2129,8 @@ mod tests {
             users_power_levels: BTreeMap::new(),
             default_power_level: 50.into(),
             notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
         };
 
         let message = serde_json::from_str::<Raw<JsonValue>>(
@@ -1218,4 +2245,52 @@ mod tests {
         let mut actions = set.get_actions(&new_message, context_one_to_one);
         assert_matches!(actions.next(), Some(Action::SetTweak(Tweak::Sound(sound))) if sound == "three");
     }
+
+    #[test]
+    fn validate_pusher_url() {
+        let validate = |url: Option<&str>| {
+            let mut data = PusherData::new();
+            data.url = url.map(ToOwned::to_owned);
+            data.validate_url().map(ToOwned::to_owned)
+        };
+
+        assert_eq!(
+            validate(Some("https://push.example.com/_matrix/push/v1/notify")).as_deref(),
+            Ok("https://push.example.com")
+        );
+        assert_eq!(
+            validate(Some("https://localhost:5000/gateway/_matrix/push/v1/notify")).as_deref(),
+            Ok("https://localhost:5000/gateway")
+        );
+        assert_eq!(
+            validate(Some("http://push.example.com/_matrix/push/v1/notify")),
+            Err(PusherUrlError::UnsupportedScheme)
+        );
+        assert_eq!(validate(None), Err(PusherUrlError::Missing));
+        assert_eq!(
+            validate(Some("ftp://push.example.com/_matrix/push/v1/notify")),
+            Err(PusherUrlError::UnsupportedScheme)
+        );
+        assert_eq!(
+            validate(Some("https:///_matrix/push/v1/notify")),
+            Err(PusherUrlError::MissingHost)
+        );
+        assert_eq!(
+            validate(Some("https://push.example.com/_matrix/push/v1/notify/")),
+            Err(PusherUrlError::InvalidPath)
+        );
+        assert_eq!(validate(Some("https://push.example.com")), Err(PusherUrlError::InvalidPath));
+        assert_eq!(
+            validate(Some("https://push.example.com?x=/_matrix/push/v1/notify")),
+            Err(PusherUrlError::InvalidPath)
+        );
+        assert_eq!(
+            validate(Some("https://push.example.com/gateway#/_matrix/push/v1/notify")),
+            Err(PusherUrlError::InvalidPath)
+        );
+        assert_eq!(
+            validate(Some("https://?x=/_matrix/push/v1/notify")),
+            Err(PusherUrlError::MissingHost)
+        );
+    }
 }
@@ -1,33 +1,44 @@
-use std::{collections::BTreeMap, convert::TryFrom, ops::RangeBounds, str::FromStr};
+use std::{collections::BTreeMap, convert::TryFrom, ops::RangeBounds};
 
 use js_int::{Int, UInt};
 use ruma_identifiers::{RoomId, UserId};
-use ruma_serde::Raw;
 use serde::{Deserialize, Serialize};
-use serde_json::{to_value as to_json_value, value::Value as JsonValue};
-use tracing::warn;
-use wildmatch::WildMatch;
+use serde_json::value::Value as JsonValue;
 
 use crate::power_levels::NotificationPowerLevels;
 
+mod flattened_json;
+mod glob;
+mod push_condition_serde;
 mod room_member_count_is;
 
-pub use room_member_count_is::{ComparisonOperator, RoomMemberCountIs};
+pub use self::{
+    flattened_json::{FlattenedJson, FlattenedJsonValue, ScalarJsonValue},
+    glob::GlobMatcher,
+    room_member_count_is::{ComparisonOperator, RoomMemberCountIs},
+};
 
 /// A condition that must apply for an associated push rule's action to be taken.
+///
+/// Conditions of unknown kinds are kept when deserializing, and never apply.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[serde(tag = "kind", rename_all = "snake_case")]
+#[serde(remote = "Self", tag = "kind", rename_all = "snake_case")]
 pub enum PushCondition {
     /// This is a glob pattern match on a field of the event.
     EventMatch {
-        /// The dot-separated field of the event to match.
+        /// The dot-separated path of the field of the event to match.
+        ///
+        /// See `FlattenedJson` for the escaping of dots in the names of fields.
         key: String,
 
         /// The glob-style pattern to match against.
         ///
         /// Patterns with no special glob characters should be treated as having asterisks
         /// prepended and appended when testing the condition.
+        ///
+        /// See `GlobMatcher` for the supported syntax. Patterns are compiled the first time they
+        /// are used on a thread, and cached.
         pattern: String,
     },
 
@@ -50,6 +61,76 @@ pub enum PushCondition {
         /// `content`.
         key: String,
     },
+
+    /// This is an exact value match on a field of the event.
+    EventPropertyIs {
+        /// The dot-separated path of the field of the event to match.
+        ///
+        /// See `FlattenedJson` for the escaping of dots in the names of fields.
+        key: String,
+
+        /// The value to match against.
+        value: ScalarJsonValue,
+    },
+
+    /// This matches events where an array field contains the given value.
+    EventPropertyContains {
+        /// The dot-separated path of the array field of the event to match.
+        ///
+        /// See `FlattenedJson` for the escaping of dots in the names of fields.
+        key: String,
+
+        /// The value to look for in the array.
+        value: ScalarJsonValue,
+    },
+
+    /// This is a glob pattern match on a field of an event related to the event, like the event
+    /// that it replies to.
+    ///
+    /// The related events are given by `PushConditionRoomCtx::related_events`.
+    ///
+    /// This is not yet part of the specification, see
+    /// [MSC3664](https://github.com/matrix-org/matrix-doc/pull/3664).
+    #[cfg(feature = "unstable-pre-spec")]
+    #[serde(rename = "im.nheko.msc3664.related_event_match", alias = "related_event_match")]
+    RelatedEventMatch {
+        /// The dot-separated path of the field of the related event to match.
+        ///
+        /// If this and `pattern` are missing, the condition applies to all events that have a
+        /// related event of the given type.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        key: Option<String>,
+
+        /// The glob-style pattern to match against.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+
+        /// The type of relation of the related event, `m.in_reply_to` for replies.
+        rel_type: String,
+
+        /// Whether to also match related events that are only a fallback, like the reply
+        /// fallback of threads.
+        ///
+        /// Defaults to `false`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        include_fallbacks: Option<bool>,
+    },
+
+    #[doc(hidden)]
+    #[serde(skip)]
+    _Custom(CustomPushCondition),
+}
+
+/// A push condition of an unknown kind.
+#[doc(hidden)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CustomPushCondition {
+    /// The kind of the condition.
+    kind: String,
+
+    /// The remaining fields of the condition.
+    #[serde(flatten)]
+    data: BTreeMap<String, JsonValue>,
 }
 
 impl PushCondition {
@@ -64,25 +145,25 @@ impl PushCondition {
             Self::EventMatch { key, pattern } => {
                 let value = match key.as_str() {
                     "room_id" => context.room_id.as_str(),
-                    _ => match event.get(key) {
+                    _ => match event.get_str(key) {
                         Some(v) => v,
                         None => return false,
                     },
                 };
 
-                value.matches_pattern(pattern, key == "content.body")
+                glob::matches_cached(pattern, key == "content.body", value)
             }
             Self::ContainsDisplayName => {
-                let value = match event.get("content.body") {
+                let value = match event.get_str("content.body") {
                     Some(v) => v,
                     None => return false,
                 };
 
-                value.matches_pattern(&context.user_display_name, true)
+                glob::matches_literal_cached(&context.user_display_name, value)
             }
             Self::RoomMemberCount { is } => is.contains(&context.member_count),
             Self::SenderNotificationPermission { key } => {
-                let sender_id = match event.get("sender") {
+                let sender_id = match event.get_str("sender") {
                     Some(v) => match UserId::try_from(v) {
                         Ok(u) => u,
                         Err(_) => return false,
@@ -100,12 +181,42 @@ impl PushCondition {
                     None => false,
                 }
             }
+            Self::EventPropertyIs { key, value } => event.get(key).map_or(false, |v| v == value),
+            Self::EventPropertyContains { key, value } => event
+                .get(key)
+                .and_then(FlattenedJsonValue::as_array)
+                .map_or(false, |array| array.contains(value)),
+            #[cfg(feature = "unstable-pre-spec")]
+            Self::RelatedEventMatch { key, pattern, rel_type, include_fallbacks } => {
+                let related_event = match context.related_events.get(rel_type) {
+                    Some(e) => e,
+                    None => return false,
+                };
+
+                if related_event.is_fallback && !include_fallbacks.unwrap_or(false) {
+                    return false;
+                }
+
+                match (key, pattern) {
+                    (Some(key), Some(pattern)) => {
+                        related_event.event.get_str(key).map_or(false, |value| {
+                            glob::matches_cached(pattern, key == "content.body", value)
+                        })
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+            Self::_Custom(_) => false,
         }
     }
 }
 
 /// The context of the room associated to an event to be able to test all push conditions.
+///
+/// To create an instance of this type, use `PushConditionRoomCtx::new`.
 #[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct PushConditionRoomCtx {
     /// The ID of the room.
     pub room_id: RoomId,
@@ -124,183 +235,90 @@ pub struct PushConditionRoomCtx {
 
     /// The notification power levels of the room.
     pub notification_power_levels: NotificationPowerLevels,
-}
-
-/// Additional functions for character matching.
-trait CharExt {
-    /// Whether or not this char can be part of a word.
-    fn is_word_char(&self) -> bool;
-}
 
-impl CharExt for char {
-    fn is_word_char(&self) -> bool {
-        self.is_alphanumeric() || *self == '_'
-    }
-}
-
-/// Additional functions for string matching.
-trait StrExt {
-    /// Get the length of the char at `index`. The byte index must correspond to
-    /// the start of a char boundary.
-    fn char_len(&self, index: usize) -> usize;
-
-    /// Get the char at `index`. The byte index must correspond to the start of
-    /// a char boundary.
-    fn char_at(&self, index: usize) -> char;
-
-    /// Get the index of the char that is before the char at `index`. The byte index
-    /// must correspond to a char boundary.
+    /// The events related to the event, by type of relation.
     ///
-    /// Returns `None` if there's no previous char. Otherwise, returns the char.
-    fn find_prev_char(&self, index: usize) -> Option<char>;
+    /// Used by the `related_event_match` condition.
+    #[cfg(feature = "unstable-pre-spec")]
+    pub related_events: BTreeMap<String, RelatedEvent>,
+}
 
-    /// Matches this string against `pattern`.
-    ///
-    /// The match is case insensitive.
+impl PushConditionRoomCtx {
+    /// Creates a new `PushConditionRoomCtx` for a room with the default power levels.
     ///
-    /// If `match_words` is `true`, looks for `pattern` as a substring of `self`,
-    /// and checks that it is separated from other words. Otherwise, checks
-    /// `pattern` as a glob with wildcards `*` and `?`.
-    fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool;
-
-    /// Matches this string against `pattern`, with word boundaries.
+    /// # Arguments
     ///
-    /// The match is case sensitive.
-    fn matches_word(&self, pattern: &str) -> bool;
-}
-
-impl StrExt for str {
-    fn char_len(&self, index: usize) -> usize {
-        let mut len = 1;
-        while !self.is_char_boundary(index + len) {
-            len += 1;
+    /// * `room_id` - The ID of the room.
+    /// * `member_count` - The number of joined members of the room.
+    /// * `user_display_name` - The display name of the current user in the room.
+    pub fn new(room_id: RoomId, member_count: UInt, user_display_name: String) -> Self {
+        Self {
+            room_id,
+            member_count,
+            user_display_name,
+            users_power_levels: BTreeMap::new(),
+            default_power_level: Int::default(),
+            notification_power_levels: NotificationPowerLevels::new(),
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
         }
-        len
-    }
-
-    fn char_at(&self, index: usize) -> char {
-        let end = index + self.char_len(index);
-        let char_str = &self[index..end];
-        char::from_str(char_str)
-            .unwrap_or_else(|_| panic!("Could not convert str '{}' to char", char_str))
     }
 
-    fn find_prev_char(&self, index: usize) -> Option<char> {
-        if index == 0 {
-            return None;
-        }
-
-        let mut pos = index - 1;
-        while !self.is_char_boundary(pos) {
-            pos -= 1;
-        }
-        Some(self.char_at(pos))
+    /// Sets the power levels of the room.
+    ///
+    /// With ruma-events, `PushConditionPowerLevels` can be converted from the content of the
+    /// `m.room.power_levels` event of the room.
+    pub fn with_power_levels(mut self, power_levels: PushConditionPowerLevels) -> Self {
+        let PushConditionPowerLevels { users, users_default, notifications } = power_levels;
+
+        self.users_power_levels = users;
+        self.default_power_level = users_default;
+        self.notification_power_levels = notifications;
+        self
     }
 
-    fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool {
-        let value = &self.to_lowercase();
-        let pattern = &pattern.to_lowercase();
-
-        if match_words {
-            value.matches_word(pattern)
-        } else {
-            WildMatch::new(pattern).matches(value)
-        }
+    /// Adds an event related to the event, with the given type of relation.
+    #[cfg(feature = "unstable-pre-spec")]
+    pub fn with_related_event(mut self, rel_type: impl Into<String>, event: RelatedEvent) -> Self {
+        self.related_events.insert(rel_type.into(), event);
+        self
     }
+}
 
-    fn matches_word(&self, pattern: &str) -> bool {
-        if self == pattern {
-            return true;
-        }
-        if pattern.is_empty() {
-            return false;
-        }
-
-        match self.find(pattern) {
-            Some(start) => {
-                let end = start + pattern.len();
-
-                // Look if the match has word boundaries.
-                let word_boundary_start = !self.char_at(start).is_word_char()
-                    || self.find_prev_char(start).map_or(true, |c| !c.is_word_char());
-
-                if word_boundary_start {
-                    let word_boundary_end = end == self.len()
-                        || !self.find_prev_char(end).unwrap().is_word_char()
-                        || !self.char_at(end).is_word_char();
-
-                    if word_boundary_end {
-                        return true;
-                    }
-                }
+/// The power levels of a room that are used by push conditions.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct PushConditionPowerLevels {
+    /// The power levels of the users of the room.
+    pub users: BTreeMap<UserId, Int>,
 
-                // Find next word.
-                let non_word_str = &self[start..];
-                let non_word = match non_word_str.find(|c: char| !c.is_word_char()) {
-                    Some(pos) => pos,
-                    None => return false,
-                };
+    /// The default power level of the users of the room.
+    pub users_default: Int,
 
-                let word_str = &non_word_str[non_word..];
-                let word = match word_str.find(|c: char| c.is_word_char()) {
-                    Some(pos) => pos,
-                    None => return false,
-                };
+    /// The power levels required to send notifications.
+    pub notifications: NotificationPowerLevels,
+}
 
-                word_str[word..].matches_word(pattern)
-            }
-            None => false,
-        }
+impl PushConditionPowerLevels {
+    /// Creates a new `PushConditionPowerLevels` with the given power levels.
+    pub fn new(
+        users: BTreeMap<UserId, Int>,
+        users_default: Int,
+        notifications: NotificationPowerLevels,
+    ) -> Self {
+        Self { users, users_default, notifications }
     }
 }
 
-/// The flattened representation of a JSON object.
+/// An event related to the event that push rules are evaluated for.
+#[cfg(feature = "unstable-pre-spec")]
 #[derive(Clone, Debug)]
-pub struct FlattenedJson {
-    /// The internal map containing the flattened JSON as a pair path, value.
-    map: BTreeMap<String, String>,
-}
-
-impl FlattenedJson {
-    /// Create a `FlattenedJson` from `Raw`.
-    pub fn from_raw<T>(raw: &Raw<T>) -> Self
-    where
-        T: Serialize,
-    {
-        let mut s = Self { map: BTreeMap::new() };
-
-        s.flatten_value(to_json_value(raw).unwrap(), "".into());
-
-        s
-    }
+pub struct RelatedEvent {
+    /// The flattened JSON representation of the related event.
+    pub event: FlattenedJson,
 
-    /// Flatten and insert the `value` at `path`.
-    fn flatten_value(&mut self, value: JsonValue, path: String) {
-        match value {
-            JsonValue::Object(fields) => {
-                for (key, value) in fields {
-                    let path = if path.is_empty() { key } else { format!("{}.{}", path, key) };
-                    self.flatten_value(value, path);
-                }
-            }
-            JsonValue::String(s) => {
-                if self.map.insert(path.clone(), s).is_some() {
-                    warn!("Duplicate path in flattened JSON: {}", path);
-                };
-            }
-            JsonValue::Number(_) | JsonValue::Bool(_) => {
-                if self.map.insert(path.clone(), value.to_string()).is_some() {
-                    warn!("Duplicate path in flattened JSON: {}", path);
-                };
-            }
-            JsonValue::Array(_) | JsonValue::Null => {}
-        }
-    }
-
-    /// Value associated with the given `path`.
-    pub fn get(&self, path: &str) -> Option<&str> {
-        self.map.get(path).map(|s| s.as_str())
-    }
+    /// Whether the relation is only a fallback, like the reply fallback of threads.
+    pub is_fallback: bool,
 }
 
 #[cfg(test)]
@@ -308,7 +326,6 @@ mod tests {
     use std::collections::BTreeMap;
 
     use js_int::uint;
-    use maplit::btreemap;
     use matches::assert_matches;
     use ruma_identifiers::{room_id, user_id};
     use ruma_serde::Raw;
@@ -318,7 +335,10 @@ mod tests {
 
     use crate::power_levels::NotificationPowerLevels;
 
-    use super::{FlattenedJson, PushCondition, PushConditionRoomCtx, RoomMemberCountIs, StrExt};
+    use super::{
+        FlattenedJson, PushCondition, PushConditionPowerLevels, PushConditionRoomCtx,
+        RoomMemberCountIs, ScalarJsonValue,
+    };
 
     #[test]
     fn serialize_event_match_condition() {
@@ -423,49 +443,76 @@ mod tests {
     }
 
     #[test]
-    fn words_match() {
-        assert!("foo bar".matches_word("foo"));
-        assert!(!"Foo bar".matches_word("foo"));
-        assert!(!"foobar".matches_word("foo"));
-        assert!("foobar foo".matches_word("foo"));
-        assert!(!"foobar foobar".matches_word("foo"));
-        assert!(!"foobar bar".matches_word("bar bar"));
-        assert!("foobar bar bar".matches_word("bar bar"));
-        assert!(!"foobar bar barfoo".matches_word("bar bar"));
-        assert!("conduit ⚡️".matches_word("conduit ⚡️"));
-        assert!("conduit ⚡️".matches_word("conduit"));
-        assert!("conduit ⚡️".matches_word("⚡️"));
-        assert!("conduit⚡️".matches_word("conduit"));
-        assert!("conduit⚡️".matches_word("⚡️"));
-        assert!("⚡️conduit".matches_word("conduit"));
-        assert!("⚡️conduit".matches_word("⚡️"));
-        assert!("Ruma Dev👩‍💻".matches_word("Dev"));
-        assert!("Ruma Dev👩‍💻".matches_word("👩‍💻"));
-        assert!("Ruma Dev👩‍💻".matches_word("Dev👩‍💻"));
+    fn serialize_event_property_is_condition() {
+        assert_eq!(
+            to_json_value(&PushCondition::EventPropertyIs {
+                key: r"content.m\.relates_to.rel_type".into(),
+                value: "m.thread".into(),
+            })
+            .unwrap(),
+            json!({
+                "kind": "event_property_is",
+                "key": r"content.m\.relates_to.rel_type",
+                "value": "m.thread",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_event_property_contains_condition() {
+        let json_data = json!({
+            "kind": "event_property_contains",
+            "key": "content.tags",
+            "value": 3,
+        });
+        assert_matches!(
+            from_json_value::<PushCondition>(json_data).unwrap(),
+            PushCondition::EventPropertyContains { key, value }
+            if key == "content.tags" && value == ScalarJsonValue::Integer(3.into())
+        );
+    }
+
+    #[test]
+    fn custom_condition_round_trip() {
+        let json_data = json!({
+            "kind": "org.example.custom",
+            "key": "content.foo",
+            "value": [1, 2],
+        });
+
+        let condition = from_json_value::<PushCondition>(json_data.clone()).unwrap();
+        assert_matches!(condition, PushCondition::_Custom(_));
+        assert_eq!(to_json_value(&condition).unwrap(), json_data);
+    }
+
+    #[test]
+    fn deserialize_invalid_known_condition() {
+        let json_data = json!({ "kind": "event_match", "key": "content.body" });
+        assert!(from_json_value::<PushCondition>(json_data).is_err());
     }
 
     #[test]
-    fn patterns_match() {
-        // Word matching
-        assert!("foo bar".matches_pattern("foo", true));
-        assert!("Foo bar".matches_pattern("foo", true));
-        assert!(!"foobar".matches_pattern("foo", true));
-        assert!(!"foo bar".matches_pattern("foo*", true));
-        assert!("".matches_pattern("", true));
-        assert!(!"foo".matches_pattern("", true));
-
-        // Glob matching
-        assert!(!"foo bar".matches_pattern("foo", false));
-        assert!("foo".matches_pattern("foo", false));
-        assert!("foo".matches_pattern("foo*", false));
-        assert!("foobar".matches_pattern("foo*", false));
-        assert!("foo bar".matches_pattern("foo*", false));
-        assert!(!"foo".matches_pattern("foo?", false));
-        assert!("foo".matches_pattern("fo?", false));
-        assert!("FOO".matches_pattern("foo", false));
-        assert!("".matches_pattern("", false));
-        assert!("".matches_pattern("*", false));
-        assert!(!"foo".matches_pattern("", false));
+    fn room_ctx_with_power_levels() {
+        let sender = user_id!("@worthy_whale:server.name");
+        let event_raw =
+            serde_json::from_str::<Raw<JsonValue>>(r#"{ "sender": "@worthy_whale:server.name" }"#)
+                .unwrap();
+        let event = FlattenedJson::from_raw(&event_raw);
+        let condition = PushCondition::SenderNotificationPermission { key: "room".into() };
+
+        let context =
+            PushConditionRoomCtx::new(room_id!("!room:server.name"), uint!(3), "Whale".into());
+        assert!(context.users_power_levels.is_empty());
+        assert!(!condition.applies(&event, &context));
+
+        let mut users = BTreeMap::new();
+        users.insert(sender, 50.into());
+        let context = context.with_power_levels(PushConditionPowerLevels::new(
+            users,
+            0.into(),
+            NotificationPowerLevels::new(),
+        ));
+        assert!(condition.applies(&event, &context));
     }
 
     #[test]
@@ -482,6 +529,8 @@ mod tests {
             users_power_levels,
             default_power_level: 50.into(),
             notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
         };
 
         let first_event_raw = serde_json::from_str::<Raw<JsonValue>>(
@@ -556,52 +605,163 @@ mod tests {
     }
 
     #[test]
-    fn flattened_json_values() {
-        let raw = serde_json::from_str::<Raw<JsonValue>>(
+    fn property_conditions_apply_to_events() {
+        let context = PushConditionRoomCtx {
+            room_id: room_id!("!room:server.name"),
+            member_count: 3u8.into(),
+            user_display_name: "Groovy Gorilla".into(),
+            users_power_levels: BTreeMap::new(),
+            default_power_level: 50.into(),
+            notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            #[cfg(feature = "unstable-pre-spec")]
+            related_events: BTreeMap::new(),
+        };
+
+        let event_raw = serde_json::from_str::<Raw<JsonValue>>(
             r#"{
-                "string": "Hello World",
-                "number": 10,
-                "array": [1, 2],
-                "boolean": true,
-                "null": null
+                "sender": "@worthy_whale:server.name",
+                "content": {
+                    "body": "Hello",
+                    "m.relates_to": {
+                        "rel_type": "m.thread"
+                    },
+                    "level": 3,
+                    "tags": ["fun", 10, null],
+                    "reason": null
+                }
             }"#,
         )
         .unwrap();
+        let event = FlattenedJson::from_raw(&event_raw);
 
-        let flattened = FlattenedJson::from_raw(&raw);
-        assert_eq!(
-            flattened.map,
-            btreemap! {
-                "string".into() => "Hello World".into(),
-                "number".into() => "10".into(),
-                "boolean".into() => "true".into(),
-            },
-        );
+        let property_is = |key: &str, value: ScalarJsonValue| PushCondition::EventPropertyIs {
+            key: key.into(),
+            value,
+        };
+        assert!(property_is(r"content.m\.relates_to.rel_type", "m.thread".into())
+            .applies(&event, &context));
+        assert!(!property_is(r"content.m\.relates_to.rel_type", "m.thre*".into())
+            .applies(&event, &context));
+        assert!(property_is("content.level", ScalarJsonValue::Integer(3.into()))
+            .applies(&event, &context));
+        assert!(!property_is("content.level", "3".into()).applies(&event, &context));
+        assert!(property_is("content.reason", ScalarJsonValue::Null).applies(&event, &context));
+        assert!(!property_is("content.missing", ScalarJsonValue::Null).applies(&event, &context));
+
+        let property_contains = |key: &str, value: ScalarJsonValue| {
+            PushCondition::EventPropertyContains { key: key.into(), value }
+        };
+        assert!(property_contains("content.tags", "fun".into()).applies(&event, &context));
+        assert!(property_contains("content.tags", ScalarJsonValue::Integer(10.into()))
+            .applies(&event, &context));
+        assert!(property_contains("content.tags", ScalarJsonValue::Null).applies(&event, &context));
+        assert!(!property_contains("content.tags", "boring".into()).applies(&event, &context));
+        assert!(!property_contains("content.body", "Hello".into()).applies(&event, &context));
+
+        // Event matches only apply to strings.
+        let level_match =
+            PushCondition::EventMatch { key: "content.level".into(), pattern: "3".into() };
+        assert!(!level_match.applies(&event, &context));
+
+        let custom: PushCondition =
+            from_json_value(json!({ "kind": "org.example.always", "key": "content.body" }))
+                .unwrap();
+        assert!(!custom.applies(&event, &context));
     }
 
+    #[cfg(feature = "unstable-pre-spec")]
     #[test]
-    fn flattened_json_nested() {
-        let raw = serde_json::from_str::<Raw<JsonValue>>(
+    fn related_event_match_applies_to_events() {
+        use super::RelatedEvent;
+
+        let reply_to_raw = serde_json::from_str::<Raw<JsonValue>>(
             r#"{
-                "desc": "Level 0",
-                "up": {
-                    "desc": "Level 1",
-                    "up": {
-                        "desc": "Level 2"
-                    }
-                }
+                "sender": "@jolly_jumper:server.name",
+                "content": { "body": "Who wants to ride?" }
             }"#,
         )
         .unwrap();
+        let reply_to = FlattenedJson::from_raw(&reply_to_raw);
 
-        let flattened = FlattenedJson::from_raw(&raw);
+        let mut context = PushConditionRoomCtx {
+            room_id: room_id!("!room:server.name"),
+            member_count: 3u8.into(),
+            user_display_name: "Jolly Jumper".into(),
+            users_power_levels: BTreeMap::new(),
+            default_power_level: 50.into(),
+            notification_power_levels: NotificationPowerLevels { room: 50.into() },
+            related_events: BTreeMap::new(),
+        };
+        context
+            .related_events
+            .insert("m.in_reply_to".into(), RelatedEvent { event: reply_to, is_fallback: false });
+
+        let event_raw = serde_json::from_str::<Raw<JsonValue>>(r#"{ "content": {} }"#).unwrap();
+        let event = FlattenedJson::from_raw(&event_raw);
+
+        let reply_to_me = from_json_value::<PushCondition>(json!({
+            "kind": "im.nheko.msc3664.related_event_match",
+            "key": "sender",
+            "pattern": "@jolly_jumper:server.name",
+            "rel_type": "m.in_reply_to",
+        }))
+        .unwrap();
+        assert!(reply_to_me.applies(&event, &context));
         assert_eq!(
-            flattened.map,
-            btreemap! {
-                "desc".into() => "Level 0".into(),
-                "up.desc".into() => "Level 1".into(),
-                "up.up.desc".into() => "Level 2".into(),
-            },
+            to_json_value(&reply_to_me).unwrap()["kind"],
+            "im.nheko.msc3664.related_event_match"
         );
+
+        let stable_reply_to_me = from_json_value::<PushCondition>(json!({
+            "kind": "related_event_match",
+            "key": "sender",
+            "pattern": "@jolly_jumper:server.name",
+            "rel_type": "m.in_reply_to",
+        }))
+        .unwrap();
+        assert!(stable_reply_to_me.applies(&event, &context));
+
+        let reply_to_ride = PushCondition::RelatedEventMatch {
+            key: Some("content.body".into()),
+            pattern: Some("ride".into()),
+            rel_type: "m.in_reply_to".into(),
+            include_fallbacks: None,
+        };
+        assert!(reply_to_ride.applies(&event, &context));
+
+        let reply_to_rid = PushCondition::RelatedEventMatch {
+            key: Some("content.body".into()),
+            pattern: Some("rid".into()),
+            rel_type: "m.in_reply_to".into(),
+            include_fallbacks: None,
+        };
+        assert!(!reply_to_rid.applies(&event, &context));
+
+        let any_reply = PushCondition::RelatedEventMatch {
+            key: None,
+            pattern: None,
+            rel_type: "m.in_reply_to".into(),
+            include_fallbacks: None,
+        };
+        assert!(any_reply.applies(&event, &context));
+
+        let thread = PushCondition::RelatedEventMatch {
+            key: None,
+            pattern: None,
+            rel_type: "m.thread".into(),
+            include_fallbacks: None,
+        };
+        assert!(!thread.applies(&event, &context));
+
+        context.related_events.get_mut("m.in_reply_to").unwrap().is_fallback = true;
+        assert!(!reply_to_me.applies(&event, &context));
+
+        let reply_to_me_with_fallbacks = PushCondition::RelatedEventMatch {
+            key: Some("sender".into()),
+            pattern: Some("@jolly_jumper:server.name".into()),
+            rel_type: "m.in_reply_to".into(),
+            include_fallbacks: Some(true),
+        };
+        assert!(reply_to_me_with_fallbacks.applies(&event, &context));
     }
 }
@@ -0,0 +1,364 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use js_int::Int;
+use ruma_serde::Raw;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{to_value as to_json_value, value::Value as JsonValue};
+use tracing::warn;
+
+/// The flattened representation of a JSON object.
+///
+/// The paths of the properties are the names of the nested objects and of the property, separated
+/// by dots. Dots and backslashes in the names are escaped with a backslash, so the path of the
+/// `rel_type` in `{ "content": { "m.relates_to": { "rel_type": "m.thread" } } }` is
+/// `content.m\.relates_to.rel_type`.
+#[derive(Clone, Debug)]
+pub struct FlattenedJson {
+    /// The internal map containing the flattened JSON as a pair path, value.
+    map: BTreeMap<String, FlattenedJsonValue>,
+
+    /// Whether the `content` of the event has an `m.mentions` object.
+    ///
+    /// This can't be deduced from the map, since an empty object has no flattened property.
+    has_mentions: bool,
+}
+
+impl FlattenedJson {
+    /// Create a `FlattenedJson` from `Raw`.
+    pub fn from_raw<T>(raw: &Raw<T>) -> Self
+    where
+        T: Serialize,
+    {
+        let value = to_json_value(raw).unwrap();
+        let has_mentions = matches!(
+            value.get("content").and_then(|content| content.get("m.mentions")),
+            Some(JsonValue::Object(_))
+        );
+
+        let mut s = Self { map: BTreeMap::new(), has_mentions };
+        s.flatten_value(value, "".into());
+
+        s
+    }
+
+    /// Flatten and insert the `value` at `path`.
+    fn flatten_value(&mut self, value: JsonValue, path: String) {
+        let value = match value {
+            JsonValue::Object(fields) => {
+                for (key, value) in fields {
+                    let key = escape_key(&key);
+                    let path = if path.is_empty() { key } else { format!("{}.{}", path, key) };
+                    self.flatten_value(value, path);
+                }
+                return;
+            }
+            JsonValue::Array(values) => FlattenedJsonValue::Array(
+                values.into_iter().filter_map(ScalarJsonValue::from_json_value).collect(),
+            ),
+            value => match ScalarJsonValue::from_json_value(value) {
+                Some(value) => value.into(),
+                None => return,
+            },
+        };
+
+        if self.map.insert(path.clone(), value).is_some() {
+            warn!("Duplicate path in flattened JSON: {}", path);
+        }
+    }
+
+    /// Value associated with the given `path`.
+    pub fn get(&self, path: &str) -> Option<&FlattenedJsonValue> {
+        self.map.get(path)
+    }
+
+    /// String value associated with the given `path`.
+    ///
+    /// Returns `None` if there is no value at `path` or if it is not a string.
+    pub fn get_str(&self, path: &str) -> Option<&str> {
+        self.get(path).and_then(FlattenedJsonValue::as_str)
+    }
+
+    /// Whether the `content` of the event has an `m.mentions` property, even if it is empty.
+    pub fn contains_mentions(&self) -> bool {
+        self.has_mentions
+    }
+}
+
+/// Escapes the dots and backslashes in the name of a property.
+fn escape_key(key: &str) -> String {
+    key.replace('\\', r"\\").replace('.', r"\.")
+}
+
+/// The value of a property of a `FlattenedJson`.
+///
+/// Floating-point numbers and nested arrays are not kept, since they are not allowed in events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum FlattenedJsonValue {
+    /// A string.
+    String(String),
+
+    /// An integer.
+    Integer(Int),
+
+    /// A boolean.
+    Bool(bool),
+
+    /// `null`.
+    Null,
+
+    /// An array, only containing its scalar values.
+    Array(Vec<ScalarJsonValue>),
+}
+
+impl FlattenedJsonValue {
+    /// The string value, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The values of the array, if this is an array.
+    pub fn as_array(&self) -> Option<&[ScalarJsonValue]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<ScalarJsonValue> for FlattenedJsonValue {
+    fn from(value: ScalarJsonValue) -> Self {
+        match value {
+            ScalarJsonValue::String(s) => Self::String(s),
+            ScalarJsonValue::Integer(i) => Self::Integer(i),
+            ScalarJsonValue::Bool(b) => Self::Bool(b),
+            ScalarJsonValue::Null => Self::Null,
+        }
+    }
+}
+
+impl PartialEq<ScalarJsonValue> for FlattenedJsonValue {
+    fn eq(&self, other: &ScalarJsonValue) -> bool {
+        match (self, other) {
+            (Self::String(a), ScalarJsonValue::String(b)) => a == b,
+            (Self::Integer(a), ScalarJsonValue::Integer(b)) => a == b,
+            (Self::Bool(a), ScalarJsonValue::Bool(b)) => a == b,
+            (Self::Null, ScalarJsonValue::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A scalar JSON value that can be compared exactly with the value of a property of an event.
+///
+/// Used by the `event_property_is` and `event_property_contains` push conditions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum ScalarJsonValue {
+    /// A string.
+    String(String),
+
+    /// An integer.
+    Integer(Int),
+
+    /// A boolean.
+    Bool(bool),
+
+    /// `null`.
+    Null,
+}
+
+impl ScalarJsonValue {
+    /// Converts a JSON value, returning `None` if it is not a string, an integer in the range of
+    /// `Int`, a boolean or `null`.
+    fn from_json_value(value: JsonValue) -> Option<Self> {
+        match value {
+            JsonValue::String(s) => Some(Self::String(s)),
+            JsonValue::Number(n) => {
+                n.as_i64().and_then(|n| Int::try_from(n).ok()).map(Self::Integer)
+            }
+            JsonValue::Bool(b) => Some(Self::Bool(b)),
+            JsonValue::Null => Some(Self::Null),
+            JsonValue::Array(_) | JsonValue::Object(_) => None,
+        }
+    }
+}
+
+impl From<String> for ScalarJsonValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ScalarJsonValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<Int> for ScalarJsonValue {
+    fn from(value: Int) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for ScalarJsonValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl Serialize for ScalarJsonValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::String(s) => serializer.serialize_str(s),
+            Self::Integer(i) => i.serialize(serializer),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Null => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ScalarJsonValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = JsonValue::deserialize(deserializer)?;
+        Self::from_json_value(value)
+            .ok_or_else(|| de::Error::custom("expected a string, an integer, a boolean or null"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::int;
+    use maplit::btreemap;
+    use ruma_serde::Raw;
+    use serde_json::{
+        from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+    };
+
+    use super::{FlattenedJson, FlattenedJsonValue, ScalarJsonValue};
+
+    #[test]
+    fn flattened_json_values() {
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "string": "Hello World",
+                "number": 10,
+                "float": 1.5,
+                "array": [1, "two", [3], { "four": 4 }],
+                "boolean": true,
+                "null": null
+            }"#,
+        )
+        .unwrap();
+
+        let flattened = FlattenedJson::from_raw(&raw);
+        assert_eq!(
+            flattened.map,
+            btreemap! {
+                "string".into() => FlattenedJsonValue::String("Hello World".into()),
+                "number".into() => FlattenedJsonValue::Integer(int!(10)),
+                "array".into() => FlattenedJsonValue::Array(vec![
+                    ScalarJsonValue::Integer(int!(1)),
+                    ScalarJsonValue::String("two".into()),
+                ]),
+                "boolean".into() => FlattenedJsonValue::Bool(true),
+                "null".into() => FlattenedJsonValue::Null,
+            },
+        );
+        assert_eq!(flattened.get_str("string"), Some("Hello World"));
+        assert_eq!(flattened.get_str("number"), None);
+    }
+
+    #[test]
+    fn flattened_json_nested() {
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "desc": "Level 0",
+                "up": {
+                    "desc": "Level 1",
+                    "up": {
+                        "desc": "Level 2"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let flattened = FlattenedJson::from_raw(&raw);
+        assert_eq!(
+            flattened.map,
+            btreemap! {
+                "desc".into() => FlattenedJsonValue::String("Level 0".into()),
+                "up.desc".into() => FlattenedJsonValue::String("Level 1".into()),
+                "up.up.desc".into() => FlattenedJsonValue::String("Level 2".into()),
+            },
+        );
+    }
+
+    #[test]
+    fn flattened_json_escaped_keys() {
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "content": {
+                    "m.relates_to": {
+                        "rel_type": "m.thread"
+                    },
+                    "back\\slash": true
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let flattened = FlattenedJson::from_raw(&raw);
+        assert_eq!(flattened.get_str(r"content.m\.relates_to.rel_type"), Some("m.thread"));
+        assert_eq!(flattened.get(r"content.back\\slash"), Some(&FlattenedJsonValue::Bool(true)));
+        assert_eq!(flattened.get("content.m.relates_to.rel_type"), None);
+    }
+
+    #[test]
+    fn flattened_json_mentions() {
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{ "content": { "body": "Hello", "m.mentions": {} } }"#,
+        )
+        .unwrap();
+        assert!(FlattenedJson::from_raw(&raw).contains_mentions());
+
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{ "content": { "body": "Hello", "m.mentions": { "room": true } } }"#,
+        )
+        .unwrap();
+        assert!(FlattenedJson::from_raw(&raw).contains_mentions());
+
+        let raw = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{ "content": { "body": "Hello" }, "m.mentions": {} }"#,
+        )
+        .unwrap();
+        assert!(!FlattenedJson::from_raw(&raw).contains_mentions());
+    }
+
+    #[test]
+    fn scalar_json_value_serde() {
+        assert_eq!(to_json_value(ScalarJsonValue::Integer(int!(-5))).unwrap(), json!(-5));
+        assert_eq!(to_json_value(ScalarJsonValue::Null).unwrap(), json!(null));
+        assert_eq!(
+            from_json_value::<ScalarJsonValue>(json!("text")).unwrap(),
+            ScalarJsonValue::String("text".into())
+        );
+        assert_eq!(
+            from_json_value::<ScalarJsonValue>(json!(false)).unwrap(),
+            ScalarJsonValue::Bool(false)
+        );
+        assert!(from_json_value::<ScalarJsonValue>(json!(1.5)).is_err());
+        assert!(from_json_value::<ScalarJsonValue>(json!([1])).is_err());
+    }
+}
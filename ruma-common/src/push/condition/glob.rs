@@ -0,0 +1,437 @@
+use std::{cell::RefCell, collections::HashMap};
+
+/// The maximum number of compiled patterns kept in the cache of a thread, for each matching mode.
+///
+/// The cache is cleared when it is full.
+const MAX_CACHED_PATTERNS: usize = 1024;
+
+thread_local! {
+    /// The compiled patterns, indexed by whether they match words.
+    static PATTERN_CACHE: RefCell<[HashMap<String, GlobMatcher>; 2]> =
+        RefCell::new([HashMap::new(), HashMap::new()]);
+
+    /// The compiled literal texts, that match words.
+    static LITERAL_CACHE: RefCell<HashMap<String, GlobMatcher>> = RefCell::new(HashMap::new());
+}
+
+/// A compiled glob pattern, as used by the `event_match` push condition.
+///
+/// The pattern supports the `*` (any sequence of characters) and `?` (any single character)
+/// wildcards, and character classes like `[abc]`, `[a-z]` or `[!0-9]`. An unclosed `[` is a
+/// literal character. The match is case-insensitive.
+///
+/// When matching words, like for the `content.body` key, the pattern must match a part of the
+/// value that starts and ends at word boundaries. Otherwise it must match the whole value.
+#[derive(Clone, Debug)]
+pub struct GlobMatcher {
+    /// The tokens of the pattern, whose characters are lowercase if `ignore_case` is `true`.
+    tokens: Vec<Token>,
+    match_words: bool,
+    ignore_case: bool,
+}
+
+impl GlobMatcher {
+    /// Compiles the given glob pattern.
+    pub fn new(pattern: &str, match_words: bool) -> Self {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        let mut tokens = Vec::new();
+
+        let mut i = 0;
+        while i < pattern.len() {
+            let token = match pattern[i] {
+                '*' => Token::AnyString,
+                '?' => Token::AnyChar,
+                '[' => match parse_class(&pattern[i + 1..]) {
+                    Some((class, len)) => {
+                        i += len;
+                        class
+                    }
+                    None => Token::Char('['),
+                },
+                c => Token::Char(c),
+            };
+            i += 1;
+
+            // Consecutive `*` are equivalent to a single one.
+            if token != Token::AnyString || tokens.last() != Some(&Token::AnyString) {
+                tokens.push(token);
+            }
+        }
+
+        Self { tokens, match_words, ignore_case: true }
+    }
+
+    /// Creates a matcher for the given text, without wildcards or character classes.
+    pub fn literal(text: &str, match_words: bool) -> Self {
+        Self {
+            tokens: text.to_lowercase().chars().map(Token::Char).collect(),
+            match_words,
+            ignore_case: true,
+        }
+    }
+
+    /// Whether the given value matches this pattern.
+    pub fn matches(&self, value: &str) -> bool {
+        if !self.match_words {
+            return self.matches_from(value, 0, |end| end == value.len());
+        }
+
+        if self.tokens.is_empty() {
+            return value.is_empty();
+        }
+
+        // Quickly skip the ASCII characters that can't start a match, without decoding the value.
+        // ASCII letters are lowercased by setting the `0x20` bit.
+        let first_byte = match self.tokens.first() {
+            Some(Token::Char(c)) if c.is_ascii_lowercase() => Some((*c as u8, 0x20)),
+            Some(Token::Char(c)) if c.is_ascii() && !c.is_ascii_uppercase() => Some((*c as u8, 0)),
+            _ => None,
+        };
+
+        // Whether there is a word boundary at the given byte index of the value.
+        let is_word_boundary = |index: usize| {
+            !matches!(value[index..].chars().next(), Some(c) if c.is_word_char())
+                || !matches!(value[..index].chars().next_back(), Some(c) if c.is_word_char())
+        };
+
+        (0..=value.len()).any(|start| {
+            match value.as_bytes().get(start) {
+                Some(&byte) if byte.is_ascii() => {
+                    if let Some((first_byte, mask)) = first_byte {
+                        if byte | mask != first_byte {
+                            return false;
+                        }
+                    }
+                }
+                _ if !value.is_char_boundary(start) => return false,
+                _ => {}
+            }
+
+            is_word_boundary(start) && self.matches_from(value, start, is_word_boundary)
+        })
+    }
+
+    /// Whether this pattern matches a part of `value` that starts at the byte index `start` and
+    /// ends at a byte index for which `is_end` returns `true`.
+    fn matches_from(&self, value: &str, start: usize, is_end: impl Fn(usize) -> bool) -> bool {
+        let (mut token_index, mut value_index) = (0, start);
+
+        // The token index after the last `*` and the value index it was tried at.
+        let mut backtrack = None;
+
+        loop {
+            let c = value[value_index..].chars().next();
+            match (self.tokens.get(token_index), c) {
+                (Some(Token::AnyString), _) => {
+                    token_index += 1;
+                    backtrack = Some((token_index, value_index));
+                    continue;
+                }
+                (Some(token), Some(c)) if self.token_matches(token, c) => {
+                    token_index += 1;
+                    value_index += c.len_utf8();
+                    continue;
+                }
+                (None, _) if is_end(value_index) => return true,
+                _ => {}
+            }
+
+            // Let the last `*` match one more character.
+            let (star_token_index, star_value_index) = match backtrack {
+                Some(backtrack) => backtrack,
+                None => return false,
+            };
+            let c = match value[star_value_index..].chars().next() {
+                Some(c) => c,
+                None => return false,
+            };
+
+            token_index = star_token_index;
+            value_index = star_value_index + c.len_utf8();
+            backtrack = Some((token_index, value_index));
+        }
+    }
+
+    /// Whether the given token matches the given character of a value.
+    ///
+    /// `AnyString` is handled by the matcher.
+    fn token_matches(&self, token: &Token, c: char) -> bool {
+        if token.matches(c) {
+            return true;
+        }
+        if !self.ignore_case {
+            return false;
+        }
+
+        // The characters of the pattern are already lowercase. Characters whose lowercase form
+        // has several characters can't match a single token.
+        if c.is_ascii() {
+            let lower = c.to_ascii_lowercase();
+            return lower != c && token.matches(lower);
+        }
+
+        let mut lowercase = c.to_lowercase();
+        match (lowercase.next(), lowercase.next()) {
+            (Some(lower), None) => lower != c && token.matches(lower),
+            _ => false,
+        }
+    }
+}
+
+/// Matches `value` against the glob `pattern`, compiling the pattern only the first time it is
+/// used on the current thread.
+pub(super) fn matches_cached(pattern: &str, match_words: bool, value: &str) -> bool {
+    PATTERN_CACHE.with(|cache| {
+        let cache = &mut cache.borrow_mut()[match_words as usize];
+        matches_in_cache(cache, pattern, value, || GlobMatcher::new(pattern, match_words))
+    })
+}
+
+/// Matches the words of `value` against the literal `text`, like a display name, compiling the
+/// matcher only the first time it is used on the current thread.
+pub(super) fn matches_literal_cached(text: &str, value: &str) -> bool {
+    LITERAL_CACHE.with(|cache| {
+        let cache = &mut cache.borrow_mut();
+        matches_in_cache(cache, text, value, || GlobMatcher::literal(text, true))
+    })
+}
+
+/// Matches `value` against the matcher cached under `key`, compiling it with `compile` first if
+/// it is not in the cache.
+fn matches_in_cache(
+    cache: &mut HashMap<String, GlobMatcher>,
+    key: &str,
+    value: &str,
+    compile: impl FnOnce() -> GlobMatcher,
+) -> bool {
+    if let Some(matcher) = cache.get(key) {
+        return matcher.matches(value);
+    }
+
+    if cache.len() >= MAX_CACHED_PATTERNS {
+        cache.clear();
+    }
+
+    let matcher = compile();
+    let matches = matcher.matches(value);
+    cache.insert(key.to_owned(), matcher);
+
+    matches
+}
+
+/// A part of a compiled glob pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// A literal character.
+    Char(char),
+
+    /// `?`, any single character.
+    AnyChar,
+
+    /// `*`, any sequence of characters.
+    AnyString,
+
+    /// A character class, as inclusive ranges of characters.
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+impl Token {
+    /// Whether this token matches the given character.
+    ///
+    /// `AnyString` is handled by the matcher.
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Char(expected) => *expected == c,
+            Self::AnyChar | Self::AnyString => true,
+            Self::Class { negated, ranges } => {
+                ranges.iter().any(|(first, last)| (*first..=*last).contains(&c)) != *negated
+            }
+        }
+    }
+}
+
+/// Parses a character class, `rest` being the pattern after the opening `[`.
+///
+/// Returns the class and the number of characters it uses in `rest`, including the closing `]`,
+/// or `None` if the class is not closed.
+fn parse_class(rest: &[char]) -> Option<(Token, usize)> {
+    let negated = rest.first() == Some(&'!');
+    let mut ranges = Vec::new();
+
+    let mut i = if negated { 1 } else { 0 };
+    loop {
+        let c = *rest.get(i)?;
+
+        // A `]` at the start of the class is a literal character.
+        if c == ']' && !ranges.is_empty() {
+            return Some((Token::Class { negated, ranges }, i + 1));
+        }
+
+        match (rest.get(i + 1), rest.get(i + 2)) {
+            (Some(&'-'), Some(&last)) if last != ']' => {
+                ranges.push((c, last));
+                i += 3;
+            }
+            _ => {
+                ranges.push((c, c));
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Additional functions for character matching.
+trait CharExt {
+    /// Whether or not this char can be part of a word.
+    fn is_word_char(&self) -> bool;
+}
+
+impl CharExt for char {
+    fn is_word_char(&self) -> bool {
+        self.is_alphanumeric() || *self == '_'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_cached, matches_literal_cached, GlobMatcher, Token};
+
+    trait StrExt {
+        fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool;
+        fn matches_word(&self, pattern: &str) -> bool;
+    }
+
+    impl StrExt for str {
+        fn matches_pattern(&self, pattern: &str, match_words: bool) -> bool {
+            GlobMatcher::new(pattern, match_words).matches(self)
+        }
+
+        /// Case-sensitive word matching.
+        fn matches_word(&self, pattern: &str) -> bool {
+            let tokens = pattern.chars().map(Token::Char).collect();
+            GlobMatcher { tokens, match_words: true, ignore_case: false }.matches(self)
+        }
+    }
+
+    fn glob_matches(value: &str, pattern: &str, match_words: bool) -> bool {
+        value.matches_pattern(pattern, match_words)
+    }
+
+    #[test]
+    fn words_match() {
+        assert!("foo bar".matches_word("foo"));
+        assert!(!"Foo bar".matches_word("foo"));
+        assert!(!"foobar".matches_word("foo"));
+        assert!("foobar foo".matches_word("foo"));
+        assert!(!"foobar foobar".matches_word("foo"));
+        assert!(!"foobar bar".matches_word("bar bar"));
+        assert!("foobar bar bar".matches_word("bar bar"));
+        assert!(!"foobar bar barfoo".matches_word("bar bar"));
+        assert!("conduit ⚡️".matches_word("conduit ⚡️"));
+        assert!("conduit ⚡️".matches_word("conduit"));
+        assert!("conduit ⚡️".matches_word("⚡️"));
+        assert!("conduit⚡️".matches_word("conduit"));
+        assert!("conduit⚡️".matches_word("⚡️"));
+        assert!("⚡️conduit".matches_word("conduit"));
+        assert!("⚡️conduit".matches_word("⚡️"));
+        assert!("Ruma Dev👩‍💻".matches_word("Dev"));
+        assert!("Ruma Dev👩‍💻".matches_word("👩‍💻"));
+        assert!("Ruma Dev👩‍💻".matches_word("Dev👩‍💻"));
+        assert!(!"foo*".matches_word("foo?"));
+        assert!("foo? bar".matches_word("foo?"));
+    }
+
+    #[test]
+    fn patterns_match() {
+        // Word matching
+        assert!("foo bar".matches_pattern("foo", true));
+        assert!("Foo bar".matches_pattern("foo", true));
+        assert!(!"foobar".matches_pattern("foo", true));
+        assert!("foo bar".matches_pattern("foo*", true));
+        assert!("".matches_pattern("", true));
+        assert!(!"foo".matches_pattern("", true));
+
+        // Glob matching
+        assert!(!"foo bar".matches_pattern("foo", false));
+        assert!("foo".matches_pattern("foo", false));
+        assert!("foo".matches_pattern("foo*", false));
+        assert!("foobar".matches_pattern("foo*", false));
+        assert!("foo bar".matches_pattern("foo*", false));
+        assert!(!"foo".matches_pattern("foo?", false));
+        assert!("foo".matches_pattern("fo?", false));
+        assert!("FOO".matches_pattern("foo", false));
+        assert!("".matches_pattern("", false));
+        assert!("".matches_pattern("*", false));
+        assert!(!"foo".matches_pattern("", false));
+    }
+
+    #[test]
+    fn words_match_glob() {
+        assert!(glob_matches("foo bar", "foo", true));
+        assert!(glob_matches("Foo bar", "foo", true));
+        assert!(!glob_matches("foobar", "foo", true));
+        assert!(glob_matches("foo bar", "foo*", true));
+        assert!(glob_matches("foobar", "foo*", true));
+        assert!(!glob_matches("barfoo", "foo*", true));
+        assert!(glob_matches("hello foobar!", "foo*", true));
+        assert!(glob_matches("hello foobar!", "*bar", true));
+        assert!(glob_matches("cat and hat", "?at", true));
+        assert!(!glob_matches("that", "?at", true));
+        assert!(glob_matches("is it 2021?", "20[0-9][0-9]", true));
+        assert!(!glob_matches("is it 20xx?", "20[0-9][0-9]", true));
+        assert!(!glob_matches("is it 20210?", "20[0-9][0-9]", true));
+        assert!(glob_matches("@room, hello", "@room", true));
+        assert!(glob_matches("hello foo", "foo", true));
+        assert!(!glob_matches("hello foofoo", "foo", true));
+        assert!(glob_matches("", "", true));
+        assert!(!glob_matches("foo", "", true));
+    }
+
+    #[test]
+    fn whole_value_match_glob() {
+        assert!(!glob_matches("foo bar", "foo", false));
+        assert!(glob_matches("foo", "foo", false));
+        assert!(glob_matches("foo", "foo*", false));
+        assert!(glob_matches("foobar", "foo*", false));
+        assert!(glob_matches("foo bar", "foo*", false));
+        assert!(glob_matches("foo bar", "foo**", false));
+        assert!(!glob_matches("foo", "foo?", false));
+        assert!(glob_matches("foo", "fo?", false));
+        assert!(glob_matches("FOO", "foo", false));
+        assert!(glob_matches("", "", false));
+        assert!(glob_matches("", "*", false));
+        assert!(!glob_matches("foo", "", false));
+        assert!(glob_matches("m.notice", "m.*ice", false));
+        assert!(glob_matches("abcbc", "*bc", false));
+        assert!(!glob_matches("abcb", "*bc", false));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(glob_matches("cat", "[bc]at", false));
+        assert!(!glob_matches("hat", "[bc]at", false));
+        assert!(glob_matches("hat", "[!bc]at", false));
+        assert!(!glob_matches("cat", "[!bc]at", false));
+        assert!(glob_matches("Cat", "[a-z]at", false));
+        assert!(glob_matches("cat", "[A-Z]at", false));
+        assert!(glob_matches("-at", "[a-]at", false));
+        assert!(glob_matches("]at", "[]]at", false));
+        assert!(glob_matches("!at", "[]!]at", false));
+        assert!(glob_matches("[at", "[at", false));
+        assert!(!glob_matches("cat", "[at", false));
+    }
+
+    #[test]
+    fn cached_matches() {
+        assert!(matches_cached("foo*", false, "foobar"));
+        assert!(!matches_cached("foo*", false, "barfoo"));
+        assert!(matches_cached("foo*", true, "bar foobar"));
+        assert!(!matches_cached("foo*", true, "bar barfoo"));
+        assert!(!matches_cached("foo*", false, "bar foobar"));
+
+        // Literal texts are cached separately from patterns, since `*` is not a wildcard in them.
+        assert!(matches_literal_cached("foo*", "bar foo*"));
+        assert!(!matches_literal_cached("foo*", "bar foobar"));
+        assert!(matches_literal_cached("Foo", "bar FOO!"));
+    }
+}
@@ -0,0 +1,61 @@
+//! `Serialize` and `Deserialize` implementations for `PushCondition`.
+
+use serde::{de, Deserialize, Serialize, Serializer};
+use serde_json::value::RawValue as RawJsonValue;
+
+use super::PushCondition;
+
+/// Helper struct to determine the kind of a push condition from a `serde_json::value::RawValue`.
+#[derive(Deserialize)]
+struct PushConditionDeHelper {
+    kind: String,
+}
+
+impl PushCondition {
+    /// Whether the given kind is the kind of one of the known variants of `PushCondition`.
+    fn is_known_kind(kind: &str) -> bool {
+        match kind {
+            "event_match"
+            | "contains_display_name"
+            | "room_member_count"
+            | "sender_notification_permission"
+            | "event_property_is"
+            | "event_property_contains" => true,
+            #[cfg(feature = "unstable-pre-spec")]
+            "related_event_match" | "im.nheko.msc3664.related_event_match" => true,
+            _ => false,
+        }
+    }
+}
+
+impl Serialize for PushCondition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::_Custom(custom) => custom.serialize(serializer),
+            // Uses the implementation generated with `#[serde(remote = "Self")]`.
+            _ => Self::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PushCondition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let json = Box::<RawJsonValue>::deserialize(deserializer)?;
+        let PushConditionDeHelper { kind } =
+            serde_json::from_str(json.get()).map_err(de::Error::custom)?;
+
+        if Self::is_known_kind(&kind) {
+            // Uses the implementation generated with `#[serde(remote = "Self")]`.
+            Self::deserialize(&mut serde_json::Deserializer::from_str(json.get()))
+                .map_err(de::Error::custom)
+        } else {
+            serde_json::from_str(json.get()).map(Self::_Custom).map_err(de::Error::custom)
+        }
+    }
+}
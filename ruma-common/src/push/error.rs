@@ -0,0 +1,118 @@
+//! Errors of the push rule editing methods of `Ruleset` and of pusher validation.
+
+use std::{error::Error, fmt};
+
+/// An error that happens when inserting a push rule with `Ruleset::insert`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum InsertPushRuleError {
+    /// The rule ID starts with a dot (`.`), which is reserved for server-default rules.
+    ServerDefaultRuleId,
+
+    /// The rule ID contains a forward slash (`/`) or a backslash (`\`).
+    InvalidRuleId,
+
+    /// The rule given in `before` or `after` doesn't exist.
+    UnknownRuleId,
+
+    /// The rule given in `before` or `after` is a server-default rule.
+    ///
+    /// Rules can only be positioned relative to user-defined rules.
+    RelativeToServerDefaultRule,
+
+    /// The rule given in `before` has a higher priority than the rule given in `after`.
+    BeforeHigherThanAfter,
+}
+
+impl fmt::Display for InsertPushRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServerDefaultRuleId => {
+                write!(f, "rule IDs starting with a dot are reserved for server-default rules")
+            }
+            Self::InvalidRuleId => write!(f, "rule ID contains a slash or a backslash"),
+            Self::UnknownRuleId => write!(f, "rule given in `before` or `after` doesn't exist"),
+            Self::RelativeToServerDefaultRule => {
+                write!(f, "cannot position a rule relative to a server-default rule")
+            }
+            Self::BeforeHigherThanAfter => {
+                write!(f, "rule given in `before` has a higher priority than the one in `after`")
+            }
+        }
+    }
+}
+
+impl Error for InsertPushRuleError {}
+
+/// An error that happens when removing a push rule with `Ruleset::remove`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RemovePushRuleError {
+    /// The rule is a server-default rule, which can't be removed.
+    ServerDefault,
+
+    /// The rule doesn't exist.
+    NotFound,
+}
+
+impl fmt::Display for RemovePushRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServerDefault => write!(f, "server-default rules cannot be removed"),
+            Self::NotFound => write!(f, "push rule not found"),
+        }
+    }
+}
+
+impl Error for RemovePushRuleError {}
+
+/// An error that happens when a push rule to update doesn't exist.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct RuleNotFoundError;
+
+impl fmt::Display for RuleNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "push rule not found")
+    }
+}
+
+impl Error for RuleNotFoundError {}
+
+/// An error that happens when the URL of an HTTP pusher is not valid, with
+/// `PusherData::validate_url`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum PusherUrlError {
+    /// The pusher doesn't have a URL.
+    Missing,
+
+    /// The URL doesn't use the `https` scheme.
+    UnsupportedScheme,
+
+    /// The URL doesn't have a host.
+    MissingHost,
+
+    /// The path of the URL doesn't end in `/_matrix/push/v1/notify`, or the URL has a query or a
+    /// fragment.
+    InvalidPath,
+}
+
+impl fmt::Display for PusherUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "pusher URL is missing"),
+            Self::UnsupportedScheme => write!(f, "pusher URL must use the https scheme"),
+            Self::MissingHost => write!(f, "pusher URL doesn't have a host"),
+            Self::InvalidPath => {
+                write!(
+                    f,
+                    "pusher URL path must end in `{}`, without a query or fragment",
+                    super::PUSH_GATEWAY_NOTIFY_PATH
+                )
+            }
+        }
+    }
+}
+
+impl Error for PusherUrlError {}
@@ -0,0 +1,184 @@
+use super::{Action, RuleKind, Tweak};
+
+/// How a user should be notified about an event, according to the push rule that matched it.
+///
+/// This is the result of `Ruleset::evaluate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct NotificationOutcome {
+    /// Whether the event generates a notification.
+    ///
+    /// This is `true` if the actions contain `notify` or `coalesce`, and `false` if they contain
+    /// `dont_notify` or no action at all.
+    pub notify: bool,
+
+    /// Whether the event should be highlighted.
+    ///
+    /// This is the value of the `highlight` tweak, and always `false` if the event doesn't
+    /// generate a notification.
+    pub highlight: bool,
+
+    /// The sound to play for the notification, from the `sound` tweak.
+    ///
+    /// This is always `None` if the event doesn't generate a notification.
+    pub sound: Option<String>,
+
+    /// The kind and ID of the push rule that matched the event.
+    pub matched_rule: (RuleKind, String),
+}
+
+impl NotificationOutcome {
+    /// Creates a new `NotificationOutcome` by interpreting the actions of the push rule that
+    /// matched an event.
+    ///
+    /// If the actions contain several tweaks of the same kind, the last one is used.
+    pub fn from_actions(actions: &[Action], matched_rule: (RuleKind, String)) -> Self {
+        let mut notify = false;
+        let mut highlight = false;
+        let mut sound = None;
+
+        for action in actions {
+            match action {
+                Action::Notify | Action::Coalesce => notify = true,
+                Action::DontNotify => notify = false,
+                Action::SetTweak(Tweak::Highlight(value)) => highlight = *value,
+                Action::SetTweak(Tweak::Sound(value)) => sound = Some(value.clone()),
+                Action::SetTweak(Tweak::Custom { .. }) => {}
+            }
+        }
+
+        if !notify {
+            highlight = false;
+            sound = None;
+        }
+
+        Self { notify, highlight, sound, matched_rule }
+    }
+}
+
+/// How a user should be notified about an encrypted event, according to the push rules evaluated
+/// for the encrypted event and for the decrypted event.
+///
+/// This is the result of `Ruleset::evaluate_decrypted`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DecryptedEventOutcome {
+    /// The outcome for the encrypted event, like it was computed by the server.
+    pub encrypted: Option<NotificationOutcome>,
+
+    /// The outcome for the decrypted event.
+    pub decrypted: Option<NotificationOutcome>,
+
+    /// The kinds and IDs of the enabled rules that apply to only one of the encrypted and the
+    /// decrypted events, from the highest to the lowest priority.
+    pub changed_rules: Vec<(RuleKind, String)>,
+}
+
+impl DecryptedEventOutcome {
+    /// Whether the decrypted event generates a notification.
+    pub fn notify(&self) -> bool {
+        matches!(&self.decrypted, Some(outcome) if outcome.notify)
+    }
+
+    /// Whether the decrypted event should be highlighted.
+    pub fn highlight(&self) -> bool {
+        matches!(&self.decrypted, Some(outcome) if outcome.highlight)
+    }
+
+    /// Whether the decrypted event should notify the user differently than the encrypted event,
+    /// i.e. whether the notification, the highlight or the sound changed.
+    pub fn outcome_changed(&self) -> bool {
+        fn notification(outcome: &Option<NotificationOutcome>) -> (bool, bool, Option<&str>) {
+            match outcome {
+                Some(outcome) => (outcome.notify, outcome.highlight, outcome.sound.as_deref()),
+                None => (false, false, None),
+            }
+        }
+
+        notification(&self.encrypted) != notification(&self.decrypted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, DecryptedEventOutcome, NotificationOutcome, RuleKind, Tweak};
+
+    fn outcome(actions: &[Action]) -> NotificationOutcome {
+        NotificationOutcome::from_actions(actions, (RuleKind::Override, "rule".into()))
+    }
+
+    #[test]
+    fn notify_with_tweaks() {
+        let outcome = outcome(&[
+            Action::Notify,
+            Action::SetTweak(Tweak::Sound("default".into())),
+            Action::SetTweak(Tweak::Highlight(true)),
+        ]);
+
+        assert!(outcome.notify);
+        assert!(outcome.highlight);
+        assert_eq!(outcome.sound.as_deref(), Some("default"));
+        assert_eq!(outcome.matched_rule, (RuleKind::Override, "rule".into()));
+    }
+
+    #[test]
+    fn coalesce_notifies() {
+        let outcome = outcome(&[Action::Coalesce, Action::SetTweak(Tweak::Highlight(false))]);
+
+        assert!(outcome.notify);
+        assert!(!outcome.highlight);
+        assert_eq!(outcome.sound, None);
+    }
+
+    #[test]
+    fn dont_notify_ignores_tweaks() {
+        let outcome = outcome(&[
+            Action::DontNotify,
+            Action::SetTweak(Tweak::Sound("default".into())),
+            Action::SetTweak(Tweak::Highlight(true)),
+        ]);
+
+        assert!(!outcome.notify);
+        assert!(!outcome.highlight);
+        assert_eq!(outcome.sound, None);
+    }
+
+    #[test]
+    fn no_actions() {
+        let outcome = outcome(&[]);
+
+        assert!(!outcome.notify);
+        assert!(!outcome.highlight);
+    }
+
+    #[test]
+    fn decrypted_event_outcome_changed() {
+        let encrypted = outcome(&[Action::Notify, Action::SetTweak(Tweak::Highlight(false))]);
+        let decrypted = outcome(&[Action::Notify]);
+        let unchanged = DecryptedEventOutcome {
+            encrypted: Some(encrypted.clone()),
+            decrypted: Some(decrypted),
+            changed_rules: vec![],
+        };
+        assert!(unchanged.notify());
+        assert!(!unchanged.highlight());
+        assert!(!unchanged.outcome_changed());
+
+        let decrypted = outcome(&[Action::Notify, Action::SetTweak(Tweak::Highlight(true))]);
+        let highlighted = DecryptedEventOutcome {
+            encrypted: Some(encrypted.clone()),
+            decrypted: Some(decrypted),
+            changed_rules: vec![],
+        };
+        assert!(highlighted.highlight());
+        assert!(highlighted.outcome_changed());
+
+        let muted = DecryptedEventOutcome {
+            encrypted: Some(encrypted),
+            decrypted: None,
+            changed_rules: vec![],
+        };
+        assert!(!muted.notify());
+        assert!(muted.outcome_changed());
+    }
+}
@@ -27,8 +27,10 @@ impl Ruleset {
                 ConditionalPushRule::suppress_notices(),
                 ConditionalPushRule::invite_for_me(user_id),
                 ConditionalPushRule::member_event(),
+                ConditionalPushRule::is_user_mention(user_id),
                 ConditionalPushRule::contains_display_name(),
                 ConditionalPushRule::tombstone(),
+                ConditionalPushRule::is_room_mention(),
                 ConditionalPushRule::roomnotif(),
                 ConditionalPushRule::reaction(),
             ],
@@ -38,8 +40,10 @@ impl Ruleset {
                 ConditionalPushRule::suppress_notices(),
                 ConditionalPushRule::invite_for_me(user_id),
                 ConditionalPushRule::member_event(),
+                ConditionalPushRule::is_user_mention(user_id),
                 ConditionalPushRule::contains_display_name(),
                 ConditionalPushRule::tombstone(),
+                ConditionalPushRule::is_room_mention(),
                 ConditionalPushRule::roomnotif(),
             ],
             underride: indexset![
@@ -112,8 +116,29 @@ impl ConditionalPushRule {
         }
     }
 
+    /// Matches any message which contains the user's Matrix ID in the list of `user_ids` under the
+    /// `m.mentions` property.
+    pub fn is_user_mention(user_id: &UserId) -> Self {
+        Self {
+            actions: vec![
+                Notify,
+                SetTweak(Tweak::Sound("default".into())),
+                SetTweak(Tweak::Highlight(true)),
+            ],
+            default: true,
+            enabled: true,
+            rule_id: ".m.rule.is_user_mention".into(),
+            conditions: vec![EventPropertyContains {
+                key: r"content.m\.mentions.user_ids".into(),
+                value: user_id.as_str().into(),
+            }],
+        }
+    }
+
     /// Matches any message whose content is unencrypted and contains the user's current display
     /// name in the room in which it was sent.
+    ///
+    /// This rule is ignored for events that have an `m.mentions` property.
     pub fn contains_display_name() -> Self {
         Self {
             actions: vec![
@@ -144,8 +169,25 @@ impl ConditionalPushRule {
         }
     }
 
+    /// Matches any message whose `room` property under `m.mentions` is `true`, signifying the
+    /// whole room should be notified of the event, if the sender has the permission to do so.
+    pub fn is_room_mention() -> Self {
+        Self {
+            actions: vec![Notify, SetTweak(Tweak::Highlight(true))],
+            default: true,
+            enabled: true,
+            rule_id: ".m.rule.is_room_mention".into(),
+            conditions: vec![
+                EventPropertyIs { key: r"content.m\.mentions.room".into(), value: true.into() },
+                SenderNotificationPermission { key: "room".into() },
+            ],
+        }
+    }
+
     /// Matches any message whose content is unencrypted and contains the text `@room`, signifying
     /// the whole room should be notified of the event.
+    ///
+    /// This rule is ignored for events that have an `m.mentions` property.
     pub fn roomnotif() -> Self {
         Self {
             actions: vec![Notify, SetTweak(Tweak::Highlight(true))],
@@ -177,9 +219,11 @@ impl ConditionalPushRule {
 impl PatternedPushRule {
     /// Matches any message whose content is unencrypted and contains the local part of the user's
     /// Matrix ID, separated by word boundaries.
+    ///
+    /// This rule is ignored for events that have an `m.mentions` property.
     pub fn contains_user_name(user_id: &UserId) -> Self {
         Self {
-            rule_id: ".m.rules.contains_user_name".into(),
+            rule_id: ".m.rule.contains_user_name".into(),
             enabled: true,
             default: true,
             pattern: user_id.localpart().into(),
@@ -197,7 +241,7 @@ impl ConditionalPushRule {
     /// Matches any incoming VOIP call.
     pub fn call() -> Self {
         Self {
-            rule_id: ".m.rules.call".into(),
+            rule_id: ".m.rule.call".into(),
             default: true,
             enabled: true,
             conditions: vec![EventMatch { key: "type".into(), pattern: "m.call.invite".into() }],
@@ -215,7 +259,7 @@ impl ConditionalPushRule {
     /// events that are encrypted (in 1:1 rooms) or none.
     pub fn encrypted_room_one_to_one() -> Self {
         Self {
-            rule_id: ".m.rules.encrypted_room_one_to_one".into(),
+            rule_id: ".m.rule.encrypted_room_one_to_one".into(),
             default: true,
             enabled: true,
             conditions: vec![
@@ -233,7 +277,7 @@ impl ConditionalPushRule {
     /// Matches any message sent in a room with exactly two members.
     pub fn room_one_to_one() -> Self {
         Self {
-            rule_id: ".m.rules.room_one_to_one".into(),
+            rule_id: ".m.rule.room_one_to_one".into(),
             default: true,
             enabled: true,
             conditions: vec![
@@ -251,7 +295,7 @@ impl ConditionalPushRule {
     /// Matches all chat messages.
     pub fn message() -> Self {
         Self {
-            rule_id: ".m.rules.message".into(),
+            rule_id: ".m.rule.message".into(),
             default: true,
             enabled: true,
             conditions: vec![EventMatch { key: "type".into(), pattern: "m.room.message".into() }],
@@ -265,7 +309,7 @@ impl ConditionalPushRule {
     /// rooms) or none.
     pub fn encrypted() -> Self {
         Self {
-            rule_id: ".m.rules.encrypted".into(),
+            rule_id: ".m.rule.encrypted".into(),
             default: true,
             enabled: true,
             conditions: vec![EventMatch { key: "type".into(), pattern: "m.room.encrypted".into() }],
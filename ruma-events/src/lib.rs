@@ -136,6 +136,7 @@ mod enums;
 mod error;
 mod event_kinds;
 mod event_type;
+mod mentions;
 
 // Hack to allow both ruma-events itself and external crates (or tests) to use procedural macros
 // that expect `ruma_events` to exist in the prelude.
@@ -206,6 +207,7 @@ pub use self::{
         SyncMessageEvent, SyncStateEvent, ToDeviceEvent,
     },
     event_type::EventType,
+    mentions::Mentions,
 };
 
 /// Extra information about an event that is not incorporated into the event's
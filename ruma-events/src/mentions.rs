@@ -0,0 +1,40 @@
+//! Types for the `m.mentions` property of message contents.
+
+use std::collections::BTreeSet;
+
+use ruma_identifiers::UserId;
+use serde::{Deserialize, Serialize};
+
+/// Describes whether the event mentions other users or the room.
+///
+/// This is the `m.mentions` property of the content of message events. When it is present, the
+/// server-default push rules only look at it to decide whether the event mentions the user, and
+/// ignore the user's display name, user name and `@room` in the body of the message.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct Mentions {
+    /// The user IDs of the users that are mentioned by this event.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub user_ids: BTreeSet<UserId>,
+
+    /// Whether the whole room is mentioned by this event.
+    #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+    pub room: bool,
+}
+
+impl Mentions {
+    /// Create a `Mentions` that doesn't mention anyone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a `Mentions` for the given user IDs.
+    pub fn with_user_ids(user_ids: impl IntoIterator<Item = UserId>) -> Self {
+        Self { user_ids: user_ids.into_iter().collect(), ..Default::default() }
+    }
+
+    /// Create a `Mentions` for a room mention.
+    pub fn with_room_mention() -> Self {
+        Self { room: true, ..Default::default() }
+    }
+}
@@ -16,6 +16,7 @@ use super::relationships::{Annotation, Reference, RelationJsonRepr, Replacement}
 use super::{relationships::RelatesToJsonRepr, EncryptedFile, ImageInfo, ThumbnailInfo};
 #[cfg(feature = "unstable-pre-spec")]
 use crate::key::verification::VerificationMethod;
+use crate::Mentions;
 
 // FIXME: Do we want to keep re-exporting this?
 pub use super::relationships::InReplyTo;
@@ -52,6 +53,13 @@ pub struct MessageEventContent {
     #[cfg(feature = "unstable-pre-spec")]
     #[serde(rename = "m.new_content", skip_serializing_if = "Option::is_none")]
     pub new_content: Option<Box<MessageEventContent>>,
+
+    /// The users and the room mentioned by this message.
+    ///
+    /// For an edit, this should only contain the mentions that weren't already in the original
+    /// message.
+    #[serde(rename = "m.mentions", skip_serializing_if = "Option::is_none")]
+    pub mentions: Option<Mentions>,
 }
 
 impl MessageEventContent {
@@ -62,6 +70,7 @@ impl MessageEventContent {
             relates_to: None,
             #[cfg(feature = "unstable-pre-spec")]
             new_content: None,
+            mentions: None,
         }
     }
 
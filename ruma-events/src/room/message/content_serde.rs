@@ -6,9 +6,10 @@ use serde_json::value::RawValue as RawJsonValue;
 use crate::{
     from_raw_json_value,
     room::message::{MessageEventContent, MessageType, Relation},
+    Mentions,
 };
 
-/// Helper struct to determine the msgtype, relates_to and new_content fields
+/// Helper struct to determine the msgtype, relates_to, new_content and mentions fields
 /// from a `serde_json::value::RawValue`
 #[derive(Debug, Deserialize)]
 struct MessageContentDeHelper {
@@ -18,6 +19,9 @@ struct MessageContentDeHelper {
     #[cfg(feature = "unstable-pre-spec")]
     #[serde(rename = "m.new_content")]
     new_content: Option<Box<MessageEventContent>>,
+
+    #[serde(rename = "m.mentions")]
+    mentions: Option<Mentions>,
 }
 
 impl<'de> de::Deserialize<'de> for MessageEventContent {
@@ -33,6 +37,7 @@ impl<'de> de::Deserialize<'de> for MessageEventContent {
             relates_to: helper.relates_to,
             #[cfg(feature = "unstable-pre-spec")]
             new_content: helper.new_content,
+            mentions: helper.mentions,
         })
     }
 }
@@ -3,7 +3,7 @@
 use std::collections::BTreeMap;
 
 use js_int::{int, Int};
-use ruma_common::power_levels::default_power_level;
+use ruma_common::{power_levels::default_power_level, push::PushConditionPowerLevels};
 use ruma_events_macros::StateEventContent;
 use ruma_identifiers::UserId;
 use serde::{Deserialize, Serialize};
@@ -135,6 +135,12 @@ impl Default for PowerLevelsEventContent {
     }
 }
 
+impl From<PowerLevelsEventContent> for PushConditionPowerLevels {
+    fn from(power_levels: PowerLevelsEventContent) -> Self {
+        Self::new(power_levels.users, power_levels.users_default, power_levels.notifications)
+    }
+}
+
 /// Used with `#[serde(skip_serializing_if)]` to omit default power levels.
 #[allow(clippy::trivially_copy_pass_by_ref)]
 fn is_default_power_level(l: &Int) -> bool {
@@ -149,14 +155,32 @@ mod tests {
     };
 
     use assign::assign;
-    use js_int::int;
+    use js_int::{int, uint};
     use maplit::btreemap;
+    use ruma_common::push::PushConditionRoomCtx;
     use ruma_identifiers::{event_id, room_id, user_id};
     use serde_json::{json, to_value as to_json_value};
 
     use super::{default_power_level, NotificationPowerLevels, PowerLevelsEventContent};
     use crate::{EventType, StateEvent, Unsigned};
 
+    #[test]
+    fn push_condition_power_levels() {
+        let power_levels = assign!(PowerLevelsEventContent::default(), {
+            users: btreemap! { user_id!("@carl:example.com") => int!(100) },
+            users_default: int!(10),
+            notifications: assign!(NotificationPowerLevels::new(), { room: int!(75) }),
+        });
+
+        let context =
+            PushConditionRoomCtx::new(room_id!("!n8f893n9:example.com"), uint!(5), "Carl".into())
+                .with_power_levels(power_levels.into());
+
+        assert_eq!(context.users_power_levels[&user_id!("@carl:example.com")], int!(100));
+        assert_eq!(context.default_power_level, int!(10));
+        assert_eq!(context.notification_power_levels.room, int!(75));
+    }
+
     #[test]
     fn serialization_with_optional_fields_as_none() {
         let default = default_power_level();
@@ -4,7 +4,7 @@ use ruma_events_macros::MessageEventContent;
 use ruma_identifiers::MxcUri;
 use serde::{Deserialize, Serialize};
 
-use crate::{room::ImageInfo, MessageEvent};
+use crate::{room::ImageInfo, Mentions, MessageEvent};
 
 /// A sticker message.
 pub type StickerEvent = MessageEvent<StickerEventContent>;
@@ -23,4 +23,8 @@ pub struct StickerEventContent {
 
     /// The URL to the sticker image. This must be a valid `mxc://` URI.
     pub url: MxcUri,
+
+    /// The users and the room mentioned by this sticker.
+    #[serde(rename = "m.mentions", skip_serializing_if = "Option::is_none")]
+    pub mentions: Option<Mentions>,
 }
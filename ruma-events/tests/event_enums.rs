@@ -88,6 +88,7 @@ fn serialize_message_event() {
                 blurhash: None,
             },
             url: mxc_uri!("mxc://matrix.org/arsrns98rsRSR"),
+            mentions: None,
         },
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
@@ -34,6 +34,7 @@ fn message_serialize_sticker() {
                 blurhash: None,
             },
             url: mxc_uri!("mxc://matrix.org/rnsldl8srs98IRrs"),
+            mentions: None,
         }),
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
@@ -195,6 +196,7 @@ fn deserialize_message_sticker() {
                     blurhash: None,
                 },
                 url,
+                mentions: None,
             }),
             event_id,
             origin_server_ts,
@@ -14,7 +14,7 @@ use ruma_events::{
         },
         relationships::InReplyTo,
     },
-    Unsigned,
+    Mentions, Unsigned,
 };
 #[cfg(feature = "unstable-pre-spec")]
 use ruma_identifiers::DeviceIdBox;
@@ -186,6 +186,73 @@ fn relates_to_content_serialization() {
     assert_eq!(to_json_value(&message_event_content).unwrap(), json_data);
 }
 
+#[test]
+fn mentions_content_serialization() {
+    let message_event_content = assign!(MessageEventContent::text_plain("Hey @room and Alice!"), {
+        mentions: Some(assign!(Mentions::with_user_ids(vec![user_id!("@alice:example.com")]), {
+            room: true,
+        })),
+    });
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Hey @room and Alice!",
+            "msgtype": "m.text",
+            "m.mentions": {
+                "user_ids": ["@alice:example.com"],
+                "room": true
+            }
+        })
+    );
+
+    let message_event_content = assign!(MessageEventContent::text_plain("Hello"), {
+        mentions: Some(Mentions::new()),
+    });
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Hello",
+            "msgtype": "m.text",
+            "m.mentions": {}
+        })
+    );
+}
+
+#[test]
+fn mentions_content_deserialization() {
+    let json_data = json!({
+        "body": "Hey Alice!",
+        "msgtype": "m.text",
+        "m.mentions": {
+            "user_ids": ["@alice:example.com"]
+        }
+    });
+
+    let content = from_json_value::<MessageEventContent>(json_data).unwrap();
+    let mentions = content.mentions.unwrap();
+    assert!(mentions.user_ids.contains(&user_id!("@alice:example.com")));
+    assert!(!mentions.room);
+
+    let json_data = json!({
+        "body": "Hello",
+        "msgtype": "m.text",
+        "m.mentions": {}
+    });
+
+    let content = from_json_value::<MessageEventContent>(json_data).unwrap();
+    assert_eq!(content.mentions, Some(Mentions::new()));
+
+    let json_data = json!({
+        "body": "Hello",
+        "msgtype": "m.text"
+    });
+
+    let content = from_json_value::<MessageEventContent>(json_data).unwrap();
+    assert_eq!(content.mentions, None);
+}
+
 #[test]
 #[cfg(not(feature = "unstable-pre-spec"))]
 fn edit_deserialization_061() {
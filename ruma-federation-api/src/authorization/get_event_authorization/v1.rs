@@ -30,6 +30,8 @@ ruma_api! {
         /// and their authorization events, recursively.
         pub auth_chain: Vec<Raw<Pdu>>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
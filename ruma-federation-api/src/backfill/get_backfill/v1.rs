@@ -43,6 +43,8 @@ ruma_api! {
         /// List of persistent updates to rooms.
         pub pdus: Vec<Raw<Pdu>>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -34,6 +34,8 @@ ruma_api! {
         /// The user's devices. May be empty.
         pub devices: Vec<UserDevice>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -18,9 +18,11 @@ ruma_api! {
 
     response: {
         /// The server name to delegate server-server communciations to, with optional port.
-        #[serde(rename = "m.homeserver")]
+        #[serde(rename = "m.server")]
         pub homeserver: ServerNameBox,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -37,6 +37,8 @@ ruma_api! {
         /// The queried server's keys, signed by the notary server.
         pub server_keys: Vec<ServerSigningKeys>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -28,7 +28,6 @@ ruma_api! {
         ///
         /// The notary server may return multiple keys regardless of the Key IDs
         /// given.
-        #[ruma_api(body)]
         pub server_keys: BTreeMap<ServerNameBox, BTreeMap<ServerSigningKeyId, QueryCriteria>>,
 
         /// A millisecond POSIX timestamp in milliseconds indicating when the
@@ -46,6 +45,8 @@ ruma_api! {
         /// The queried server's keys, signed by the notary server.
         pub server_keys: Vec<ServerSigningKeys>,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -22,6 +22,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub server_key: ServerSigningKeys,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -7,8 +7,8 @@ ruma_api! {
     metadata: {
         description: "Get the implementation name and version of this homeserver.",
         method: GET,
-        name: "discover_homeserver",
-        path: "/.well-known/matrix/server",
+        name: "get_server_version",
+        path: "/_matrix/federation/v1/version",
         rate_limited: false,
         authentication: None,
     }
@@ -22,6 +22,8 @@ ruma_api! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub server: Option<Server>,
     }
+
+    error: crate::Error
 }
 
 impl Request {
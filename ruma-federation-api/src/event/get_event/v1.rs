@@ -35,6 +35,8 @@ ruma_api! {
         #[serde(rename = "pdus", with = "ruma_serde::single_element_seq")]
         pub pdu: Raw<Pdu>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -41,6 +41,8 @@ ruma_api! {
         /// The missing PDUs.
         pub events: Vec<Raw<Pdu>>
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
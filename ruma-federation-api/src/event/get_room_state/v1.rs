@@ -33,6 +33,8 @@ ruma_api! {
         /// The fully resolved state of the room at the given event.
         pub pdus: Vec<Raw<Pdu>>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
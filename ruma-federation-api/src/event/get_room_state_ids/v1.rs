@@ -31,6 +31,8 @@ ruma_api! {
         /// The fully resolved state of the room at the given event.
         pub pdu_ids: Vec<EventId>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -26,6 +26,8 @@ ruma_api! {
         /// One-time keys for the queried devices
         pub one_time_keys: OneTimeKeys,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -26,6 +26,8 @@ ruma_api! {
         /// Keys from the queried devices.
         pub device_keys: BTreeMap<UserId, BTreeMap<DeviceIdBox, DeviceKeys>>,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -12,7 +12,7 @@ ruma_api! {
     metadata: {
         description: "Invites a remote user to a room.",
         method: PUT,
-        name: "create_invite",
+        name: "create_invite_v1",
         path: "/_matrix/federation/v1/invite/:room_id/:event_id",
         rate_limited: false,
         authentication: ServerSignatures,
@@ -58,6 +58,8 @@ ruma_api! {
         #[serde(with = "crate::serde::v1_pdu")]
         pub event: Raw<Pdu>,
     }
+
+    error: crate::Error
 }
 
 /// Information included alongside an event that is not signed.
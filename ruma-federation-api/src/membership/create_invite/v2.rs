@@ -9,7 +9,7 @@ ruma_api! {
     metadata: {
         description: "Invites a remote user to a room.",
         method: PUT,
-        name: "create_invite",
+        name: "create_invite_v2",
         path: "/_matrix/federation/v2/invite/:room_id/:event_id",
         rate_limited: false,
         authentication: ServerSignatures,
@@ -38,6 +38,8 @@ ruma_api! {
         /// The signed invite event.
         pub event: Raw<Pdu>,
     }
+
+    error: crate::Error
 }
 
 impl Request {
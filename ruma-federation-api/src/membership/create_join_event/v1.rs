@@ -10,7 +10,7 @@ use super::RoomState;
 ruma_api! {
     metadata: {
         description: "Send a join event to a resident server.",
-        name: "create_join_event",
+        name: "create_join_event_v1",
         method: PUT,
         path: "/_matrix/federation/v1/send_join/:room_id/:event_id",
         rate_limited: false,
@@ -39,6 +39,8 @@ ruma_api! {
         #[serde(with = "crate::serde::v1_pdu")]
         pub room_state: RoomState,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
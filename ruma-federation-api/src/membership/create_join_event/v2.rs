@@ -10,7 +10,7 @@ use super::RoomState;
 ruma_api! {
     metadata: {
         description: "Send a join event to a resident server.",
-        name: "create_join_event",
+        name: "create_join_event_v2",
         method: PUT,
         path: "/_matrix/federation/v2/send_join/:room_id/:event_id",
         rate_limited: false,
@@ -38,6 +38,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub room_state: RoomState,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -40,6 +40,8 @@ ruma_api! {
         /// An unsigned template event.
         pub event: Raw<Pdu>,
     }
+
+    error: crate::Error
 }
 
 fn default_ver() -> Vec<RoomVersionId> {
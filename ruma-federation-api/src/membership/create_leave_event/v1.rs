@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 ruma_api! {
     metadata: {
         description: "Submits a signed leave event to the receiving server for it to accept it into the room's graph.",
-        name: "create_leave_event",
+        name: "create_leave_event_v1",
         method: PUT,
         path: "/_matrix/federation/v1/send_leave/:room_id/:event_id",
         rate_limited: false,
@@ -29,33 +29,26 @@ ruma_api! {
         pub event_id: &'a EventId,
 
         /// The user ID of the leaving member.
-        #[ruma_api(query)]
         pub sender: &'a UserId,
 
         /// The name of the leaving homeserver.
-        #[ruma_api(query)]
         pub origin: &'a ServerName,
 
         /// A timestamp added by the leaving homeserver.
-        #[ruma_api(query)]
         #[serde(with = "ruma_serde::time::ms_since_unix_epoch")]
         pub origin_server_ts: SystemTime,
 
         /// The value `m.room.member`.
-        #[ruma_api(query)]
         #[serde(rename = "type")]
         pub event_type: EventType,
 
         /// The user ID of the leaving member.
-        #[ruma_api(query)]
         pub state_key: &'a str,
 
         /// The content of the event.
-        #[ruma_api(query)]
         pub content: Raw<MemberEventContent>,
 
         /// This field must be present but is ignored; it may be 0.
-        #[ruma_api(query)]
         pub depth: UInt,
     }
 
@@ -68,6 +61,8 @@ ruma_api! {
         #[serde(with = "crate::serde::v1_pdu")]
         pub empty: Empty,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -37,6 +37,8 @@ ruma_api! {
         /// room version - check the room version specification for precise event formats.
         pub event: EventTemplate,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -23,6 +23,8 @@ ruma_api! {
         /// The Matrix User ID who generated the token.
         pub sub: UserId,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
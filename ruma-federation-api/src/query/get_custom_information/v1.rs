@@ -30,6 +30,8 @@ ruma_api! {
         #[ruma_api(body)]
         pub body: JsonValue,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -42,6 +42,8 @@ ruma_api! {
         )]
         pub avatar_url: Option<MxcUri>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
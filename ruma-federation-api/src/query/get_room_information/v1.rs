@@ -26,6 +26,8 @@ ruma_api! {
         /// An array of server names that are likely to hold the given room.
         pub servers: Vec<ServerNameBox>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -1,10 +1,9 @@
 //! [PUT /_matrix/federation/v1/3pid/onbind](https://matrix.org/docs/spec/server_server/r0.1.4#put-matrix-federation-v1-3pid-onbind)
 
-use std::collections::BTreeMap;
-
 use ruma_api::ruma_api;
 use ruma_common::thirdparty::Medium;
-use ruma_identifiers::{RoomId, ServerNameBox, ServerSigningKeyId, UserId};
+use ruma_events::room::member::SignedContent;
+use ruma_identifiers::{RoomId, UserId};
 use serde::{Deserialize, Serialize};
 
 ruma_api! {
@@ -32,6 +31,8 @@ ruma_api! {
     }
 
     response: {}
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -60,8 +61,8 @@ pub struct ThirdPartyInvite {
     /// The user ID that sent the invite.
     pub sender: UserId,
 
-    /// Signature from the identity server using a long-term private key.
-    pub signed: BTreeMap<ServerNameBox, BTreeMap<ServerSigningKeyId, String>>,
+    /// A block of content signed by the identity server using a long-term private key.
+    pub signed: SignedContent,
 }
 
 impl ThirdPartyInvite {
@@ -71,7 +72,7 @@ impl ThirdPartyInvite {
         mxid: UserId,
         room_id: RoomId,
         sender: UserId,
-        signed: BTreeMap<ServerNameBox, BTreeMap<ServerSigningKeyId, String>>,
+        signed: SignedContent,
     ) -> Self {
         Self { medium: Medium::Email, address, mxid, room_id, sender, signed }
     }
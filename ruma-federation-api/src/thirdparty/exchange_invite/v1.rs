@@ -1,7 +1,7 @@
 //! [PUT /_matrix/federation/v1/exchange_third_party_invite/{roomId}](https://matrix.org/docs/spec/server_server/r0.1.4#put-matrix-federation-v1-exchange-third-party-invite-roomid)
 
 use ruma_api::ruma_api;
-use ruma_events::{room::member::ThirdPartyInvite, EventType};
+use ruma_events::{room::member::MemberEventContent, EventType};
 use ruma_identifiers::{RoomId, UserId};
 
 ruma_api! {
@@ -30,11 +30,13 @@ ruma_api! {
         pub state_key: &'a UserId,
 
         /// The content of the invite event.
-        pub content: &'a ThirdPartyInvite,
+        pub content: &'a MemberEventContent,
     }
 
     #[derive(Default)]
     response: {}
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -43,7 +45,7 @@ impl<'a> Request<'a> {
         room_id: &'a RoomId,
         sender: &'a UserId,
         state_key: &'a UserId,
-        content: &'a ThirdPartyInvite,
+        content: &'a MemberEventContent,
     ) -> Self {
         Self { room_id, kind: EventType::RoomMember, sender, state_key, content }
     }
@@ -51,6 +51,8 @@ ruma_api! {
         #[serde(with = "crate::serde::pdu_process_response")]
         pub pdus: BTreeMap<EventId, Result<(), String>>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -0,0 +1,209 @@
+//! Checks the HTTP (de)serialization of every endpoint against the examples in `fixtures`, which
+//! are mostly taken from the specification.
+//!
+//! The fixture of an endpoint is found at the path of its module, e.g.
+//! `fixtures/event/get_event/v1.json` for `event::get_event::v1`.
+
+#![cfg(all(feature = "client", feature = "server"))]
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use js_int::uint;
+use ruma_api::test_utils::{check_request, round_trip_response, Fixture};
+use ruma_common::directory::{Filter, RoomNetwork};
+use ruma_events::{pdu::Pdu, EventType};
+use ruma_federation_api::{
+    membership::create_invite::v1::RequestInit as InviteRequestInit,
+    query::get_profile_information::v1::ProfileField,
+    thirdparty::bind_callback::v1::ThirdPartyInvite,
+};
+use ruma_identifiers::{event_id, room_alias_id, room_id, server_name, user_id, RoomVersionId};
+use ruma_serde::{Outgoing, Raw};
+use serde_json::from_value as from_json_value;
+
+/// Generates a test for each given endpoint that checks that the given request is serialized to
+/// the request of the endpoint's fixture, and that the fixture's response round-trips.
+///
+/// The request expression can refer to the endpoint's `Request` type directly. The JSON body of the
+/// fixture's request can be made available to it, for building complex fields, by adding the name
+/// to bind it to in parentheses after the test name.
+macro_rules! fixture_tests {
+    (
+        $(
+            $( #[$attr:meta] )*
+            $test:ident $( ($body:ident) )?: $( $module:ident )::+ => $request:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            $( #[$attr] )*
+            fn $test() {
+                use ruma_federation_api::$( $module )::+::{Request, Response};
+
+                let fixture = Fixture::from_json(include_str!(concat!(
+                    "fixtures" $( , "/", stringify!($module) )+, ".json"
+                )));
+                $( let $body = fixture.request.body.clone().unwrap(); )?
+
+                let _: <Request as Outgoing>::Incoming = check_request($request, &fixture.request);
+                let _: Response = round_trip_response(&fixture.response);
+            }
+        )*
+    };
+}
+
+fixture_tests! {
+    get_event_authorization: authorization::get_event_authorization::v1
+        => Request::new(&room_id!("!abc123:matrix.org"), &event_id!("$abc123:example.org")),
+    get_backfill: backfill::get_backfill::v1 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &[event_id!("$13:matrix.org"), event_id!("$14:matrix.org")],
+        uint!(2),
+    ),
+    get_devices: device::get_devices::v1 => Request::new(&user_id!("@alice:example.com")),
+    get_public_rooms: directory::get_public_rooms::v1 => {
+        let mut request = Request::new();
+        request.limit = Some(uint!(10));
+        request.since = Some("p1902");
+        request
+    },
+    get_public_rooms_filtered: directory::get_public_rooms_filtered::v1 => {
+        let mut request = Request::new();
+        request.limit = Some(uint!(10));
+        request.filter = Filter::new();
+        request.filter.generic_search_term = Some("foo");
+        request.room_network = RoomNetwork::ThirdParty("irc");
+        request
+    },
+    discover_homeserver: discovery::discover_homeserver => Request::new(),
+    get_remote_server_keys: discovery::get_remote_server_keys::v2
+        => Request::new(&server_name!("example.org"), ts(1_234_567_890)),
+    get_remote_server_keys_batch(body): discovery::get_remote_server_keys_batch::v2
+        => Request::new(from_json_value(body["server_keys"].clone()).unwrap(), ts(1_234_567_890)),
+    get_server_keys: discovery::get_server_keys::v2 => Request::new(),
+    get_server_version: discovery::get_server_version::v1 => Request::new(),
+    get_event: event::get_event::v1 => Request::new(&event_id!("$abc123:example.org")),
+    get_missing_events: event::get_missing_events::v1 => {
+        let mut request = Request::new(
+            leak(room_id!("!abc123:matrix.org")),
+            leak([event_id!("$missing_event:example.org")]),
+            leak([event_id!("$event_that_has_the_missing_event_as_a_previous_event:example.org")]),
+        );
+        request.limit = uint!(20);
+        request
+    },
+    get_room_state: event::get_room_state::v1
+        => Request::new(&event_id!("$helloworld:matrix.org"), &room_id!("!abc123:matrix.org")),
+    get_room_state_ids: event::get_room_state_ids::v1
+        => Request::new(&event_id!("$helloworld:matrix.org"), &room_id!("!abc123:matrix.org")),
+    claim_keys(body): keys::claim_keys::v1
+        => Request::new(from_json_value(body["one_time_keys"].clone()).unwrap()),
+    get_keys(body): keys::get_keys::v1
+        => Request::new(from_json_value(body["device_keys"].clone()).unwrap()),
+    create_invite_v1(body): membership::create_invite::v1 => Request::from(InviteRequestInit {
+        room_id: &room_id!("!abc123:matrix.org"),
+        event_id: &event_id!("$abc123:example.org"),
+        sender: &user_id!("@someone:example.org"),
+        origin: &server_name!("matrix.org"),
+        origin_server_ts: ts(1_234_567_890),
+        state_key: &user_id!("@joe:elsewhere.com"),
+        content: from_json_value(body["content"].clone()).unwrap(),
+        unsigned: from_json_value(body["unsigned"].clone()).unwrap(),
+    }),
+    create_invite_v2(body): membership::create_invite::v2 => Request::new(
+        room_id!("!abc123:matrix.org"),
+        event_id!("$abc123:example.org"),
+        RoomVersionId::Version2,
+        from_json_value(body["event"].clone()).unwrap(),
+        from_json_value(body["invite_room_state"].clone()).unwrap(),
+    ),
+    // The room state only contains the origin without `unstable-pre-spec`.
+    #[cfg(not(feature = "unstable-pre-spec"))]
+    create_join_event_v1(body): membership::create_join_event::v1 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &event_id!("$abc123:example.org"),
+        from_json_value(body).unwrap(),
+    ),
+    #[cfg(not(feature = "unstable-pre-spec"))]
+    create_join_event_v2(body): membership::create_join_event::v2 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &event_id!("$abc123:example.org"),
+        from_json_value(body).unwrap(),
+    ),
+    create_join_event_template: membership::create_join_event_template::v1 => {
+        let mut request = Request::new(
+            leak(room_id!("!abc123:matrix.org")),
+            leak(user_id!("@someone:example.org")),
+        );
+        request.ver = leak([RoomVersionId::Version1, RoomVersionId::Version2]);
+        request
+    },
+    create_leave_event_v1(body): membership::create_leave_event::v1 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &event_id!("$abc123:example.org"),
+        &user_id!("@someone:example.org"),
+        &server_name!("matrix.org"),
+        ts(1_234_567_890),
+        EventType::RoomMember,
+        "@someone:example.org",
+        from_json_value(body["content"].clone()).unwrap(),
+        uint!(12),
+    ),
+    create_leave_event_v2(body): membership::create_leave_event::v2 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &event_id!("$abc123:example.org"),
+        &user_id!("@someone:example.org"),
+        &server_name!("matrix.org"),
+        ts(1_234_567_890),
+        EventType::RoomMember,
+        "@someone:example.org",
+        from_json_value(body["content"].clone()).unwrap(),
+        uint!(12),
+    ),
+    get_leave_event: membership::get_leave_event::v1
+        => Request::new(&room_id!("!abc123:matrix.org"), &user_id!("@someone:example.org")),
+    get_openid_userinfo: openid::get_openid_userinfo::v1 => Request::new("SomeT0kenHere"),
+    get_custom_information: query::get_custom_information::v1 => {
+        let mut params = BTreeMap::new();
+        params.insert("foo".to_owned(), "bar".to_owned());
+        Request::new("custom_type", params)
+    },
+    get_profile_information: query::get_profile_information::v1 => {
+        let mut request = Request::new(leak(user_id!("@someone:example.org")));
+        request.field = Some(&ProfileField::DisplayName);
+        request
+    },
+    get_room_information: query::get_room_information::v1
+        => Request::new(&room_alias_id!("#room_alias:example.org")),
+    bind_callback(body): thirdparty::bind_callback::v1 => Request::new(
+        "alice@example.com",
+        &user_id!("@alice:matrix.org"),
+        &from_json_value::<Vec<ThirdPartyInvite>>(body["invites"].clone()).unwrap(),
+    ),
+    exchange_invite(body): thirdparty::exchange_invite::v1 => Request::new(
+        &room_id!("!abc123:matrix.org"),
+        &user_id!("@joe:example.org"),
+        &user_id!("@someone:example.org"),
+        &from_json_value(body["content"].clone()).unwrap(),
+    ),
+    send_transaction_message(body): transactions::send_transaction_message::v1 => {
+        let mut request =
+            Request::new("S0meTransacti0nId", leak(server_name!("matrix.org")), ts(1_234_567_890));
+        request.pdus = leak(from_json_value::<Vec<Raw<Pdu>>>(body["pdus"].clone()).unwrap());
+        request
+    },
+}
+
+/// The time at the given number of milliseconds since the unix epoch.
+fn ts(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+/// Moves the given value to the heap and leaks it, for requests that are built in a block and
+/// borrow from it.
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
@@ -20,6 +20,8 @@ ruma_api! {
         /// The user ID which registered the token.
         pub user_id: UserId,
     }
+
+    error: crate::Error
 }
 
 impl Request {
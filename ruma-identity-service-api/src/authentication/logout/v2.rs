@@ -17,6 +17,8 @@ ruma_api! {
 
     #[derive(Default)]
     response: {}
+
+    error: crate::Error
 }
 
 impl Request {
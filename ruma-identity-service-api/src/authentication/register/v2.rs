@@ -37,6 +37,8 @@ ruma_api! {
         /// An opaque string representing the token to authenticate future requests to the identity server with.
         pub token: String,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -23,6 +23,8 @@ ruma_api! {
         /// Unpadded Base64 encoded public key.
         pub public_key: String,
     }
+
+    error: crate::Error
 }
 
 impl Request {
@@ -22,6 +22,8 @@ ruma_api! {
         /// Whether the short-term public key is recognised and is currently valid.
         pub valid: bool,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
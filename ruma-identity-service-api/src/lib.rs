@@ -11,3 +11,24 @@ pub mod authentication;
 pub mod keys;
 pub mod status;
 pub mod tos;
+
+/// A Matrix error returned by the identity server.
+pub use ruma_api::error::MatrixError as Error;
+
+/// The metadata of all endpoints of the identity service API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry`
+/// feature.
+#[cfg(feature = "registry")]
+pub fn endpoints() -> impl Iterator<Item = &'static ruma_api::Metadata> {
+    ruma_api::registry::endpoints_of(module_path!())
+}
+
+/// The schemas of all endpoints of the identity service API in this crate.
+///
+/// Endpoints behind disabled features are not included. Only available with the `registry` and
+/// `schema` features.
+#[cfg(all(feature = "registry", feature = "schema"))]
+pub fn endpoint_schemas() -> impl Iterator<Item = &'static ruma_api::schema::EndpointSchema> {
+    ruma_api::registry::schemas_of(module_path!())
+}
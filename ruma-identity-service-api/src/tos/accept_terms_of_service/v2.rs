@@ -21,6 +21,8 @@ ruma_api! {
 
     #[derive(Default)]
     response: {}
+
+    error: crate::Error
 }
 
 impl Request {
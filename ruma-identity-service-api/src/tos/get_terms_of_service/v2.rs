@@ -24,6 +24,8 @@ ruma_api! {
         /// Mapped from arbitrary ID (unused in this version of the specification) to a Policy Object.
         pub policies: BTreeMap<String, Policies>
     }
+
+    error: crate::Error
 }
 
 impl Request {
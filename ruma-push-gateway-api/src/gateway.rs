@@ -0,0 +1,251 @@
+//! Helpers to implement a push gateway, which forwards the notifications it receives from
+//! homeservers to push providers like APNs or FCM.
+//!
+//! A `Dispatcher` sends each notification to the devices it is addressed to with the
+//! `PushProvider` registered for their `app_id`, concurrently, and collects the pushkeys that were
+//! rejected for the response to the homeserver. The `apns` and `fcm` functions build reference
+//! payloads for those providers.
+
+use std::{collections::BTreeMap, error::Error as StdError, fmt, future::Future, pin::Pin};
+
+use futures_util::future::{join_all, ready};
+
+use crate::send_event_notification::v1::{Device, IncomingNotification, Response};
+
+mod payload;
+
+pub use self::payload::{apns, fcm, PayloadKind};
+
+/// The future returned by `PushProvider::send`.
+pub type PushFuture<'a> = Pin<Box<dyn Future<Output = Result<(), PushError>> + Send + 'a>>;
+
+/// A service that delivers notifications to the devices of an app.
+pub trait PushProvider: Send + Sync {
+    /// Sends the given notification to the given device, one of `notification.devices`.
+    fn send<'a>(
+        &'a self,
+        notification: &'a IncomingNotification,
+        device: &'a Device,
+    ) -> PushFuture<'a>;
+}
+
+/// An error returned by a `PushProvider` that couldn't deliver a notification.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum PushError {
+    /// The pushkey of the device is not valid, because it expired or was never valid.
+    ///
+    /// The pushkey is returned to the homeserver, which should remove the associated pusher.
+    Rejected,
+
+    /// The notification couldn't be delivered for another reason, like a network error.
+    Failed(Box<dyn StdError + Send + Sync>),
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rejected => f.write_str("the pushkey was rejected"),
+            Self::Failed(err) => write!(f, "failed to deliver the notification: {}", err),
+        }
+    }
+}
+
+impl StdError for PushError {}
+
+/// Sends notifications to devices with the `PushProvider` of their app.
+#[derive(Default)]
+pub struct Dispatcher {
+    providers: BTreeMap<String, Box<dyn PushProvider>>,
+}
+
+impl Dispatcher {
+    /// Creates a `Dispatcher` without any provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the provider to use for the devices with the given `app_id`.
+    ///
+    /// Replaces the previous provider for this `app_id`, if any.
+    pub fn add_provider(
+        &mut self,
+        app_id: impl Into<String>,
+        provider: impl PushProvider + 'static,
+    ) {
+        self.providers.insert(app_id.into(), Box::new(provider));
+    }
+
+    /// Sends the notification to all of its devices, concurrently.
+    ///
+    /// The pushkeys of devices whose `app_id` has no provider are rejected.
+    pub async fn dispatch(&self, notification: &IncomingNotification) -> DispatchReport {
+        let results = join_all(notification.devices.iter().map(|device| {
+            match self.providers.get(&device.app_id) {
+                Some(provider) => provider.send(notification, device),
+                None => Box::pin(ready(Err(PushError::Rejected))),
+            }
+        }))
+        .await;
+
+        let mut report = DispatchReport::default();
+        for (device, result) in notification.devices.iter().zip(results) {
+            match result {
+                Ok(()) => report.sent.push(device.pushkey.clone()),
+                Err(PushError::Rejected) => report.rejected.push(device.pushkey.clone()),
+                Err(err) => report.failed.push((device.pushkey.clone(), err)),
+            }
+        }
+
+        report
+    }
+}
+
+impl fmt::Debug for Dispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher").field("app_ids", &self.providers.keys()).finish()
+    }
+}
+
+/// The results of `Dispatcher::dispatch`, by pushkey.
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DispatchReport {
+    /// The pushkeys of the devices the notification was sent to.
+    pub sent: Vec<String>,
+
+    /// The pushkeys that were rejected.
+    pub rejected: Vec<String>,
+
+    /// The pushkeys of the devices the notification couldn't be delivered to, with the error of
+    /// the provider.
+    pub failed: Vec<(String, PushError)>,
+}
+
+impl DispatchReport {
+    /// Whether the notification couldn't be delivered to some devices for another reason than a
+    /// rejected pushkey.
+    ///
+    /// The gateway may then respond with an error, so the homeserver retries later.
+    pub fn has_failures(&self) -> bool {
+        !self.failed.is_empty()
+    }
+
+    /// Creates the response to the homeserver, with the rejected pushkeys.
+    pub fn into_response(self) -> Response {
+        Response::new(self.rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::{Dispatcher, PushError, PushFuture, PushProvider};
+    use crate::send_event_notification::v1::{Device, IncomingNotification};
+
+    /// A provider that records the pushkeys it sends notifications to.
+    #[derive(Clone, Default)]
+    struct MockProvider {
+        sent: Arc<Mutex<Vec<String>>>,
+        rejected: Vec<&'static str>,
+        failing: Vec<&'static str>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl PushProvider for MockProvider {
+        fn send<'a>(
+            &'a self,
+            _notification: &'a IncomingNotification,
+            device: &'a Device,
+        ) -> PushFuture<'a> {
+            Box::pin(async move {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+                // Let the other notifications be sent in the meantime.
+                tokio::task::yield_now().await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                if self.rejected.contains(&device.pushkey.as_str()) {
+                    return Err(PushError::Rejected);
+                }
+                if self.failing.contains(&device.pushkey.as_str()) {
+                    return Err(PushError::Failed(Box::new(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "timed out",
+                    ))));
+                }
+
+                self.sent.lock().unwrap().push(device.pushkey.clone());
+                Ok(())
+            })
+        }
+    }
+
+    fn notification() -> IncomingNotification {
+        from_json_value(json!({
+            "event_id": "$3957tyerfgewrf384",
+            "room_id": "!slw48wfj34rtnrf:example.com",
+            "counts": { "unread": 2 },
+            "devices": [
+                { "app_id": "org.example.ios", "pushkey": "ios_1" },
+                { "app_id": "org.example.android", "pushkey": "android_1" },
+                { "app_id": "org.example.ios", "pushkey": "ios_2" },
+                { "app_id": "org.example.android", "pushkey": "android_2" },
+                { "app_id": "org.example.unknown", "pushkey": "unknown_1" },
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn dispatch_to_providers() {
+        let ios = MockProvider { rejected: vec!["ios_2"], ..MockProvider::default() };
+        let android = MockProvider { failing: vec!["android_2"], ..MockProvider::default() };
+
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.add_provider("org.example.ios", ios.clone());
+        dispatcher.add_provider("org.example.android", android.clone());
+
+        let report = dispatcher.dispatch(&notification()).await;
+
+        assert_eq!(*ios.sent.lock().unwrap(), vec!["ios_1"]);
+        assert_eq!(*android.sent.lock().unwrap(), vec!["android_1"]);
+        assert_eq!(report.sent, vec!["ios_1", "android_1"]);
+        assert_eq!(report.rejected, vec!["ios_2", "unknown_1"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "android_2");
+        assert!(report.has_failures());
+
+        // Both devices of each app were sent the notification at the same time.
+        assert_eq!(ios.max_in_flight.load(Ordering::SeqCst), 2);
+        assert_eq!(android.max_in_flight.load(Ordering::SeqCst), 2);
+
+        let response = report.into_response();
+        assert_eq!(response.rejected, vec!["ios_2", "unknown_1"]);
+    }
+
+    #[tokio::test]
+    async fn dispatch_without_devices() {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.add_provider("org.example.ios", MockProvider::default());
+
+        let notification: IncomingNotification =
+            from_json_value(json!({ "counts": { "unread": 0 }, "devices": [] })).unwrap();
+        let report = dispatcher.dispatch(&notification).await;
+
+        assert!(report.sent.is_empty());
+        assert!(!report.has_failures());
+        assert!(report.into_response().rejected.is_empty());
+    }
+}
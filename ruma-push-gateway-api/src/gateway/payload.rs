@@ -0,0 +1,389 @@
+//! Reference payloads for the APNs and FCM push providers.
+
+use ruma_common::push::{PushFormat, Tweak};
+use serde::Serialize;
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+use crate::send_event_notification::v1::{Device, IncomingNotification, NotificationPriority};
+
+/// The maximum size of an APNs payload, in bytes.
+const APNS_MAX_PAYLOAD_SIZE: usize = 4096;
+
+/// The pointers to the `body` strings that are trimmed to fit a payload in the maximum size.
+const BODY_POINTERS: &[&str] = &["/aps/alert/body", "/content/body"];
+
+/// The kind of push notification to build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum PayloadKind {
+    /// A silent notification that only carries data, that the app uses to display a notification
+    /// itself, possibly after fetching the event.
+    DataOnly,
+
+    /// A notification that is displayed by the operating system, with the room and the body of
+    /// the event.
+    Notification,
+}
+
+/// Builds the JSON payload of an Apple Push Notification service (APNs) notification.
+///
+/// The data of the notification is in the top-level fields of the payload, next to the `aps`
+/// dictionary. The badge is the number of unread messages.
+///
+/// If the pusher of the device uses the `event_id_only` format, the payload only contains the
+/// event ID, the room ID and the counts, and is always data-only.
+///
+/// Payloads larger than the 4 KiB accepted by APNs are shortened by trimming the `body` of the
+/// alert and of the event content.
+pub fn apns(notification: &IncomingNotification, device: &Device, kind: PayloadKind) -> JsonValue {
+    let event_id_only = is_event_id_only(device);
+    let mut payload = data(notification, event_id_only);
+
+    let mut aps = JsonMap::new();
+    if kind == PayloadKind::Notification && !event_id_only {
+        aps.insert("alert".into(), alert(notification));
+        if let Some(sound) = sound(device) {
+            aps.insert("sound".into(), sound.into());
+        }
+    } else {
+        aps.insert("content-available".into(), 1.into());
+    }
+    aps.insert("badge".into(), u64::from(notification.counts.unread).into());
+
+    payload.insert("aps".into(), aps.into());
+
+    let mut payload = payload.into();
+    truncate_bodies(&mut payload, APNS_MAX_PAYLOAD_SIZE);
+    payload
+}
+
+/// Builds the JSON body of a Firebase Cloud Messaging (FCM) notification, for its legacy HTTP
+/// API.
+///
+/// Low priority notifications are sent with the `normal` FCM priority.
+///
+/// If the pusher of the device uses the `event_id_only` format, the data only contains the event
+/// ID, the room ID and the counts, and the payload is always data-only.
+pub fn fcm(notification: &IncomingNotification, device: &Device, kind: PayloadKind) -> JsonValue {
+    let event_id_only = is_event_id_only(device);
+
+    let priority = match notification.prio {
+        NotificationPriority::Low => "normal",
+        _ => "high",
+    };
+
+    let mut payload = json!({
+        "to": device.pushkey,
+        "priority": priority,
+        "data": data(notification, event_id_only),
+    });
+
+    if kind == PayloadKind::Notification && !event_id_only {
+        let mut fcm_notification = alert(notification);
+        if let Some(sound) = sound(device) {
+            fcm_notification["sound"] = sound.into();
+        }
+        payload["notification"] = fcm_notification;
+    }
+
+    payload
+}
+
+/// Trims the `body` strings of the payload until its serialized size is at most `max_size`.
+///
+/// The `body` strings are shortened evenly, starting with the longest one. The payload can still
+/// be too large if all of them are empty.
+fn truncate_bodies(payload: &mut JsonValue, max_size: usize) {
+    loop {
+        let size = serde_json::to_vec(payload).expect("payloads serialize to JSON").len();
+        if size <= max_size {
+            return;
+        }
+
+        let mut bodies: Vec<_> = BODY_POINTERS
+            .iter()
+            .filter_map(|pointer| {
+                payload.pointer(pointer)?.as_str().map(|body| (pointer, body.len()))
+            })
+            .filter(|(_, len)| *len > 0)
+            .collect();
+        bodies.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let (pointer, len) = match bodies.first() {
+            Some(longest) => *longest,
+            None => return,
+        };
+        let next_len = bodies.get(1).map_or(0, |(_, len)| *len);
+
+        // Trim the longest body down to the next one, or trim both by half of the excess, so the
+        // bodies are shortened evenly.
+        let excess = size - max_size;
+        let trimmed = excess.min((len - next_len).max(excess - excess / 2));
+
+        if let Some(JsonValue::String(body)) = payload.pointer_mut(pointer) {
+            // Removing a character from a string removes at least as many bytes from its JSON
+            // representation.
+            let mut len = len.saturating_sub(trimmed);
+            while !body.is_char_boundary(len) {
+                len -= 1;
+            }
+            body.truncate(len);
+        }
+    }
+}
+
+/// Whether the pusher of the device uses the `event_id_only` format.
+fn is_event_id_only(device: &Device) -> bool {
+    matches!(
+        device.data.as_ref().and_then(|data| data.format.as_ref()),
+        Some(PushFormat::EventIdOnly)
+    )
+}
+
+/// The sound to play for the device, from its `sound` tweak.
+fn sound(device: &Device) -> Option<&str> {
+    device.tweaks.iter().find_map(|tweak| match tweak {
+        Tweak::Sound(sound) => Some(sound.as_str()),
+        _ => None,
+    })
+}
+
+/// The fields of the notification that are passed to the app.
+fn data(notification: &IncomingNotification, event_id_only: bool) -> JsonMap<String, JsonValue> {
+    let mut data = JsonMap::new();
+
+    insert(&mut data, "event_id", &notification.event_id);
+    insert(&mut data, "room_id", &notification.room_id);
+
+    if !event_id_only {
+        insert(&mut data, "type", &notification.event_type);
+        insert(&mut data, "sender", &notification.sender);
+        insert(&mut data, "sender_display_name", &notification.sender_display_name);
+        insert(&mut data, "room_name", &notification.room_name);
+        insert(&mut data, "room_alias", &notification.room_alias);
+        insert(&mut data, "content", &notification.content);
+
+        if notification.user_is_target {
+            data.insert("user_is_target".into(), true.into());
+        }
+    }
+
+    data.insert("unread_count".into(), u64::from(notification.counts.unread).into());
+    data.insert("missed_calls".into(), u64::from(notification.counts.missed_calls).into());
+
+    data
+}
+
+/// Inserts the value in the map under the given key, if it is present.
+fn insert<T: Serialize>(map: &mut JsonMap<String, JsonValue>, key: &str, value: &Option<T>) {
+    if let Some(value) = value {
+        let value = serde_json::to_value(value).expect("notification fields serialize to JSON");
+        map.insert(key.to_owned(), value);
+    }
+}
+
+/// The title and body of the notification to display.
+///
+/// The title is the name of the room, or the sender in rooms without a name. The body is the
+/// `body` of the event content, prefixed with the sender if the title is the room name.
+fn alert(notification: &IncomingNotification) -> JsonValue {
+    let sender = notification
+        .sender_display_name
+        .clone()
+        .or_else(|| notification.sender.as_ref().map(|sender| sender.to_string()));
+    let body = notification
+        .content
+        .as_ref()
+        .and_then(|content| serde_json::from_str::<JsonValue>(content.get()).ok())
+        .and_then(|content| content.get("body")?.as_str().map(ToOwned::to_owned));
+
+    let (title, body) = match (&notification.room_name, sender, body) {
+        (Some(room_name), Some(sender), Some(body)) => {
+            (Some(room_name.clone()), Some(format!("{}: {}", sender, body)))
+        }
+        (Some(room_name), _, body) => (Some(room_name.clone()), body),
+        (None, sender, body) => (sender, body),
+    };
+
+    let mut alert = JsonMap::new();
+    if let Some(title) = title {
+        alert.insert("title".into(), title.into());
+    }
+    if let Some(body) = body {
+        alert.insert("body".into(), body.into());
+    }
+
+    alert.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+
+    use super::{apns, fcm, PayloadKind, APNS_MAX_PAYLOAD_SIZE};
+    use crate::send_event_notification::v1::IncomingNotification;
+
+    fn notification(device: JsonValue) -> IncomingNotification {
+        from_json_value(json!({
+            "event_id": "$3957tyerfgewrf384",
+            "room_id": "!slw48wfj34rtnrf:example.com",
+            "type": "m.room.message",
+            "sender": "@exampleuser:matrix.org",
+            "sender_display_name": "Major Tom",
+            "room_name": "Mission Control",
+            "prio": "low",
+            "content": {
+                "msgtype": "m.text",
+                "body": "I'm floating in a most peculiar way."
+            },
+            "counts": { "unread": 2 },
+            "devices": [device]
+        }))
+        .unwrap()
+    }
+
+    fn device(format: Option<&str>) -> JsonValue {
+        let mut device = json!({
+            "app_id": "org.example.app",
+            "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+            "tweaks": { "sound": "bing" }
+        });
+        if let Some(format) = format {
+            device["data"] = json!({ "format": format });
+        }
+        device
+    }
+
+    fn full_data() -> JsonValue {
+        json!({
+            "event_id": "$3957tyerfgewrf384",
+            "room_id": "!slw48wfj34rtnrf:example.com",
+            "type": "m.room.message",
+            "sender": "@exampleuser:matrix.org",
+            "sender_display_name": "Major Tom",
+            "room_name": "Mission Control",
+            "content": {
+                "msgtype": "m.text",
+                "body": "I'm floating in a most peculiar way."
+            },
+            "unread_count": 2,
+            "missed_calls": 0
+        })
+    }
+
+    #[test]
+    fn apns_payloads() {
+        let notification = notification(device(None));
+        let device = &notification.devices[0];
+
+        let mut expected = full_data();
+        expected["aps"] = json!({
+            "alert": {
+                "title": "Mission Control",
+                "body": "Major Tom: I'm floating in a most peculiar way."
+            },
+            "sound": "bing",
+            "badge": 2
+        });
+        assert_eq!(apns(&notification, device, PayloadKind::Notification), expected);
+
+        let mut expected = full_data();
+        expected["aps"] = json!({ "content-available": 1, "badge": 2 });
+        assert_eq!(apns(&notification, device, PayloadKind::DataOnly), expected);
+    }
+
+    #[test]
+    fn fcm_payloads() {
+        let notification = notification(device(None));
+        let device = &notification.devices[0];
+
+        assert_eq!(
+            fcm(&notification, device, PayloadKind::Notification),
+            json!({
+                "to": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                "priority": "normal",
+                "data": full_data(),
+                "notification": {
+                    "title": "Mission Control",
+                    "body": "Major Tom: I'm floating in a most peculiar way.",
+                    "sound": "bing"
+                }
+            })
+        );
+        assert_eq!(
+            fcm(&notification, device, PayloadKind::DataOnly),
+            json!({
+                "to": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                "priority": "normal",
+                "data": full_data()
+            })
+        );
+    }
+
+    #[test]
+    fn event_id_only_payloads() {
+        let notification = notification(device(Some("event_id_only")));
+        let device = &notification.devices[0];
+        let data = json!({
+            "event_id": "$3957tyerfgewrf384",
+            "room_id": "!slw48wfj34rtnrf:example.com",
+            "unread_count": 2,
+            "missed_calls": 0
+        });
+
+        let mut expected = data.clone();
+        expected["aps"] = json!({ "content-available": 1, "badge": 2 });
+        assert_eq!(apns(&notification, device, PayloadKind::Notification), expected);
+
+        assert_eq!(
+            fcm(&notification, device, PayloadKind::Notification),
+            json!({
+                "to": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                "priority": "normal",
+                "data": data
+            })
+        );
+    }
+
+    #[test]
+    fn alert_without_room_name() {
+        let mut notification = notification(device(None));
+        notification.room_name = None;
+        notification.sender_display_name = None;
+        let device = &notification.devices[0];
+
+        let payload = apns(&notification, device, PayloadKind::Notification);
+        assert_eq!(
+            payload["aps"]["alert"],
+            json!({
+                "title": "@exampleuser:matrix.org",
+                "body": "I'm floating in a most peculiar way."
+            })
+        );
+    }
+
+    #[test]
+    fn apns_payload_truncation() {
+        let mut notification = notification(device(None));
+        let body = "Ground control to Major Tom 🚀 ".repeat(200);
+        notification.content = Some(
+            serde_json::value::to_raw_value(&json!({ "msgtype": "m.text", "body": body })).unwrap(),
+        );
+        let device = &notification.devices[0];
+
+        let payload = apns(&notification, device, PayloadKind::Notification);
+        let size = serde_json::to_vec(&payload).unwrap().len();
+        assert!(size <= APNS_MAX_PAYLOAD_SIZE);
+        assert!(size > APNS_MAX_PAYLOAD_SIZE - 10);
+
+        let alert_body = payload["aps"]["alert"]["body"].as_str().unwrap();
+        assert!(alert_body.starts_with("Major Tom: Ground control to Major Tom 🚀"));
+        let content_body = payload["content"]["body"].as_str().unwrap();
+        assert!(body.starts_with(content_body));
+        assert!(content_body.len() < body.len());
+        assert_eq!(payload["room_name"], "Mission Control");
+
+        let payload = apns(&notification, device, PayloadKind::DataOnly);
+        assert!(serde_json::to_vec(&payload).unwrap().len() <= APNS_MAX_PAYLOAD_SIZE);
+    }
+}
@@ -4,7 +4,7 @@ use std::time::SystemTime;
 
 use js_int::UInt;
 use ruma_api::ruma_api;
-use ruma_common::push::{PusherData, Tweak};
+use ruma_common::push::{NotificationOutcome, PushFormat, PusherData, Tweak};
 use ruma_events::EventType;
 use ruma_identifiers::{EventId, RoomAliasId, RoomId, UserId};
 use ruma_serde::{Outgoing, StringEnum};
@@ -39,6 +39,8 @@ ruma_api! {
         /// the same pushkey failed. May be empty.
         pub rejected: Vec<String>,
     }
+
+    error: crate::Error
 }
 
 impl<'a> Request<'a> {
@@ -129,6 +131,92 @@ impl<'a> Notification<'a> {
     pub fn new(devices: &'a [Device]) -> Self {
         Notification { devices, ..Default::default() }
     }
+
+    /// Creates a notification about the given event for the given devices.
+    ///
+    /// `outcome` is the result of the evaluation of the push rules of the user for the event. The
+    /// notification has a low priority if the event is not highlighted and doesn't play a sound.
+    ///
+    /// `user_id` is the ID of the user that owns the pushers, `room_name` the current name of the
+    /// room and `sender_display_name` the current display name of the sender in the room.
+    ///
+    /// With the `event_id_only` format, only the IDs of the event and of the room are included,
+    /// besides the priority, the counts and the devices.
+    #[allow(clippy::too_many_arguments)]
+    pub fn for_event(
+        event: NotificationEvent<'a>,
+        outcome: &NotificationOutcome,
+        user_id: &UserId,
+        room_name: Option<&'a str>,
+        sender_display_name: Option<&'a str>,
+        counts: NotificationCounts,
+        format: Option<&PushFormat>,
+        devices: &'a [Device],
+    ) -> Self {
+        let mut notification = Notification {
+            event_id: Some(event.event_id),
+            room_id: Some(event.room_id),
+            counts,
+            devices,
+            ..Default::default()
+        };
+
+        if !outcome.highlight && outcome.sound.is_none() {
+            notification.prio = NotificationPriority::Low;
+        }
+
+        if matches!(format, Some(PushFormat::EventIdOnly)) {
+            return notification;
+        }
+
+        notification.event_type = Some(event.event_type);
+        notification.sender = Some(event.sender);
+        notification.sender_display_name = sender_display_name;
+        notification.room_name = room_name;
+        notification.user_is_target =
+            *event.event_type == EventType::RoomMember && event.state_key == Some(user_id.as_str());
+        notification.content = Some(event.content.to_owned());
+
+        notification
+    }
+}
+
+/// The event a notification is about, for `Notification::for_event`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct NotificationEvent<'a> {
+    /// The ID of the event.
+    pub event_id: &'a EventId,
+
+    /// The ID of the room of the event.
+    pub room_id: &'a RoomId,
+
+    /// The type of the event.
+    pub event_type: &'a EventType,
+
+    /// The sender of the event.
+    pub sender: &'a UserId,
+
+    /// The state key of the event, if it is a state event.
+    pub state_key: Option<&'a str>,
+
+    /// The content of the event.
+    pub content: &'a RawJsonValue,
+}
+
+impl<'a> NotificationEvent<'a> {
+    /// Creates a new `NotificationEvent` for a message event with the given fields.
+    ///
+    /// For state events, set the `state_key` field.
+    pub fn new(
+        event_id: &'a EventId,
+        room_id: &'a RoomId,
+        event_type: &'a EventType,
+        sender: &'a UserId,
+        content: &'a RawJsonValue,
+    ) -> Self {
+        Self { event_id, room_id, event_type, sender, state_key: None, content }
+    }
 }
 
 /// Type for passing information about notification priority.
@@ -193,6 +281,7 @@ pub struct Device {
 
     /// The unix timestamp (in seconds) when the pushkey was last updated.
     #[serde(
+        default,
         with = "ruma_serde::time::opt_s_since_unix_epoch",
         skip_serializing_if = "Option::is_none"
     )]
@@ -206,7 +295,7 @@ pub struct Device {
 
     /// A dictionary of customisations made to the way this notification is to
     /// be presented. These are added by push rules.
-    #[serde(with = "tweak_serde", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, with = "tweak_serde", skip_serializing_if = "Vec::is_empty")]
     pub tweaks: Vec<Tweak>,
 }
 
@@ -295,13 +384,21 @@ mod tests {
     use std::time::{Duration, SystemTime};
 
     use js_int::uint;
+    use ruma_common::push::{Action, NotificationOutcome, PushFormat, RuleKind};
     use ruma_events::EventType;
     use ruma_identifiers::{event_id, room_alias_id, room_id, user_id};
     use serde_json::{
-        from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+        from_value as from_json_value, json, to_value as to_json_value,
+        value::to_raw_value as to_raw_json_value, Value as JsonValue,
     };
 
-    use super::{Device, Notification, NotificationCounts, NotificationPriority, Tweak};
+    use super::{
+        Device, Notification, NotificationCounts, NotificationEvent, NotificationPriority, Tweak,
+    };
+
+    fn outcome(actions: &[Action]) -> NotificationOutcome {
+        NotificationOutcome::from_actions(actions, (RuleKind::Underride, ".m.rule.message".into()))
+    }
 
     #[test]
     fn serialize_request() {
@@ -371,4 +468,162 @@ mod tests {
 
         assert_eq!(expected, to_json_value(notice).unwrap())
     }
+
+    #[test]
+    fn notification_for_event() {
+        let eid = event_id!("$3957tyerfgewrf384");
+        let rid = room_id!("!slw48wfj34rtnrf:example.com");
+        let uid = user_id!("@exampleuser:matrix.org");
+        let content = to_raw_json_value(&json!({ "msgtype": "m.text", "body": "Hello" })).unwrap();
+        let event = NotificationEvent::new(&eid, &rid, &EventType::RoomMessage, &uid, &content);
+        let counts = NotificationCounts::new(uint!(2), uint!(0));
+        let devices = &[Device::new("org.example.app".into(), "pushkey".into())];
+
+        let sound = outcome(&[Action::Notify, Action::SetTweak(Tweak::Sound("default".into()))]);
+
+        let notification = Notification::for_event(
+            event,
+            &sound,
+            &user_id!("@recipient:matrix.org"),
+            Some("Mission Control"),
+            Some("Major Tom"),
+            counts.clone(),
+            None,
+            devices,
+        );
+        assert_eq!(
+            to_json_value(notification).unwrap(),
+            json!({
+                "event_id": "$3957tyerfgewrf384",
+                "room_id": "!slw48wfj34rtnrf:example.com",
+                "type": "m.room.message",
+                "sender": "@exampleuser:matrix.org",
+                "sender_display_name": "Major Tom",
+                "room_name": "Mission Control",
+                "content": { "msgtype": "m.text", "body": "Hello" },
+                "counts": { "unread": 2 },
+                "devices": [{ "app_id": "org.example.app", "pushkey": "pushkey" }]
+            })
+        );
+
+        let notification = Notification::for_event(
+            event,
+            &sound,
+            &user_id!("@recipient:matrix.org"),
+            Some("Mission Control"),
+            Some("Major Tom"),
+            counts.clone(),
+            Some(&PushFormat::EventIdOnly),
+            devices,
+        );
+        assert_eq!(
+            to_json_value(notification).unwrap(),
+            json!({
+                "event_id": "$3957tyerfgewrf384",
+                "room_id": "!slw48wfj34rtnrf:example.com",
+                "counts": { "unread": 2 },
+                "devices": [{ "app_id": "org.example.app", "pushkey": "pushkey" }]
+            })
+        );
+
+        let silent = outcome(&[Action::Notify]);
+        let notification = Notification::for_event(
+            event,
+            &silent,
+            &user_id!("@recipient:matrix.org"),
+            None,
+            None,
+            counts.clone(),
+            Some(&PushFormat::EventIdOnly),
+            devices,
+        );
+        assert_eq!(notification.prio, NotificationPriority::Low);
+
+        let highlight = outcome(&[Action::Notify, Action::SetTweak(Tweak::Highlight(true))]);
+        let notification = Notification::for_event(
+            event,
+            &highlight,
+            &user_id!("@recipient:matrix.org"),
+            None,
+            None,
+            counts,
+            Some(&PushFormat::EventIdOnly),
+            devices,
+        );
+        assert_eq!(notification.prio, NotificationPriority::High);
+    }
+
+    #[test]
+    fn notification_for_member_event() {
+        let eid = event_id!("$3957tyerfgewrf384");
+        let rid = room_id!("!slw48wfj34rtnrf:example.com");
+        let sender = user_id!("@exampleuser:matrix.org");
+        let recipient = user_id!("@recipient:matrix.org");
+        let content = to_raw_json_value(&json!({ "membership": "invite" })).unwrap();
+        let mut event =
+            NotificationEvent::new(&eid, &rid, &EventType::RoomMember, &sender, &content);
+        event.state_key = Some(recipient.as_str());
+
+        let outcome = outcome(&[Action::Notify]);
+
+        let notification = Notification::for_event(
+            event,
+            &outcome,
+            &recipient,
+            None,
+            None,
+            NotificationCounts::default(),
+            None,
+            &[],
+        );
+        assert!(notification.user_is_target);
+
+        let notification = Notification::for_event(
+            event,
+            &outcome,
+            &sender,
+            None,
+            None,
+            NotificationCounts::default(),
+            None,
+            &[],
+        );
+        assert!(!notification.user_is_target);
+    }
+
+    #[test]
+    fn deserialize_device_without_optional_fields() {
+        let device: Device = from_json_value(json!({
+            "app_id": "org.matrix.matrixConsole.ios",
+            "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+        }))
+        .unwrap();
+
+        assert_eq!(device.app_id, "org.matrix.matrixConsole.ios");
+        assert!(device.pushkey_ts.is_none());
+        assert!(device.data.is_none());
+        assert!(device.tweaks.is_empty());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn deserialize_error_response() {
+        use std::convert::TryFrom;
+
+        use ruma_api::error::{FromHttpResponseError, ServerError};
+
+        let http_response = http::Response::builder()
+            .status(http::StatusCode::FORBIDDEN)
+            .body(br#"{ "errcode": "M_FORBIDDEN", "error": "Unknown pushkey" }"#.to_vec())
+            .unwrap();
+
+        match super::Response::try_from(http_response) {
+            Err(FromHttpResponseError::Http(ServerError::Known(error))) => {
+                assert_eq!(error.status_code, http::StatusCode::FORBIDDEN);
+                assert_eq!(error.kind, ruma_api::error::ErrorKind::Forbidden);
+                assert_eq!(error.message, "Unknown pushkey");
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
 }
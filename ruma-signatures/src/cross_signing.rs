@@ -0,0 +1,611 @@
+//! Verification of the signatures of device and cross-signing keys.
+
+use std::{collections::BTreeMap, fmt};
+
+use ruma_common::encryption::{CrossSigningKey, DeviceKeys, KeyUsage, OneTimeKey};
+use ruma_identifiers::{
+    DeviceId, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm, UserId,
+};
+use ruma_serde::{CanonicalJsonObject, Raw};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue as RawJsonValue;
+
+use crate::{verify_json, Error};
+
+/// An error encountered when verifying the signatures of device or cross-signing keys.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum KeyVerificationError {
+    /// The keys don't belong to the expected user, or the keys of a signature chain don't all
+    /// belong to the same user.
+    UserIdMismatch,
+
+    /// The device keys don't belong to the expected device.
+    DeviceIdMismatch,
+
+    /// The device doesn't support an encryption algorithm that is required to communicate with
+    /// it.
+    MissingAlgorithm(EventEncryptionAlgorithm),
+
+    /// A cross-signing key doesn't have the usage required for its place in a signature chain.
+    InvalidUsage(KeyUsage),
+
+    /// The ed25519 public key to verify the signature with is missing.
+    ///
+    /// Cross-signing keys must contain exactly one ed25519 public key, device keys must contain an
+    /// ed25519 key whose key ID is the device ID.
+    MissingPublicKey,
+
+    /// The signature is missing or invalid.
+    Signature(Error),
+}
+
+impl fmt::Display for KeyVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UserIdMismatch => write!(f, "keys belong to an unexpected user"),
+            Self::DeviceIdMismatch => write!(f, "device keys belong to an unexpected device"),
+            Self::MissingAlgorithm(algorithm) => {
+                write!(f, "device doesn't support the {} algorithm", algorithm)
+            }
+            Self::InvalidUsage(usage) => {
+                write!(f, "cross-signing key is missing the {:?} usage", usage)
+            }
+            Self::MissingPublicKey => write!(f, "ed25519 public key of the signing key is missing"),
+            Self::Signature(error) => write!(f, "signature verification failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for KeyVerificationError {}
+
+impl From<Error> for KeyVerificationError {
+    fn from(error: Error) -> Self {
+        Self::Signature(error)
+    }
+}
+
+/// Verifies that the given key is signed by the given cross-signing key.
+///
+/// This is used to check that a self-signing or user-signing key is signed by the master key of
+/// the same user, or that another user's master key is signed by a user-signing key.
+///
+/// The signature is checked against the key as it was received, including the fields that are not
+/// part of `CrossSigningKey`, which is why this takes the raw JSON of the key, like the values of
+/// `self_signing_keys` in a `/keys/query` response.
+pub fn verify_cross_signing_key(
+    key: &Raw<CrossSigningKey>,
+    signing_key: &CrossSigningKey,
+) -> Result<(), KeyVerificationError> {
+    verify_signature(key.json(), signing_key)
+}
+
+/// Verifies that the given device keys belong to the given user and device, support the Olm and
+/// Megolm encryption algorithms and are signed by the device's own ed25519 key.
+///
+/// `user_id` and `device_id` should be the keys under which these device keys were found, e.g. in
+/// the `device_keys` map of a `/keys/query` response. Device keys that fail this check must not be
+/// used, since they may have been spoofed by the server.
+///
+/// Like [`verify_cross_signing_key`], this takes the device keys as they were received.
+pub fn verify_device_keys(
+    device_keys: &Raw<DeviceKeys>,
+    user_id: &UserId,
+    device_id: &DeviceId,
+) -> Result<(), KeyVerificationError> {
+    let raw_device_keys = device_keys;
+    let device_keys = deserialize(device_keys)?;
+
+    if device_keys.user_id != *user_id {
+        return Err(KeyVerificationError::UserIdMismatch);
+    }
+
+    if *device_keys.device_id != *device_id {
+        return Err(KeyVerificationError::DeviceIdMismatch);
+    }
+
+    for algorithm in &[
+        EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+        EventEncryptionAlgorithm::MegolmV1AesSha2,
+    ] {
+        if !device_keys.algorithms.contains(algorithm) {
+            return Err(KeyVerificationError::MissingAlgorithm(algorithm.clone()));
+        }
+    }
+
+    let key_id = DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, device_id);
+    let public_key = device_keys.keys.get(&key_id).ok_or(KeyVerificationError::MissingPublicKey)?;
+
+    verify_raw_signature(raw_device_keys.json(), user_id, &key_id, public_key)
+}
+
+/// Verifies that the given device keys are signed by the given cross-signing key, usually the
+/// self-signing key of the device's owner.
+///
+/// Like [`verify_cross_signing_key`], this takes the device keys as they were received.
+pub fn verify_device_keys_signed_by(
+    device_keys: &Raw<DeviceKeys>,
+    signing_key: &CrossSigningKey,
+) -> Result<(), KeyVerificationError> {
+    verify_signature(device_keys.json(), signing_key)
+}
+
+/// Verifies that the given one-time or fallback key is a `SignedKey` that is signed by the ed25519
+/// key of the device it was claimed for.
+///
+/// Like [`verify_device_keys`], this takes the key as it was received, like the values of
+/// `one_time_keys` in a `/keys/claim` response. Keys that aren't signed fail verification.
+///
+/// The device keys themselves should have been verified with [`verify_device_keys`] before.
+pub fn verify_one_time_key(
+    key: &Raw<OneTimeKey>,
+    device_keys: &DeviceKeys,
+) -> Result<(), KeyVerificationError> {
+    let key_id = DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, &device_keys.device_id);
+    let public_key = device_keys.keys.get(&key_id).ok_or(KeyVerificationError::MissingPublicKey)?;
+
+    verify_raw_signature(key.json(), &device_keys.user_id, &key_id, public_key)
+}
+
+/// Verifies the cross-signing signature chain of a device.
+///
+/// The chain is valid if all keys belong to the same user, the self-signing key is signed by the
+/// master key and the device keys are signed by the self-signing key. Whether the master key
+/// itself is trusted has to be decided by the caller.
+///
+/// The self-signing key and the device keys are taken as they were received, see
+/// [`verify_cross_signing_key`].
+pub fn verify_device_signature_chain(
+    master_key: &CrossSigningKey,
+    self_signing_key: &Raw<CrossSigningKey>,
+    device_keys: &Raw<DeviceKeys>,
+) -> Result<(), KeyVerificationError> {
+    let raw_self_signing_key = self_signing_key;
+    let self_signing_key = deserialize(self_signing_key)?;
+    let user_id = deserialize(device_keys)?.user_id;
+
+    if self_signing_key.user_id != master_key.user_id || user_id != master_key.user_id {
+        return Err(KeyVerificationError::UserIdMismatch);
+    }
+
+    if !master_key.usage.contains(&KeyUsage::Master) {
+        return Err(KeyVerificationError::InvalidUsage(KeyUsage::Master));
+    }
+
+    if !self_signing_key.usage.contains(&KeyUsage::SelfSigning) {
+        return Err(KeyVerificationError::InvalidUsage(KeyUsage::SelfSigning));
+    }
+
+    verify_cross_signing_key(raw_self_signing_key, master_key)?;
+    verify_device_keys_signed_by(device_keys, &self_signing_key)
+}
+
+fn deserialize<T: DeserializeOwned>(raw: &Raw<T>) -> Result<T, KeyVerificationError> {
+    Ok(raw.deserialize().map_err(Error::from)?)
+}
+
+/// Verifies that `object` is signed by the public key of `signing_key`.
+fn verify_signature(
+    object: &RawJsonValue,
+    signing_key: &CrossSigningKey,
+) -> Result<(), KeyVerificationError> {
+    let (key_id, public_key) =
+        signing_key.public_key().ok_or(KeyVerificationError::MissingPublicKey)?;
+
+    verify_raw_signature(object, &signing_key.user_id, key_id, public_key)
+}
+
+/// Verifies that the JSON `object` is signed by `user_id` with the given public key.
+fn verify_raw_signature(
+    object: &RawJsonValue,
+    user_id: &UserId,
+    key_id: &DeviceKeyId,
+    public_key: &str,
+) -> Result<(), KeyVerificationError> {
+    let object: CanonicalJsonObject = serde_json::from_str(object.get()).map_err(Error::from)?;
+
+    let mut public_key_set = BTreeMap::new();
+    public_key_set.insert(key_id.to_string(), public_key.to_owned());
+
+    let mut public_key_map = BTreeMap::new();
+    public_key_map.insert(user_id.to_string(), public_key_set);
+
+    Ok(verify_json(&public_key_map, &object)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    use base64::{encode_config, STANDARD_NO_PAD};
+    use ruma_common::encryption::{CrossSigningKey, DeviceKeys, KeyUsage, OneTimeKey, SignedKey};
+    use ruma_identifiers::{
+        device_id, user_id, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm, UserId,
+    };
+    use ruma_serde::{to_canonical_value, CanonicalJsonValue, Raw};
+    use serde::Serialize;
+    use serde_json::{
+        json, to_value as to_json_value, value::to_raw_value as to_raw_json_value,
+        Value as JsonValue,
+    };
+
+    use super::{
+        verify_cross_signing_key, verify_device_keys, verify_device_signature_chain,
+        verify_one_time_key, KeyVerificationError,
+    };
+    use crate::{sign_json, Ed25519KeyPair};
+
+    /// Creates a key pair whose version is its own public key, like cross-signing keys.
+    fn cross_signing_key_pair() -> Ed25519KeyPair {
+        let document = Ed25519KeyPair::generate().unwrap();
+        let public_key =
+            Ed25519KeyPair::new(&document, String::new()).unwrap().public_key().to_vec();
+
+        Ed25519KeyPair::new(&document, encode_config(public_key, STANDARD_NO_PAD)).unwrap()
+    }
+
+    fn key_id(key_pair: &Ed25519KeyPair) -> DeviceKeyId {
+        DeviceKeyId::try_from(format!("ed25519:{}", key_pair.version())).unwrap()
+    }
+
+    fn cross_signing_key(
+        user_id: &UserId,
+        usage: KeyUsage,
+        key_pair: &Ed25519KeyPair,
+    ) -> CrossSigningKey {
+        let mut keys = BTreeMap::new();
+        keys.insert(key_id(key_pair), encode_config(key_pair.public_key(), STANDARD_NO_PAD));
+
+        CrossSigningKey::new(user_id.clone(), vec![usage], keys, BTreeMap::new())
+    }
+
+    fn device_keys(user_id: &UserId) -> DeviceKeys {
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            DeviceKeyId::try_from("curve25519:JLAFKJWSCS").unwrap(),
+            "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI".into(),
+        );
+        keys.insert(
+            DeviceKeyId::try_from("ed25519:JLAFKJWSCS").unwrap(),
+            "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI".into(),
+        );
+
+        DeviceKeys::new(
+            user_id.clone(),
+            device_id!("JLAFKJWSCS"),
+            vec![
+                EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+                EventEncryptionAlgorithm::MegolmV1AesSha2,
+            ],
+            keys,
+            BTreeMap::new(),
+        )
+    }
+
+    /// Creates device keys that are signed by the device's own ed25519 key.
+    fn self_signed_device_keys(user_id: &UserId) -> Raw<DeviceKeys> {
+        self_signed_device_keys_with_key_pair(user_id).0
+    }
+
+    fn self_signed_device_keys_with_key_pair(
+        user_id: &UserId,
+    ) -> (Raw<DeviceKeys>, Ed25519KeyPair) {
+        let device_key_pair = device_key_pair();
+        let device_keys =
+            sign(&device_keys_of(user_id, &device_key_pair), user_id, &device_key_pair);
+
+        (device_keys, device_key_pair)
+    }
+
+    fn device_key_pair() -> Ed25519KeyPair {
+        Ed25519KeyPair::new(&Ed25519KeyPair::generate().unwrap(), "JLAFKJWSCS".into()).unwrap()
+    }
+
+    /// Creates device keys with the public key of the given key pair.
+    fn device_keys_of(user_id: &UserId, device_key_pair: &Ed25519KeyPair) -> DeviceKeys {
+        let mut device_keys = device_keys(user_id);
+        device_keys.keys.insert(
+            DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, &device_keys.device_id),
+            encode_config(device_key_pair.public_key(), STANDARD_NO_PAD),
+        );
+
+        device_keys
+    }
+
+    /// Changes the given device keys without updating their signatures.
+    fn modified(device_keys: &Raw<DeviceKeys>, f: impl FnOnce(&mut DeviceKeys)) -> Raw<DeviceKeys> {
+        let mut device_keys = device_keys.deserialize().unwrap();
+        f(&mut device_keys);
+        Raw::from(device_keys)
+    }
+
+    fn sign<T: Serialize>(object: &T, user_id: &UserId, key_pair: &Ed25519KeyPair) -> Raw<T> {
+        sign_value(to_json_value(object).unwrap(), user_id, key_pair)
+    }
+
+    /// Signs the given JSON, which can contain fields that are not part of `T`.
+    fn sign_value<T>(object: JsonValue, user_id: &UserId, key_pair: &Ed25519KeyPair) -> Raw<T> {
+        let mut object = match to_canonical_value(object).unwrap() {
+            CanonicalJsonValue::Object(object) => object,
+            _ => unreachable!(),
+        };
+        sign_json(user_id.as_str(), key_pair, &mut object).unwrap();
+
+        Raw::from_json(to_raw_json_value(&object).unwrap())
+    }
+
+    /// Adds a field that is not part of `T` to the JSON of `object`.
+    fn with_unknown_field<T: Serialize>(object: &T) -> JsonValue {
+        let mut object = to_json_value(object).unwrap();
+        object.as_object_mut().unwrap().insert("org.example.extra".into(), json!({ "version": 2 }));
+        object
+    }
+
+    #[test]
+    fn valid_signature_chain() {
+        let user_id = user_id!("@alice:example.org");
+        let master_key_pair = cross_signing_key_pair();
+        let self_signing_key_pair = cross_signing_key_pair();
+
+        let master_key = cross_signing_key(&user_id, KeyUsage::Master, &master_key_pair);
+        let self_signing_key = sign(
+            &cross_signing_key(&user_id, KeyUsage::SelfSigning, &self_signing_key_pair),
+            &user_id,
+            &master_key_pair,
+        );
+        let device_keys = sign(&device_keys(&user_id), &user_id, &self_signing_key_pair);
+
+        assert_eq!(
+            verify_device_signature_chain(&master_key, &self_signing_key, &device_keys),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn signature_chain_with_unknown_fields() {
+        let user_id = user_id!("@alice:example.org");
+        let master_key_pair = cross_signing_key_pair();
+        let self_signing_key_pair = cross_signing_key_pair();
+
+        let master_key = cross_signing_key(&user_id, KeyUsage::Master, &master_key_pair);
+        let self_signing_key = sign_value(
+            with_unknown_field(&cross_signing_key(
+                &user_id,
+                KeyUsage::SelfSigning,
+                &self_signing_key_pair,
+            )),
+            &user_id,
+            &master_key_pair,
+        );
+        let device_keys = sign_value(
+            with_unknown_field(&device_keys(&user_id)),
+            &user_id,
+            &self_signing_key_pair,
+        );
+
+        assert_eq!(verify_cross_signing_key(&self_signing_key, &master_key), Ok(()));
+        assert_eq!(
+            verify_device_signature_chain(&master_key, &self_signing_key, &device_keys),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn device_not_signed_by_self_signing_key() {
+        let user_id = user_id!("@alice:example.org");
+        let master_key_pair = cross_signing_key_pair();
+        let self_signing_key_pair = cross_signing_key_pair();
+
+        let master_key = cross_signing_key(&user_id, KeyUsage::Master, &master_key_pair);
+        let self_signing_key = sign(
+            &cross_signing_key(&user_id, KeyUsage::SelfSigning, &self_signing_key_pair),
+            &user_id,
+            &master_key_pair,
+        );
+        let device_keys = sign(&device_keys(&user_id), &user_id, &master_key_pair);
+
+        assert!(matches!(
+            verify_device_signature_chain(&master_key, &self_signing_key, &device_keys),
+            Err(KeyVerificationError::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn self_signing_key_with_wrong_usage() {
+        let user_id = user_id!("@alice:example.org");
+        let master_key_pair = cross_signing_key_pair();
+        let user_signing_key_pair = cross_signing_key_pair();
+
+        let master_key = cross_signing_key(&user_id, KeyUsage::Master, &master_key_pair);
+        let user_signing_key = sign(
+            &cross_signing_key(&user_id, KeyUsage::UserSigning, &user_signing_key_pair),
+            &user_id,
+            &master_key_pair,
+        );
+        let device_keys = sign(&device_keys(&user_id), &user_id, &user_signing_key_pair);
+
+        assert_eq!(
+            verify_device_signature_chain(&master_key, &user_signing_key, &device_keys),
+            Err(KeyVerificationError::InvalidUsage(KeyUsage::SelfSigning))
+        );
+    }
+
+    #[test]
+    fn device_of_other_user() {
+        let user_id = user_id!("@alice:example.org");
+        let other_user_id = user_id!("@mallory:example.org");
+        let master_key_pair = cross_signing_key_pair();
+        let self_signing_key_pair = cross_signing_key_pair();
+
+        let master_key = cross_signing_key(&user_id, KeyUsage::Master, &master_key_pair);
+        let self_signing_key = sign(
+            &cross_signing_key(&user_id, KeyUsage::SelfSigning, &self_signing_key_pair),
+            &user_id,
+            &master_key_pair,
+        );
+        let device_keys = sign(&device_keys(&other_user_id), &user_id, &self_signing_key_pair);
+
+        assert_eq!(
+            verify_device_signature_chain(&master_key, &self_signing_key, &device_keys),
+            Err(KeyVerificationError::UserIdMismatch)
+        );
+    }
+
+    #[test]
+    fn valid_self_signed_device_keys() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = self_signed_device_keys(&user_id);
+
+        assert_eq!(
+            verify_device_keys(&device_keys, &user_id, device_id!("JLAFKJWSCS").as_ref()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn self_signed_device_keys_with_unknown_fields() {
+        let user_id = user_id!("@alice:example.org");
+        let device_key_pair = device_key_pair();
+        let device_keys = sign_value(
+            with_unknown_field(&device_keys_of(&user_id, &device_key_pair)),
+            &user_id,
+            &device_key_pair,
+        );
+
+        assert_eq!(
+            verify_device_keys(&device_keys, &user_id, device_id!("JLAFKJWSCS").as_ref()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn self_signed_device_keys_of_other_device() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = self_signed_device_keys(&user_id);
+
+        assert_eq!(
+            verify_device_keys(&device_keys, &user_id, device_id!("EGURVBUNJP").as_ref()),
+            Err(KeyVerificationError::DeviceIdMismatch)
+        );
+        assert_eq!(
+            verify_device_keys(
+                &device_keys,
+                &user_id!("@mallory:example.org"),
+                device_id!("JLAFKJWSCS").as_ref()
+            ),
+            Err(KeyVerificationError::UserIdMismatch)
+        );
+    }
+
+    #[test]
+    fn device_keys_with_missing_algorithm() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = modified(&self_signed_device_keys(&user_id), |device_keys| {
+            device_keys.algorithms.retain(|a| *a != EventEncryptionAlgorithm::MegolmV1AesSha2)
+        });
+
+        assert_eq!(
+            verify_device_keys(&device_keys, &user_id, device_id!("JLAFKJWSCS").as_ref()),
+            Err(KeyVerificationError::MissingAlgorithm(EventEncryptionAlgorithm::MegolmV1AesSha2))
+        );
+    }
+
+    #[test]
+    fn device_keys_with_tampered_key() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = modified(&self_signed_device_keys(&user_id), |device_keys| {
+            device_keys.keys.insert(
+                DeviceKeyId::try_from("curve25519:JLAFKJWSCS").unwrap(),
+                "wjLpTLRqbqBzLs63aYaEv2Boi6cFEbbM/sSRQ2oAKk4".into(),
+            );
+        });
+
+        assert!(matches!(
+            verify_device_keys(&device_keys, &user_id, device_id!("JLAFKJWSCS").as_ref()),
+            Err(KeyVerificationError::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn device_keys_without_signing_key() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = modified(&self_signed_device_keys(&user_id), |device_keys| {
+            device_keys.keys.remove(&DeviceKeyId::try_from("ed25519:JLAFKJWSCS").unwrap());
+        });
+
+        assert_eq!(
+            verify_device_keys(&device_keys, &user_id, device_id!("JLAFKJWSCS").as_ref()),
+            Err(KeyVerificationError::MissingPublicKey)
+        );
+    }
+
+    #[test]
+    fn valid_signed_fallback_key() {
+        let user_id = user_id!("@alice:example.org");
+        let (device_keys, device_key_pair) = self_signed_device_keys_with_key_pair(&user_id);
+        let fallback_key = sign_value(
+            to_json_value(SignedKey::new_fallback(
+                "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs".into(),
+                BTreeMap::new(),
+            ))
+            .unwrap(),
+            &user_id,
+            &device_key_pair,
+        );
+
+        assert!(matches!(
+            fallback_key.deserialize().unwrap(),
+            OneTimeKey::SignedKey(SignedKey { fallback: true, .. })
+        ));
+        assert_eq!(verify_one_time_key(&fallback_key, &device_keys.deserialize().unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn signed_key_with_explicit_fallback_flag() {
+        let user_id = user_id!("@alice:example.org");
+        let (device_keys, device_key_pair) = self_signed_device_keys_with_key_pair(&user_id);
+        let one_time_key = sign_value(
+            json!({ "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs", "fallback": false }),
+            &user_id,
+            &device_key_pair,
+        );
+
+        assert_eq!(verify_one_time_key(&one_time_key, &device_keys.deserialize().unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn signed_key_with_removed_fallback_flag() {
+        let user_id = user_id!("@alice:example.org");
+        let (device_keys, device_key_pair) = self_signed_device_keys_with_key_pair(&user_id);
+        let fallback_key: Raw<OneTimeKey> = sign_value(
+            to_json_value(SignedKey::new_fallback(
+                "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs".into(),
+                BTreeMap::new(),
+            ))
+            .unwrap(),
+            &user_id,
+            &device_key_pair,
+        );
+        let mut fallback_key = to_json_value(fallback_key.json()).unwrap();
+        fallback_key.as_object_mut().unwrap().remove("fallback");
+        let fallback_key = Raw::from_json(to_raw_json_value(&fallback_key).unwrap());
+
+        assert!(matches!(
+            verify_one_time_key(&fallback_key, &device_keys.deserialize().unwrap()),
+            Err(KeyVerificationError::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn unsigned_one_time_key() {
+        let user_id = user_id!("@alice:example.org");
+        let device_keys = self_signed_device_keys(&user_id);
+        let one_time_key =
+            Raw::from(OneTimeKey::Key("zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs".into()));
+
+        assert!(matches!(
+            verify_one_time_key(&one_time_key, &device_keys.deserialize().unwrap()),
+            Err(KeyVerificationError::Signature(_))
+        ));
+    }
+}
@@ -263,26 +263,38 @@ pub fn verify_json(
 
         let mut maybe_signature = None;
         let mut maybe_public_key = None;
+        let mut unsupported_key_id = None;
 
         for (key_id, public_key) in public_keys {
-            // Since only ed25519 is supported right now, we don't actually need to check what the
-            // algorithm is. If it split successfully, it's ed25519.
-            if split_id(key_id).is_err() {
-                break;
+            if !signature_set.contains_key(key_id) {
+                continue;
             }
 
-            if let Some(signature) = signature_set.get(key_id) {
-                maybe_signature = Some(signature);
-                maybe_public_key = Some(public_key);
-
-                break;
+            // Since only ed25519 is supported right now, signatures of any other algorithm can't
+            // be verified. They are only ignored if there is also an ed25519 signature. The
+            // version isn't validated because cross-signing keys use their public key as the
+            // version, which may contain characters that aren't allowed in server key versions.
+            if !key_id.starts_with("ed25519:") {
+                unsupported_key_id.get_or_insert(key_id);
+                continue;
             }
+
+            maybe_signature = signature_set.get(key_id);
+            maybe_public_key = Some(public_key);
+
+            break;
         }
 
-        let signature = match maybe_signature {
-            Some(CanonicalJsonValue::String(signature)) => signature,
-            Some(_) => return Err(Error::new("signature must be a string")),
-            None => {
+        let signature = match (maybe_signature, unsupported_key_id) {
+            (Some(CanonicalJsonValue::String(signature)), _) => signature,
+            (Some(_), _) => return Err(Error::new("signature must be a string")),
+            (None, Some(key_id)) => {
+                return Err(Error::new(format!(
+                    "unsupported signing algorithm for key `{}`, only ed25519 is supported",
+                    key_id
+                )))
+            }
+            (None, None) => {
                 return Err(Error::new("event is not signed with any of the given public keys"))
             }
         };
@@ -41,6 +41,13 @@
 //! To verify a signature on arbitrary JSON, use the `verify_json` function. To verify the
 //! signatures and hashes on an event, use the `verify_event` function. See the documentation for
 //! these respective functions for more details and full examples of use.
+//!
+//! # Verifying device and cross-signing keys
+//!
+//! Clients have to check the signatures of the device and cross-signing keys of other users before
+//! trusting them. To verify that device keys are signed by the device itself, use
+//! `verify_device_keys`. To verify the cross-signing signature chain of a device, from the master
+//! key over the self-signing key to the device keys, use `verify_device_signature_chain`.
 
 #![warn(rust_2018_idioms)]
 #![deny(missing_debug_implementations, missing_docs)]
@@ -52,6 +59,10 @@ use std::{
 
 use ruma_serde::{AsRefStr, DisplayAsRefStr};
 
+pub use cross_signing::{
+    verify_cross_signing_key, verify_device_keys, verify_device_keys_signed_by,
+    verify_device_signature_chain, verify_one_time_key, KeyVerificationError,
+};
 pub use functions::{
     canonical_json, content_hash, hash_and_sign_event, redact, reference_hash, sign_json,
     verify_event, verify_json,
@@ -61,6 +72,7 @@ pub use ruma_serde::{CanonicalJsonError, CanonicalJsonObject, CanonicalJsonValue
 pub use signatures::Signature;
 pub use verification::Verified;
 
+mod cross_signing;
 mod functions;
 mod keys;
 mod signatures;
@@ -177,7 +189,8 @@ mod tests {
     use serde_json::{from_str as from_json_str, to_string as to_json_string};
 
     use super::{
-        canonical_json, hash_and_sign_event, sign_json, verify_event, verify_json, Ed25519KeyPair,
+        canonical_json, hash_and_sign_event, sign_json, verify_event, verify_json,
+        CanonicalJsonValue, Ed25519KeyPair,
     };
 
     const PKCS8: &str = "\
@@ -370,6 +383,65 @@ mod tests {
         assert!(verify_json(&public_key_map, &reverse_value).is_ok());
     }
 
+    #[test]
+    fn verify_json_with_cross_signing_key_id() {
+        let key_id = format!("ed25519:{}", public_key_string());
+        let key_pair = Ed25519KeyPair::new(
+            decode_config(PKCS8, STANDARD_NO_PAD).unwrap().as_slice(),
+            public_key_string(),
+        )
+        .unwrap();
+
+        let mut value = from_json_str(r#"{ "one": 1, "two": "Two" }"#).unwrap();
+        sign_json("@alice:example.org", &key_pair, &mut value).unwrap();
+
+        let mut signature_set = BTreeMap::new();
+        signature_set.insert("curve25519:1".into(), "not an ed25519 key".into());
+        signature_set.insert(key_id, public_key_string());
+
+        let mut public_key_map = BTreeMap::new();
+        public_key_map.insert("@alice:example.org".into(), signature_set);
+
+        assert!(verify_json(&public_key_map, &value).is_ok());
+    }
+
+    #[test]
+    fn verify_json_with_unsupported_algorithm() {
+        let key_pair = Ed25519KeyPair::new(
+            decode_config(PKCS8, STANDARD_NO_PAD).unwrap().as_slice(),
+            "1".into(),
+        )
+        .unwrap();
+
+        let mut value = from_json_str(r#"{ "one": 1, "two": "Two" }"#).unwrap();
+        sign_json("domain", &key_pair, &mut value).unwrap();
+        match value.get_mut("signatures") {
+            Some(CanonicalJsonValue::Object(signatures)) => match signatures.get_mut("domain") {
+                Some(CanonicalJsonValue::Object(signature_set)) => {
+                    signature_set.insert(
+                        "ecdsa:1".into(),
+                        CanonicalJsonValue::String("not a signature".into()),
+                    );
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let mut signature_set = BTreeMap::new();
+        signature_set.insert("ecdsa:1".into(), "not an ed25519 key".into());
+        let mut public_key_map = BTreeMap::new();
+        public_key_map.insert("domain".into(), signature_set.clone());
+
+        let error = verify_json(&public_key_map, &value).unwrap_err();
+        assert!(error.to_string().contains("unsupported signing algorithm"));
+
+        signature_set.insert("ed25519:1".into(), public_key_string());
+        public_key_map.insert("domain".into(), signature_set);
+
+        assert!(verify_json(&public_key_map, &value).is_ok());
+    }
+
     #[test]
     fn fail_verify_json() {
         let value = from_json_str(r#"{"not":"empty","signatures":{"domain":"lXjsnvhVlz8t3etR+6AEJ0IT70WujeHC1CFjDDsVx0xSig1Bx7lvoi1x3j/2/GPNjQM4a2gD34UqsXFluaQEBA"}}"#).unwrap();
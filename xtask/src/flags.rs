@@ -20,6 +20,12 @@ xflags::xflags! {
         cmd ci
             optional version: String
         {}
+
+        /// Generate an OpenAPI document for the endpoints of all API crates.
+        cmd openapi
+            /// The path of the document, `target/openapi.json` by default
+            optional output: String
+        {}
     }
 }
 // generated start
@@ -35,6 +41,7 @@ pub enum XtaskCmd {
     Help(Help),
     Release(Release),
     Ci(Ci),
+    Openapi(Openapi),
 }
 
 #[derive(Debug)]
@@ -52,6 +59,11 @@ pub struct Ci {
     pub version: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct Openapi {
+    pub output: Option<String>,
+}
+
 impl Xtask {
     pub const HELP: &'static str = Self::HELP_;
 
@@ -15,9 +15,10 @@ use xshell::read_file;
 
 mod ci;
 mod flags;
+mod openapi;
 mod release;
 
-use self::{ci::CiTask, release::ReleaseTask};
+use self::{ci::CiTask, openapi::OpenApiTask, release::ReleaseTask};
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -45,6 +46,10 @@ fn try_main() -> Result<()> {
             let task = CiTask::new(ci.version, project_root);
             task.run()
         }
+        flags::XtaskCmd::Openapi(cmd) => {
+            let task = OpenApiTask::new(cmd.output, project_root);
+            task.run()
+        }
     }
 }
 
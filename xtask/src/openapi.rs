@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use serde_json::{from_str as from_json_str, Value as JsonValue};
+use xshell::{mkdir_p, pushd, write_file};
+
+use crate::{cmd, Result};
+
+/// The API crates whose endpoints are included in the document, with the features to enable.
+///
+/// The `registry` and `schema` features are always enabled.
+const API_CRATES: &[(&str, &[&str])] = &[
+    ("ruma-appservice-api", &[]),
+    ("ruma-client-api", &["unstable-pre-spec"]),
+    ("ruma-federation-api", &["unstable-pre-spec"]),
+    ("ruma-identity-service-api", &[]),
+    ("ruma-push-gateway-api", &[]),
+];
+
+/// Task to generate an OpenAPI document from the endpoints of all API crates.
+pub struct OpenApiTask {
+    /// The path of the generated document.
+    output: PathBuf,
+
+    /// The root of the workspace.
+    project_root: PathBuf,
+}
+
+impl OpenApiTask {
+    pub(crate) fn new(output: Option<String>, project_root: PathBuf) -> Self {
+        let output = output
+            .map(PathBuf::from)
+            .unwrap_or_else(|| project_root.join("target").join("openapi.json"));
+
+        Self { output, project_root }
+    }
+
+    /// Generates a program that prints the OpenAPI document for the endpoints of all API crates,
+    /// and runs it.
+    ///
+    /// The program gets the endpoints from the `endpoint_schemas()` registry of each crate.
+    pub(crate) fn run(self) -> Result<()> {
+        let gen_dir = self.project_root.join("target").join("openapi-gen");
+        mkdir_p(gen_dir.join("src"))?;
+
+        let mut dependencies = String::new();
+        let mut registries = String::new();
+
+        for (crate_name, features) in API_CRATES {
+            let features = ["registry", "schema"]
+                .iter()
+                .chain(features.iter())
+                .map(|f| format!("{:?}", f))
+                .collect::<Vec<_>>();
+            dependencies.push_str(&format!(
+                "{} = {{ path = {:?}, features = [{}] }}\n",
+                crate_name,
+                self.project_root.join(crate_name),
+                features.join(", ")
+            ));
+
+            registries.push_str(&format!(
+                "        .chain({}::endpoint_schemas())\n",
+                crate_name.replace('-', "_")
+            ));
+        }
+
+        write_file(
+            gen_dir.join("Cargo.toml"),
+            format!(
+                "[package]\n\
+                 name = \"ruma-openapi-gen\"\n\
+                 version = \"0.0.0\"\n\
+                 edition = \"2018\"\n\
+                 publish = false\n\
+                 \n\
+                 [workspace]\n\
+                 \n\
+                 [dependencies]\n\
+                 ruma-api = {{ path = {:?} }}\n\
+                 serde_json = \"1.0.61\"\n\
+                 {}",
+                self.project_root.join("ruma-api"),
+                dependencies,
+            ),
+        )?;
+
+        write_file(
+            gen_dir.join("src").join("main.rs"),
+            format!(
+                "fn main() {{\n\
+                 \x20   let endpoints: Vec<_> = std::iter::empty()\n\
+                 {}\
+                 \x20       .cloned()\n\
+                 \x20       .collect();\n\
+                 \x20   let document = ruma_api::schema::openapi_document(\"Ruma\", \"unstable\", &endpoints);\n\
+                 \x20   println!(\"{{}}\", serde_json::to_string_pretty(&document).unwrap());\n\
+                 }}\n",
+                registries,
+            ),
+        )?;
+
+        let document = {
+            let _p = pushd(&gen_dir)?;
+            cmd!("cargo run --quiet").read()?
+        };
+        let path_count = from_json_str::<JsonValue>(&document)?["paths"]
+            .as_object()
+            .map_or(0, |paths| paths.len());
+        write_file(&self.output, document)?;
+
+        println!("Wrote OpenAPI document with {} paths to {}", path_count, self.output.display());
+
+        Ok(())
+    }
+}